@@ -126,10 +126,7 @@ type initUserCLIParams = {
   ownerKeypairPath: string;
   adminKeypair: Keypair;
   replicaSet: number[];
-  replicaSetBumps: number[];
-  cn1: PublicKey;
-  cn2: PublicKey;
-  cn3: PublicKey;
+  contentNodes: PublicKey[];
 };
 
 async function initUserCLI(args: initUserCLIParams) {
@@ -141,10 +138,7 @@ async function initUserCLI(args: initUserCLIParams) {
     metadata,
     adminStoragePublicKey,
     replicaSet,
-    replicaSetBumps,
-    cn1,
-    cn2,
-    cn3
+    contentNodes
   } = args;
   const cliVars = initializeCLI(network, ownerKeypairPath);
   const handleBytesArray = getHandleBytesArray(handle);
@@ -157,17 +151,14 @@ async function initUserCLI(args: initUserCLIParams) {
     program: cliVars.program,
     ethAddress,
     replicaSet,
-    replicaSetBumps,
+    contentNodes,
     handleBytesArray,
     bumpSeed,
     metadata,
     userStorageAccount: userStorageAddress,
     baseAuthorityAccount,
     adminStorageAccount: adminStoragePublicKey,
-    adminKeypair,
-    cn1,
-    cn2,
-    cn3
+    adminKeypair
   });
 
   await cliVars.provider.connection.confirmTransaction(tx);
@@ -385,24 +376,15 @@ const main = async () => {
         )
       }))
 
-      const replicaSetBumps = [
-        userContentNodeInfo[0].bumpSeed,
-        userContentNodeInfo[1].bumpSeed,
-        userContentNodeInfo[2].bumpSeed
-      ]
-
       initUserCLI({
         ownerKeypairPath: options.ownerKeypair,
         ethAddress: options.ethAddress,
         handle: options.handle,
         adminStoragePublicKey: adminStorageKeypair.publicKey,
         adminKeypair,
-        metadata: "test",
+        metadata: randomCID(),
         replicaSet: userReplicaSet,
-        replicaSetBumps,
-        cn1: userContentNodeInfo[0].derivedAddress,
-        cn2: userContentNodeInfo[1].derivedAddress,
-        cn3: userContentNodeInfo[2].derivedAddress,
+        contentNodes: userContentNodeInfo.map((info) => info.derivedAddress),
       });
       break;
     case functionTypes.initUserSolPubkey:
@@ -414,9 +396,9 @@ const main = async () => {
         const tx = await initUserSolPubkey({
           program: cliVars.program,
           provider: cliVars.provider,
-          message: userSolKeypair.publicKey.toBytes(),
           ethPrivateKey,
           userStorageAccount: options.userStoragePubkey,
+          adminStoragePublicKey: adminStorageKeypair.publicKey,
           userSolPubkey,
         });
         await cliVars.provider.connection.confirmTransaction(tx);
@@ -8,8 +8,17 @@ import { Keypair } from "@solana/web3.js";
 import { Account } from "web3-core";
 import * as secp256k1 from "secp256k1";
 import { AudiusData } from "../target/types/audius_data";
-import { signBytes, SystemSysVarProgramKey } from "./utils";
-const { SystemProgram, Transaction, Secp256k1Program } = anchor.web3;
+import {
+  claimExpiry,
+  findAdminMultisigAddress,
+  findFollowAddress,
+  getClaimMessage,
+  RECOVERY_MESSAGE_PREFIX,
+  signBytes,
+  SystemSysVarProgramKey,
+} from "./utils";
+const { PublicKey, SystemProgram, Transaction, Secp256k1Program } =
+  anchor.web3;
 
 export const EntityTypesEnumValues = {
   track: { track: {} },
@@ -22,6 +31,16 @@ export const ManagementActions = {
   delete: { delete: {} },
 };
 
+/// Delegate permission bits, matching the DELEGATE_PERMISSION_* program constants
+export const DelegatePermissions = {
+  updateUser: 1 << 0,
+  manageEntity: 1 << 1,
+  socialAction: 1 << 2,
+  follow: 1 << 3,
+  manageDelegates: 1 << 4,
+  all: (1 << 5) - 1,
+};
+
 export const EntitySocialActions = {
   addSave: { addSave: {} },
   deleteSave: { deleteSave: {} },
@@ -29,6 +48,14 @@ export const EntitySocialActions = {
   deleteRepost: { deleteRepost: {} },
 };
 
+export const UserActions = {
+  followUser: { followUser: {} },
+  unfollowUser: { unfollowUser: {} },
+};
+
+/// Maximum number of actions in a writeSocialActions batch, matches MAX_SOCIAL_ACTION_BATCH_SIZE
+export const MAX_SOCIAL_ACTION_BATCH_SIZE = 7;
+
 type InitAdminParams = {
   provider: Provider;
   program: Program<AudiusData>;
@@ -49,17 +76,13 @@ type InitUserParams = {
   adminStorageAccount: anchor.web3.PublicKey;
   adminKeypair: anchor.web3.Keypair;
   replicaSet: number[];
-  replicaSetBumps: number[];
-  cn1: anchor.web3.PublicKey;
-  cn2: anchor.web3.PublicKey;
-  cn3: anchor.web3.PublicKey;
+  contentNodes: anchor.web3.PublicKey[];
 };
 
 type CreateUserParams = {
   provider: Provider;
   program: Program<AudiusData>;
   ethAccount: Account;
-  message: Uint8Array;
   userId: anchor.BN;
   handleBytesArray: number[];
   bumpSeed: number;
@@ -69,10 +92,11 @@ type CreateUserParams = {
   adminStoragePublicKey: anchor.web3.PublicKey;
   baseAuthorityAccount: anchor.web3.PublicKey;
   replicaSet: number[];
-  replicaSetBumps: number[];
-  cn1: anchor.web3.PublicKey;
-  cn2: anchor.web3.PublicKey;
-  cn3: anchor.web3.PublicKey;
+  contentNodes: anchor.web3.PublicKey[];
+  // Claim expiry, defaults to DEFAULT_CLAIM_TTL_SECONDS from now
+  expiresAt?: anchor.BN;
+  // Signed bytes, defaults to the claim message for the given accounts
+  message?: Uint8Array;
 };
 
 type UpdateUserParams = {
@@ -84,11 +108,20 @@ type UpdateUserParams = {
   userAuthorityKeypair: anchor.web3.Keypair;
 };
 
+/// Signatures for an admin authority held by an admin multisig
+/// The admin authority keypair signs as one of the multisig signers
+export type AdminMultisigSigners = {
+  adminMultisig: anchor.web3.PublicKey;
+  // Multisig signers other than the admin authority keypair
+  signers: anchor.web3.Keypair[];
+};
+
 type UpdateAdminParams = {
   program: Program<AudiusData>;
   isWriteEnabled: boolean;
   adminStorageAccount: anchor.web3.PublicKey;
   adminAuthorityKeypair: anchor.web3.Keypair;
+  multisigSigners?: AdminMultisigSigners;
 };
 
 type UpdateIsVerifiedParams = {
@@ -99,15 +132,53 @@ type UpdateIsVerifiedParams = {
   adminKeypair: Keypair;
   handleBytesArray: number[];
   bumpSeed: number;
+  isVerified?: boolean;
+};
+
+type UpdateUserStatusParams = {
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  userStorageAccount: anchor.web3.PublicKey;
+  handleBytesArray: number[];
+  bumpSeed: number;
+  // User authority or admin authority
+  authorityKeypair: Keypair;
+};
+
+type MigrateAccountParams = {
+  program: Program<AudiusData>;
+  account: anchor.web3.PublicKey;
+};
+
+type ChangeUserHandleParams = {
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  userStorageAccount: anchor.web3.PublicKey;
+  handleBytesArray: number[];
+  bumpSeed: number;
+  newUserStorageAccount: anchor.web3.PublicKey;
+  newHandleBytesArray: number[];
+  userAuthorityKeypair: anchor.web3.Keypair;
 };
 
 type InitUserSolPubkeyParams = {
   provider: Provider;
   program: Program<AudiusData>;
   ethPrivateKey: string;
-  message: Uint8Array;
   userSolPubkey: anchor.web3.PublicKey;
   userStorageAccount: anchor.web3.PublicKey;
+  adminStoragePublicKey: anchor.web3.PublicKey;
+  // Claim expiry, defaults to DEFAULT_CLAIM_TTL_SECONDS from now
+  expiresAt?: anchor.BN;
+  // Signed bytes, defaults to the claim message for the given accounts
+  message?: Uint8Array;
+};
+
+type RecoverUserParams = InitUserSolPubkeyParams & {
+  // Current authority epoch of the user, which the recovery is signed for
+  authorityEpoch: number;
 };
 
 export type UpdateEntityParams = {
@@ -152,6 +223,17 @@ export type DeleteEntityParams = {
   bumpSeed: number;
 };
 
+/// An append, remove or move of a playlist track
+/// Removes and moves name the track expected at the index, so stale edits are rejected
+export type PlaylistContentsAction =
+  | { append: { trackId: anchor.BN } }
+  | { remove: { index: number; trackId: anchor.BN } }
+  | { move: { from: number; to: number; trackId: anchor.BN } };
+
+export type ManagePlaylistContentsParams = DeleteEntityParams & {
+  action: PlaylistContentsAction;
+};
+
 /// Create a content node with the audius admin authority
 type CreateContentNode = {
   provider: Provider;
@@ -172,11 +254,8 @@ type UpdateUserReplicaSet = {
   adminStoragePublicKey: anchor.web3.PublicKey;
   baseAuthorityAccount: anchor.web3.PublicKey;
   replicaSet: number[];
-  replicaSetBumps: number[];
+  contentNodes: anchor.web3.PublicKey[];
   contentNodeAuthority: anchor.web3.Keypair;
-  cn1: anchor.web3.PublicKey;
-  cn2: anchor.web3.PublicKey;
-  cn3: anchor.web3.PublicKey;
   userAcct: anchor.web3.PublicKey;
   userHandle: { seed: number[]; bump: number };
 };
@@ -200,6 +279,32 @@ type EntitySocialActionArgs = {
   id: string;
 };
 
+/// A follow or unfollow in a writeSocialActions batch
+export type FollowBatchAction = {
+  userAction: typeof UserActions[keyof typeof UserActions];
+  followeeUserStorageAccount: anchor.web3.PublicKey;
+  followeeHandle: { seed: number[]; bump: number };
+};
+
+/// A save or repost in a writeSocialActions batch
+export type EntitySocialBatchAction = {
+  entitySocialAction: typeof EntitySocialActions[keyof typeof EntitySocialActions];
+  entityType: typeof EntityTypesEnumValues[keyof typeof EntityTypesEnumValues];
+  id: string;
+};
+
+type WriteSocialActionsParams = {
+  program: Program<AudiusData>;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  adminStoragePublicKey: anchor.web3.PublicKey;
+  userStorageAccountPDA: anchor.web3.PublicKey;
+  userHandle: { seed: number[]; bump: number };
+  userAuthorityKeypair: Keypair;
+  userAuthorityDelegateAccountPDA: anchor.web3.PublicKey;
+  authorityDelegationStatusAccountPDA: anchor.web3.PublicKey;
+  actions: (FollowBatchAction | EntitySocialBatchAction)[];
+};
+
 type Proposer = {
   pda: anchor.web3.PublicKey;
   authority: anchor.web3.Keypair;
@@ -216,9 +321,7 @@ type PublicCreateOrUpdateContentNode = {
   contentNodeAuthority: anchor.web3.PublicKey;
   spID: anchor.BN;
   ownerEthAddress: string;
-  proposer1: Proposer;
-  proposer2: Proposer;
-  proposer3: Proposer;
+  proposers: Proposer[];
 };
 
 /// Create a content node with proposers
@@ -226,12 +329,33 @@ type PublicDeleteContentNode = {
   provider: Provider;
   program: Program<AudiusData>;
   adminStoragePublicKey: anchor.web3.PublicKey;
-  adminAuthorityPublicKey: anchor.web3.PublicKey;
+  refundAccount: anchor.web3.PublicKey;
   baseAuthorityAccount: anchor.web3.PublicKey;
   cnDelete: Proposer;
-  proposer1: Proposer;
-  proposer2: Proposer;
-  proposer3: Proposer;
+  proposers: Proposer[];
+};
+
+/// Rotate a content node's authority, signed by its current authority
+type UpdateContentNodeAuthority = {
+  program: Program<AudiusData>;
+  adminStoragePublicKey: anchor.web3.PublicKey;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  contentNodeAcct: anchor.web3.PublicKey;
+  spID: anchor.BN;
+  contentNodeAuthority: Keypair;
+  newAuthority: anchor.web3.PublicKey;
+};
+
+/// Publish a content node's endpoint and metadata, signed by its authority
+type UpdateContentNodeProfile = {
+  program: Program<AudiusData>;
+  adminStoragePublicKey: anchor.web3.PublicKey;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  contentNodeAcct: anchor.web3.PublicKey;
+  spID: anchor.BN;
+  contentNodeAuthority: Keypair;
+  endpoint: string;
+  metadata: string;
 };
 
 /// Initialize an Audius Admin instance
@@ -256,6 +380,27 @@ export const initAdmin = async ({
   );
 };
 
+/// Admin multisig account followed by its additional signers, passed as remaining accounts
+const toAdminSignerAccounts = (multisigSigners?: AdminMultisigSigners) =>
+  multisigSigners
+    ? [
+        {
+          pubkey: multisigSigners.adminMultisig,
+          isSigner: false,
+          isWritable: false,
+        },
+        ...multisigSigners.signers.map((signer) => ({
+          pubkey: signer.publicKey,
+          isSigner: true,
+          isWritable: false,
+        })),
+      ]
+    : [];
+
+/// Replica set content node accounts, passed as remaining accounts in replica set order
+const toReplicaSetAccounts = (contentNodes: anchor.web3.PublicKey[]) =>
+  contentNodes.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+
 /// Initialize a user from the Audius Admin account
 /// No ID param because every user being 'initialized' from Admin already has an ID
 export const initUser = async ({
@@ -265,21 +410,17 @@ export const initUser = async ({
   handleBytesArray,
   bumpSeed,
   replicaSet,
-  replicaSetBumps,
+  contentNodes,
   metadata,
   userStorageAccount,
   baseAuthorityAccount,
   adminStorageAccount,
   adminKeypair,
-  cn1,
-  cn2,
-  cn3,
 }: InitUserParams) => {
   return program.rpc.initUser(
     baseAuthorityAccount,
     [...anchor.utils.bytes.hex.decode(ethAddress)],
     replicaSet,
-    replicaSetBumps,
     handleBytesArray,
     bumpSeed,
     metadata,
@@ -288,12 +429,10 @@ export const initUser = async ({
         admin: adminStorageAccount,
         payer: provider.wallet.publicKey,
         user: userStorageAccount,
-        cn1,
-        cn2,
-        cn3,
         authority: adminKeypair.publicKey,
         systemProgram: SystemProgram.programId,
       },
+      remainingAccounts: toReplicaSetAccounts(contentNodes),
       signers: [adminKeypair],
     }
   );
@@ -304,11 +443,79 @@ export const initUserSolPubkey = async ({
   provider,
   program,
   ethPrivateKey,
-  message,
   userSolPubkey,
   userStorageAccount,
+  adminStoragePublicKey,
+  expiresAt,
+  message,
 }: InitUserSolPubkeyParams) => {
-  const { signature, recoveryId } = signBytes(message, ethPrivateKey);
+  const claimExpiresAt = expiresAt ?? claimExpiry();
+  const signedMessage =
+    message ??
+    getClaimMessage({
+      programId: program.programId,
+      adminStorageAccount: adminStoragePublicKey,
+      userStorageAccount,
+      userSolPubkey,
+      expiresAt: claimExpiresAt,
+    });
+  const { signature, recoveryId } = signBytes(signedMessage, ethPrivateKey);
+
+  // Get the public key in a compressed format
+  const ethPubkey = secp256k1
+    .publicKeyCreate(anchor.utils.bytes.hex.decode(ethPrivateKey), false)
+    .slice(1);
+
+  const tx = new Transaction();
+
+  tx.add(
+    Secp256k1Program.createInstructionWithPublicKey({
+      publicKey: ethPubkey,
+      message: signedMessage,
+      recoveryId: recoveryId,
+      signature: signature,
+    })
+  );
+
+  tx.add(
+    program.instruction.initUserSol(userSolPubkey, claimExpiresAt, {
+      accounts: {
+        user: userStorageAccount,
+        audiusAdmin: adminStoragePublicKey,
+        sysvarProgram: SystemSysVarProgramKey,
+      },
+    })
+  );
+
+  return provider.send(tx);
+};
+
+/// Rotate the authority of a claimed user's account given an eth private key
+/// Delegates added before the recovery can no longer act for the user
+export const recoverUser = async ({
+  provider,
+  program,
+  ethPrivateKey,
+  userSolPubkey,
+  userStorageAccount,
+  adminStoragePublicKey,
+  authorityEpoch,
+  expiresAt,
+  message,
+}: RecoverUserParams) => {
+  const claimExpiresAt = expiresAt ?? claimExpiry();
+  const signedMessage =
+    message ??
+    getClaimMessage({
+      programId: program.programId,
+      adminStorageAccount: adminStoragePublicKey,
+      userStorageAccount,
+      userSolPubkey,
+      authorityEpoch,
+      expiresAt: claimExpiresAt,
+      prefix: RECOVERY_MESSAGE_PREFIX,
+    });
+  const { signature, recoveryId } = signBytes(signedMessage, ethPrivateKey);
 
   // Get the public key in a compressed format
   const ethPubkey = secp256k1
@@ -320,16 +527,17 @@ export const initUserSolPubkey = async ({
   tx.add(
     Secp256k1Program.createInstructionWithPublicKey({
       publicKey: ethPubkey,
-      message: message,
+      message: signedMessage,
       recoveryId: recoveryId,
       signature: signature,
     })
   );
 
   tx.add(
-    program.instruction.initUserSol(userSolPubkey, {
+    program.instruction.recoverUser(userSolPubkey, claimExpiresAt, {
       accounts: {
         user: userStorageAccount,
+        audiusAdmin: adminStoragePublicKey,
         sysvarProgram: SystemSysVarProgramKey,
       },
     })
@@ -373,35 +581,44 @@ export const updateUserReplicaSet = async ({
   adminStoragePublicKey,
   baseAuthorityAccount,
   replicaSet,
+  contentNodes,
   userAcct,
-  replicaSetBumps,
   userHandle,
   contentNodeAuthority,
-  cn1,
-  cn2,
-  cn3,
 }: UpdateUserReplicaSet) => {
   return program.rpc.updateUserReplicaSet(
     baseAuthorityAccount,
     userHandle,
     replicaSet,
-    replicaSetBumps,
     {
       accounts: {
         admin: adminStoragePublicKey,
         user: userAcct,
         cnAuthority: contentNodeAuthority.publicKey,
-        cn1,
-        cn2,
-        cn3,
         payer: provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       },
+      remainingAccounts: toReplicaSetAccounts(contentNodes),
       signers: [contentNodeAuthority],
     }
   );
 };
 
+/// sp_id of a proposer, read from its "sp_id" + u16 LE seed
+export const getProposerSpId = (proposer: Proposer) =>
+  Buffer.from(proposer.seedBump.seed).readUInt16LE(5);
+
+/// Proposer content node accounts, each followed by its signing authority, passed as remaining accounts
+export const toProposerAccounts = (proposers: Proposer[]) =>
+  proposers.flatMap((proposer) => [
+    { pubkey: proposer.pda, isSigner: false, isWritable: false },
+    {
+      pubkey: proposer.authority.publicKey,
+      isSigner: true,
+      isWritable: false,
+    },
+  ]);
+
 export const publicCreateOrUpdateContentNode = async ({
   provider,
   program,
@@ -411,15 +628,11 @@ export const publicCreateOrUpdateContentNode = async ({
   contentNodeAcct,
   ownerEthAddress,
   contentNodeAuthority,
-  proposer1,
-  proposer2,
-  proposer3,
+  proposers,
 }: PublicCreateOrUpdateContentNode) => {
   return program.rpc.publicCreateOrUpdateContentNode(
     baseAuthorityAccount,
-    { seed: [...proposer1.seedBump.seed], bump: proposer1.seedBump.bump },
-    { seed: [...proposer2.seedBump.seed], bump: proposer2.seedBump.bump },
-    { seed: [...proposer3.seedBump.seed], bump: proposer3.seedBump.bump },
+    proposers.map(getProposerSpId),
     spID.toNumber(),
     contentNodeAuthority,
     [...anchor.utils.bytes.hex.decode(ownerEthAddress)],
@@ -429,14 +642,9 @@ export const publicCreateOrUpdateContentNode = async ({
         payer: provider.wallet.publicKey,
         contentNode: contentNodeAcct,
         systemProgram: SystemProgram.programId,
-        proposer1: proposer1.pda,
-        proposer1Authority: proposer1.authority.publicKey,
-        proposer2: proposer2.pda,
-        proposer2Authority: proposer2.authority.publicKey,
-        proposer3: proposer3.pda,
-        proposer3Authority: proposer3.authority.publicKey,
       },
-      signers: [proposer1.authority, proposer2.authority, proposer3.authority],
+      remainingAccounts: toProposerAccounts(proposers),
+      signers: proposers.map((proposer) => proposer.authority),
     }
   );
 };
@@ -445,34 +653,75 @@ export const publicDeleteContentNode = async ({
   provider,
   program,
   adminStoragePublicKey,
-  adminAuthorityPublicKey,
+  refundAccount,
   baseAuthorityAccount,
   cnDelete,
-  proposer1,
-  proposer2,
-  proposer3,
+  proposers,
 }: PublicDeleteContentNode) => {
   return program.rpc.publicDeleteContentNode(
     baseAuthorityAccount,
     { seed: [...cnDelete.seedBump.seed], bump: cnDelete.seedBump.bump },
-    { seed: [...proposer1.seedBump.seed], bump: proposer1.seedBump.bump },
-    { seed: [...proposer2.seedBump.seed], bump: proposer2.seedBump.bump },
-    { seed: [...proposer3.seedBump.seed], bump: proposer3.seedBump.bump },
+    proposers.map(getProposerSpId),
     {
       accounts: {
         admin: adminStoragePublicKey,
-        adminAuthority: adminAuthorityPublicKey,
+        refundAccount,
         payer: provider.wallet.publicKey,
         contentNode: cnDelete.pda,
         systemProgram: SystemProgram.programId,
-        proposer1: proposer1.pda,
-        proposer1Authority: proposer1.authority.publicKey,
-        proposer2: proposer2.pda,
-        proposer2Authority: proposer2.authority.publicKey,
-        proposer3: proposer3.pda,
-        proposer3Authority: proposer3.authority.publicKey,
       },
-      signers: [proposer1.authority, proposer2.authority, proposer3.authority],
+      remainingAccounts: toProposerAccounts(proposers),
+      signers: proposers.map((proposer) => proposer.authority),
+    }
+  );
+};
+
+export const updateContentNodeAuthority = async ({
+  program,
+  adminStoragePublicKey,
+  baseAuthorityAccount,
+  contentNodeAcct,
+  spID,
+  contentNodeAuthority,
+  newAuthority,
+}: UpdateContentNodeAuthority) => {
+  return program.rpc.updateContentNodeAuthority(
+    baseAuthorityAccount,
+    spID.toNumber(),
+    newAuthority,
+    {
+      accounts: {
+        admin: adminStoragePublicKey,
+        contentNode: contentNodeAcct,
+        authority: contentNodeAuthority.publicKey,
+      },
+      signers: [contentNodeAuthority],
+    }
+  );
+};
+
+export const updateContentNodeProfile = async ({
+  program,
+  adminStoragePublicKey,
+  baseAuthorityAccount,
+  contentNodeAcct,
+  spID,
+  contentNodeAuthority,
+  endpoint,
+  metadata,
+}: UpdateContentNodeProfile) => {
+  return program.rpc.updateContentNodeProfile(
+    baseAuthorityAccount,
+    spID.toNumber(),
+    endpoint,
+    metadata,
+    {
+      accounts: {
+        admin: adminStoragePublicKey,
+        contentNode: contentNodeAcct,
+        authority: contentNodeAuthority.publicKey,
+      },
+      signers: [contentNodeAuthority],
     }
   );
 };
@@ -482,13 +731,9 @@ export const createUser = async ({
   baseAuthorityAccount,
   program,
   ethAccount,
-  message,
   replicaSet,
-  replicaSetBumps,
+  contentNodes,
   handleBytesArray,
-  cn1,
-  cn2,
-  cn3,
   userId,
   bumpSeed,
   metadata,
@@ -496,8 +741,23 @@ export const createUser = async ({
   userSolPubkey,
   userStorageAccount,
   adminStoragePublicKey,
+  expiresAt,
+  message,
 }: CreateUserParams) => {
-  const { signature, recoveryId } = signBytes(message, ethAccount.privateKey);
+  const claimExpiresAt = expiresAt ?? claimExpiry();
+  const signedMessage =
+    message ??
+    getClaimMessage({
+      programId: program.programId,
+      adminStorageAccount: adminStoragePublicKey,
+      userStorageAccount,
+      userSolPubkey,
+      expiresAt: claimExpiresAt,
+    });
+  const { signature, recoveryId } = signBytes(
+    signedMessage,
+    ethAccount.privateKey
+  );
 
   // Get the public key in a compressed format
   const ethPubkey = secp256k1
@@ -511,7 +771,7 @@ export const createUser = async ({
   tx.add(
     Secp256k1Program.createInstructionWithPublicKey({
       publicKey: ethPubkey,
-      message: message,
+      message: signedMessage,
       signature,
       recoveryId,
     })
@@ -521,23 +781,21 @@ export const createUser = async ({
       baseAuthorityAccount,
       [...anchor.utils.bytes.hex.decode(ethAccount.address)],
       replicaSet,
-      replicaSetBumps,
       handleBytesArray,
       bumpSeed,
       metadata,
       userId,
       userSolPubkey,
+      claimExpiresAt,
       {
         accounts: {
           payer: provider.wallet.publicKey,
           user: userStorageAccount,
-          cn1,
-          cn2,
-          cn3,
           systemProgram: SystemProgram.programId,
           sysvarProgram: SystemSysVarProgramKey,
           audiusAdmin: adminStoragePublicKey,
         },
+        remainingAccounts: toReplicaSetAccounts(contentNodes),
       }
     )
   );
@@ -566,6 +824,36 @@ export const updateUser = async ({
   });
 };
 
+/// Move a user to the PDA derived from a new handle, leaving a UserRedirect at the old PDA
+export const changeUserHandle = async ({
+  program,
+  adminStorageAccount,
+  baseAuthorityAccount,
+  userStorageAccount,
+  handleBytesArray,
+  bumpSeed,
+  newUserStorageAccount,
+  newHandleBytesArray,
+  userAuthorityKeypair,
+}: ChangeUserHandleParams) => {
+  return program.rpc.changeUserHandle(
+    baseAuthorityAccount,
+    { seed: handleBytesArray, bump: bumpSeed },
+    newHandleBytesArray,
+    {
+      accounts: {
+        audiusAdmin: adminStorageAccount,
+        user: userStorageAccount,
+        newUser: newUserStorageAccount,
+        authority: userAuthorityKeypair.publicKey,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      },
+      signers: [userAuthorityKeypair],
+    }
+  );
+};
+
 /// Update Audius Admin account
 
 export const updateAdmin = async ({
@@ -573,146 +861,432 @@ export const updateAdmin = async ({
   isWriteEnabled,
   adminStorageAccount,
   adminAuthorityKeypair,
+  multisigSigners,
 }: UpdateAdminParams) => {
   return program.rpc.updateAdmin(isWriteEnabled, {
     accounts: {
       admin: adminStorageAccount,
       adminAuthority: adminAuthorityKeypair.publicKey,
     },
-    signers: [adminAuthorityKeypair],
+    remainingAccounts: toAdminSignerAccounts(multisigSigners),
+    signers: [adminAuthorityKeypair, ...(multisigSigners?.signers ?? [])],
   });
 };
 
-/// Verify user with authenticatorKeypair
-export const updateIsVerified = async ({
-  program,
-  adminKeypair,
-  userStorageAccount,
-  verifierKeypair,
-  baseAuthorityAccount,
-  handleBytesArray,
-  bumpSeed,
-}: UpdateIsVerifiedParams) => {
-  return program.rpc.updateIsVerified(
-    baseAuthorityAccount,
-    { seed: handleBytesArray, bump: bumpSeed },
-    {
-      accounts: {
-        user: userStorageAccount,
-        audiusAdmin: adminKeypair.publicKey,
-        verifier: verifierKeypair.publicKey,
-      },
-      signers: [verifierKeypair],
-    }
-  );
+type UpdateMaxReplicaSetSizeParams = {
+  program: Program<AudiusData>;
+  maxReplicaSetSize: number;
+  adminStorageAccount: anchor.web3.PublicKey;
+  adminAuthorityKeypair: anchor.web3.Keypair;
 };
 
-/// Create a track
-export const createTrack = async ({
-  id,
+/// Set the maximum replica set length accepted for users
+export const updateMaxReplicaSetSize = async ({
   program,
-  baseAuthorityAccount,
-  userAuthorityKeypair,
-  userAuthorityDelegateAccountPDA,
-  authorityDelegationStatusAccountPDA,
-  userStorageAccountPDA,
-  metadata,
-  handleBytesArray,
+  maxReplicaSetSize,
   adminStorageAccount,
-  bumpSeed,
-}: CreateEntityParams) => {
-  return program.rpc.manageEntity(
-    baseAuthorityAccount,
-    { seed: handleBytesArray, bump: bumpSeed },
-    EntityTypesEnumValues.track,
-    ManagementActions.create,
-    id,
-    metadata,
-    {
-      accounts: {
-        audiusAdmin: adminStorageAccount,
-        user: userStorageAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
-        userAuthorityDelegate: userAuthorityDelegateAccountPDA,
-        authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-      },
-      signers: [userAuthorityKeypair],
-    }
-  );
+  adminAuthorityKeypair,
+}: UpdateMaxReplicaSetSizeParams) => {
+  return program.rpc.updateMaxReplicaSetSize(maxReplicaSetSize, {
+    accounts: {
+      admin: adminStorageAccount,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+    },
+    signers: [adminAuthorityKeypair],
+  });
 };
 
-/// Initialize a user from the Audius Admin account
+type UpdateProposerThresholdParams = {
+  program: Program<AudiusData>;
+  proposerThreshold: number;
+  adminStorageAccount: anchor.web3.PublicKey;
+  adminAuthorityKeypair: anchor.web3.Keypair;
+};
 
-export const updateTrack = async ({
+/// Set the number of content node proposers required for content node changes
+export const updateProposerThreshold = async ({
   program,
-  baseAuthorityAccount,
-  id,
-  metadata,
-  userAuthorityKeypair,
-  userStorageAccountPDA,
-  userAuthorityDelegateAccountPDA,
-  authorityDelegationStatusAccountPDA,
-  handleBytesArray,
+  proposerThreshold,
   adminStorageAccount,
-  bumpSeed,
-}: UpdateEntityParams) => {
-  return program.rpc.manageEntity(
-    baseAuthorityAccount,
-    { seed: handleBytesArray, bump: bumpSeed },
-    EntityTypesEnumValues.track,
-    ManagementActions.update,
-    id,
-    metadata,
+  adminAuthorityKeypair,
+}: UpdateProposerThresholdParams) => {
+  return program.rpc.updateProposerThreshold(proposerThreshold, {
+    accounts: {
+      admin: adminStorageAccount,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+    },
+    signers: [adminAuthorityKeypair],
+  });
+};
+
+type ProposeAdminRotationParams = {
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  adminAuthorityKeypair: anchor.web3.Keypair;
+  pendingPublicKey: anchor.web3.PublicKey;
+};
+
+type AcceptAdminRotationParams = {
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  pendingKeypair: anchor.web3.Keypair;
+  multisigSigners?: AdminMultisigSigners;
+};
+
+type InitAdminMultisigParams = {
+  provider: Provider;
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  adminAuthorityKeypair: anchor.web3.Keypair;
+  signers: anchor.web3.PublicKey[];
+  threshold: number;
+};
+
+type UpdateAdminMultisigParams = {
+  program: Program<AudiusData>;
+  adminMultisig: anchor.web3.PublicKey;
+  // Current multisig signers, at least the current threshold of them
+  signerKeypairs: anchor.web3.Keypair[];
+  signers: anchor.web3.PublicKey[];
+  threshold: number;
+};
+
+type CancelAdminRotationParams = {
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  adminAuthorityKeypair: anchor.web3.Keypair;
+};
+
+/// Propose a new Audius Admin authority
+export const proposeAdminAuthority = async ({
+  program,
+  adminStorageAccount,
+  adminAuthorityKeypair,
+  pendingPublicKey,
+}: ProposeAdminRotationParams) => {
+  return program.rpc.proposeAdminAuthority(pendingPublicKey, {
+    accounts: {
+      admin: adminStorageAccount,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+    },
+    signers: [adminAuthorityKeypair],
+  });
+};
+
+/// Accept the proposed Audius Admin authority
+export const acceptAdminAuthority = async ({
+  program,
+  adminStorageAccount,
+  pendingKeypair,
+  multisigSigners,
+}: AcceptAdminRotationParams) => {
+  return program.rpc.acceptAdminAuthority({
+    accounts: {
+      admin: adminStorageAccount,
+      pendingSigner: pendingKeypair.publicKey,
+    },
+    remainingAccounts: toAdminSignerAccounts(multisigSigners),
+    signers: [pendingKeypair, ...(multisigSigners?.signers ?? [])],
+  });
+};
+
+/// Cancel the proposed Audius Admin authority
+export const cancelAdminAuthority = async ({
+  program,
+  adminStorageAccount,
+  adminAuthorityKeypair,
+}: CancelAdminRotationParams) => {
+  return program.rpc.cancelAdminAuthority({
+    accounts: {
+      admin: adminStorageAccount,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+    },
+    signers: [adminAuthorityKeypair],
+  });
+};
+
+/// Initialize the admin multisig, to be proposed and accepted as the admin authority
+export const initAdminMultisig = async ({
+  provider,
+  program,
+  adminStorageAccount,
+  adminAuthorityKeypair,
+  signers,
+  threshold,
+}: InitAdminMultisigParams) => {
+  const [adminMultisig] = await findAdminMultisigAddress(
+    program.programId,
+    adminStorageAccount
+  );
+  const tx = await program.rpc.initAdminMultisig(signers, threshold, {
+    accounts: {
+      admin: adminStorageAccount,
+      adminMultisig,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+      payer: provider.wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    },
+    signers: [adminAuthorityKeypair],
+  });
+  return { tx, adminMultisig };
+};
+
+/// Change the admin multisig signer set, signed by its current signers
+export const updateAdminMultisig = async ({
+  program,
+  adminMultisig,
+  signerKeypairs,
+  signers,
+  threshold,
+}: UpdateAdminMultisigParams) => {
+  const [signerKeypair, ...coSignerKeypairs] = signerKeypairs;
+  return program.rpc.updateAdminMultisig(signers, threshold, {
+    accounts: {
+      adminMultisig,
+      signer: signerKeypair.publicKey,
+    },
+    remainingAccounts: coSignerKeypairs.map((coSigner) => ({
+      pubkey: coSigner.publicKey,
+      isSigner: true,
+      isWritable: false,
+    })),
+    signers: signerKeypairs,
+  });
+};
+
+/// Propose a new Audius Admin verifier
+export const proposeAdminVerifier = async ({
+  program,
+  adminStorageAccount,
+  adminAuthorityKeypair,
+  pendingPublicKey,
+}: ProposeAdminRotationParams) => {
+  return program.rpc.proposeAdminVerifier(pendingPublicKey, {
+    accounts: {
+      admin: adminStorageAccount,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+    },
+    signers: [adminAuthorityKeypair],
+  });
+};
+
+/// Accept the proposed Audius Admin verifier
+export const acceptAdminVerifier = async ({
+  program,
+  adminStorageAccount,
+  pendingKeypair,
+}: AcceptAdminRotationParams) => {
+  return program.rpc.acceptAdminVerifier({
+    accounts: {
+      admin: adminStorageAccount,
+      pendingSigner: pendingKeypair.publicKey,
+    },
+    signers: [pendingKeypair],
+  });
+};
+
+/// Cancel the proposed Audius Admin verifier
+export const cancelAdminVerifier = async ({
+  program,
+  adminStorageAccount,
+  adminAuthorityKeypair,
+}: CancelAdminRotationParams) => {
+  return program.rpc.cancelAdminVerifier({
+    accounts: {
+      admin: adminStorageAccount,
+      adminAuthority: adminAuthorityKeypair.publicKey,
+    },
+    signers: [adminAuthorityKeypair],
+  });
+};
+
+/// Verify user with authenticatorKeypair
+export const updateIsVerified = async ({
+  program,
+  adminKeypair,
+  userStorageAccount,
+  verifierKeypair,
+  baseAuthorityAccount,
+  handleBytesArray,
+  bumpSeed,
+  isVerified = true,
+}: UpdateIsVerifiedParams) => {
+  return program.rpc.updateIsVerified(
+    baseAuthorityAccount,
+    { seed: handleBytesArray, bump: bumpSeed },
+    isVerified,
     {
       accounts: {
-        audiusAdmin: adminStorageAccount,
-        user: userStorageAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
-        userAuthorityDelegate: userAuthorityDelegateAccountPDA,
-        authorityDelegationStatus: authorityDelegationStatusAccountPDA,
+        user: userStorageAccount,
+        audiusAdmin: adminKeypair.publicKey,
+        verifier: verifierKeypair.publicKey,
       },
-      signers: [userAuthorityKeypair],
+      signers: [verifierKeypair],
     }
   );
 };
 
-/// Initialize a user from the Audius Admin account
-
-export const deleteTrack = async ({
+/// Deactivate a user, signed by the user authority or the admin authority
+export const deactivateUser = async ({
   program,
-  id,
-  userStorageAccountPDA,
-  userAuthorityKeypair,
-  userAuthorityDelegateAccountPDA,
-  authorityDelegationStatusAccountPDA,
+  adminStorageAccount,
   baseAuthorityAccount,
+  userStorageAccount,
   handleBytesArray,
+  bumpSeed,
+  authorityKeypair,
+}: UpdateUserStatusParams) => {
+  return program.rpc.deactivateUser(
+    baseAuthorityAccount,
+    { seed: handleBytesArray, bump: bumpSeed },
+    {
+      accounts: {
+        audiusAdmin: adminStorageAccount,
+        user: userStorageAccount,
+        authority: authorityKeypair.publicKey,
+      },
+      signers: [authorityKeypair],
+    }
+  );
+};
+
+/// Close a deactivated user, leaving a UserTombstone at its PDA and refunding the rest of its rent to the signing authority
+export const closeUser = async ({
+  program,
   adminStorageAccount,
+  baseAuthorityAccount,
+  userStorageAccount,
+  handleBytesArray,
   bumpSeed,
-}: DeleteEntityParams) => {
-  return program.rpc.manageEntity(
+  authorityKeypair,
+}: UpdateUserStatusParams) => {
+  return program.rpc.closeUser(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntityTypesEnumValues.track,
-    ManagementActions.delete,
-    id,
-    "",
     {
       accounts: {
         audiusAdmin: adminStorageAccount,
-        user: userStorageAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
-        userAuthorityDelegate: userAuthorityDelegateAccountPDA,
-        authorityDelegationStatus: authorityDelegationStatusAccountPDA,
+        user: userStorageAccount,
+        authority: authorityKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       },
-      signers: [userAuthorityKeypair],
+      signers: [authorityKeypair],
     }
   );
 };
 
-/// Create a playlist
+/// Upgrade a program account to the current layout of its account type, the provider wallet pays any additional rent
+export const migrateAccount = async ({
+  program,
+  account,
+}: MigrateAccountParams) => {
+  return program.rpc.migrateAccount({
+    accounts: {
+      account,
+      payer: program.provider.wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    },
+  });
+};
 
-export const createPlaylist = async ({
+type UpdateAuthorityDelegationStatusParams = {
+  program: Program<AudiusData>;
+  adminStorageAccount: anchor.web3.PublicKey;
+  delegateAuthority: anchor.web3.PublicKey;
+  authorityDelegationStatusAccount: anchor.web3.PublicKey;
+  authorityDelegationStatusBump: number;
+  authorityKeypair: anchor.web3.Keypair;
+};
+
+/// Revoke an authority's delegation, signed by the delegate authority or the admin authority
+export const revokeAuthorityDelegation = async ({
+  program,
+  adminStorageAccount,
+  delegateAuthority,
+  authorityDelegationStatusAccount,
+  authorityDelegationStatusBump,
+  authorityKeypair,
+}: UpdateAuthorityDelegationStatusParams) => {
+  return program.rpc.revokeAuthorityDelegation(authorityDelegationStatusBump, {
+    accounts: {
+      admin: adminStorageAccount,
+      delegateAuthority,
+      authorityDelegationStatusPda: authorityDelegationStatusAccount,
+      authority: authorityKeypair.publicKey,
+    },
+    signers: [authorityKeypair],
+  });
+};
+
+/// Reinstate a revoked authority's delegation, signed by the admin authority or by the delegate authority if it revoked itself
+export const reinstateAuthorityDelegation = async ({
+  program,
+  adminStorageAccount,
+  delegateAuthority,
+  authorityDelegationStatusAccount,
+  authorityDelegationStatusBump,
+  authorityKeypair,
+}: UpdateAuthorityDelegationStatusParams) => {
+  return program.rpc.reinstateAuthorityDelegation(
+    authorityDelegationStatusBump,
+    {
+      accounts: {
+        admin: adminStorageAccount,
+        delegateAuthority,
+        authorityDelegationStatusPda: authorityDelegationStatusAccount,
+        authority: authorityKeypair.publicKey,
+      },
+      signers: [authorityKeypair],
+    }
+  );
+};
+
+type CloseExpiredUserAuthorityDelegateParams = {
+  program: Program<AudiusData>;
+  userAuthorityDelegate: anchor.web3.PublicKey;
+  payer: anchor.web3.PublicKey;
+};
+
+/// Close an expired user authority delegate, refunding its rent to the original payer
+export const closeExpiredUserAuthorityDelegate = async ({
+  program,
+  userAuthorityDelegate,
+  payer,
+}: CloseExpiredUserAuthorityDelegateParams) => {
+  return program.rpc.closeExpiredUserAuthorityDelegate({
+    accounts: {
+      userAuthorityDelegate,
+      payer,
+    },
+  });
+};
+
+/// Derive the PDA recording the owner of a track or playlist
+export const findEntityAddress = async ({
+  programId,
+  baseAuthorityAccount,
+  entityType,
+  id,
+}: {
+  programId: anchor.web3.PublicKey;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  entityType: typeof EntityTypesEnumValues[keyof typeof EntityTypesEnumValues];
+  id: anchor.BN;
+}) => {
+  const entityTypeSeed = "track" in entityType ? 0 : 1;
+  return PublicKey.findProgramAddress(
+    [
+      baseAuthorityAccount.toBytes().slice(0, 32),
+      Buffer.from("entity", "utf8"),
+      Buffer.from([entityTypeSeed]),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+};
+
+/// Create a track
+export const createTrack = async ({
   id,
   program,
   baseAuthorityAccount,
@@ -725,10 +1299,16 @@ export const createPlaylist = async ({
   adminStorageAccount,
   bumpSeed,
 }: CreateEntityParams) => {
+  const [entityPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.track,
+    id,
+  });
   return program.rpc.manageEntity(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntityTypesEnumValues.playlist,
+    EntityTypesEnumValues.track,
     ManagementActions.create,
     id,
     metadata,
@@ -739,31 +1319,40 @@ export const createPlaylist = async ({
         authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
+        entity: entityPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-/// Update a playlist
+/// Initialize a user from the Audius Admin account
 
-export const updatePlaylist = async ({
-  id,
+export const updateTrack = async ({
   program,
   baseAuthorityAccount,
+  id,
+  metadata,
   userAuthorityKeypair,
+  userStorageAccountPDA,
   userAuthorityDelegateAccountPDA,
   authorityDelegationStatusAccountPDA,
-  userStorageAccountPDA,
-  metadata,
   handleBytesArray,
   adminStorageAccount,
   bumpSeed,
 }: UpdateEntityParams) => {
+  const [entityPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.track,
+    id,
+  });
   return program.rpc.manageEntity(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntityTypesEnumValues.playlist,
+    EntityTypesEnumValues.track,
     ManagementActions.update,
     id,
     metadata,
@@ -774,14 +1363,18 @@ export const updatePlaylist = async ({
         authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
+        entity: entityPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-/// Delete a playlist
-export const deletePlaylist = async ({
+/// Initialize a user from the Audius Admin account
+
+export const deleteTrack = async ({
   program,
   id,
   userStorageAccountPDA,
@@ -793,10 +1386,16 @@ export const deletePlaylist = async ({
   adminStorageAccount,
   bumpSeed,
 }: DeleteEntityParams) => {
+  const [entityPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.track,
+    id,
+  });
   return program.rpc.manageEntity(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntityTypesEnumValues.playlist,
+    EntityTypesEnumValues.track,
     ManagementActions.delete,
     id,
     "",
@@ -807,262 +1406,492 @@ export const deletePlaylist = async ({
         authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
+        entity: entityPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
+      // Payer of the track, refunded its rent
+      remainingAccounts: [
+        {
+          pubkey: program.provider.wallet.publicKey,
+          isSigner: false,
+          isWritable: true,
+        },
+      ],
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-/// Get keypair from secret key
-export const getKeypairFromSecretKey = async (secretKey: Uint8Array) => {
-  return Keypair.fromSecretKey(Uint8Array.from(secretKey));
-};
+/// Create a playlist
 
-/// Social actions
-export const addTrackSave = async ({
+export const createPlaylist = async ({
+  id,
   program,
   baseAuthorityAccount,
-  userStorageAccountPDA,
+  userAuthorityKeypair,
   userAuthorityDelegateAccountPDA,
   authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
+  userStorageAccountPDA,
+  metadata,
   handleBytesArray,
+  adminStorageAccount,
   bumpSeed,
-  adminStoragePublicKey,
-  id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
+}: CreateEntityParams) => {
+  const [entityPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.playlist,
+    id,
+  });
+  return program.rpc.manageEntity(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.addSave,
-    EntityTypesEnumValues.track,
+    EntityTypesEnumValues.playlist,
+    ManagementActions.create,
     id,
+    metadata,
     {
       accounts: {
-        audiusAdmin: adminStoragePublicKey,
+        audiusAdmin: adminStorageAccount,
         user: userStorageAccountPDA,
         authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
+        entity: entityPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-export const deleteTrackSave = async ({
+/// Update a playlist
+
+export const updatePlaylist = async ({
+  id,
   program,
   baseAuthorityAccount,
-  userStorageAccountPDA,
+  userAuthorityKeypair,
   userAuthorityDelegateAccountPDA,
   authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
+  userStorageAccountPDA,
+  metadata,
   handleBytesArray,
+  adminStorageAccount,
   bumpSeed,
-  adminStoragePublicKey,
-  id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
+}: UpdateEntityParams) => {
+  const [entityPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.playlist,
+    id,
+  });
+  return program.rpc.manageEntity(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.deleteSave,
-    EntityTypesEnumValues.track,
+    EntityTypesEnumValues.playlist,
+    ManagementActions.update,
     id,
+    metadata,
     {
       accounts: {
-        audiusAdmin: adminStoragePublicKey,
+        audiusAdmin: adminStorageAccount,
         user: userStorageAccountPDA,
+        authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
+        entity: entityPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-export const addTrackRepost = async ({
+/// Delete a playlist, refunding it and its contents to the provider wallet that paid for them
+export const deletePlaylist = async ({
   program,
-  baseAuthorityAccount,
+  id,
   userStorageAccountPDA,
+  userAuthorityKeypair,
   userAuthorityDelegateAccountPDA,
   authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
+  baseAuthorityAccount,
   handleBytesArray,
+  adminStorageAccount,
   bumpSeed,
-  adminStoragePublicKey,
-  id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
+}: DeleteEntityParams) => {
+  const [entityPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.playlist,
+    id,
+  });
+  const [playlistContentsPDA] = await findPlaylistContentsAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    id,
+  });
+  return program.rpc.manageEntity(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.addRepost,
-    EntityTypesEnumValues.track,
+    EntityTypesEnumValues.playlist,
+    ManagementActions.delete,
     id,
+    "",
     {
       accounts: {
-        audiusAdmin: adminStoragePublicKey,
+        audiusAdmin: adminStorageAccount,
         user: userStorageAccountPDA,
+        authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
+        entity: entityPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
+      // Payers of the playlist and of its contents, refunded their rent
+      remainingAccounts: [
+        {
+          pubkey: program.provider.wallet.publicKey,
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: playlistContentsPDA, isSigner: false, isWritable: true },
+        {
+          pubkey: program.provider.wallet.publicKey,
+          isSigner: false,
+          isWritable: true,
+        },
+      ],
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-export const deleteTrackRepost = async ({
-  program,
+/// Derive the PDA storing the ordered track ids of a playlist
+export const findPlaylistContentsAddress = async ({
+  programId,
   baseAuthorityAccount,
-  userStorageAccountPDA,
-  userAuthorityDelegateAccountPDA,
-  authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
-  handleBytesArray,
-  bumpSeed,
-  adminStoragePublicKey,
   id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
-    baseAuthorityAccount,
-    { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.deleteRepost,
-    EntityTypesEnumValues.track,
-    id,
-    {
-      accounts: {
-        audiusAdmin: adminStoragePublicKey,
-        user: userStorageAccountPDA,
-        userAuthorityDelegate: userAuthorityDelegateAccountPDA,
-        authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
-      },
-      signers: [userAuthorityKeypair],
-    }
+}: {
+  programId: anchor.web3.PublicKey;
+  baseAuthorityAccount: anchor.web3.PublicKey;
+  id: anchor.BN;
+}) => {
+  return PublicKey.findProgramAddress(
+    [
+      baseAuthorityAccount.toBytes().slice(0, 32),
+      Buffer.from("playlist-contents", "utf8"),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
   );
 };
 
-export const addPlaylistSave = async ({
+/// Append a track to a playlist, or remove or move one of its tracks
+export const managePlaylistContents = async ({
   program,
-  baseAuthorityAccount,
+  id,
   userStorageAccountPDA,
+  userAuthorityKeypair,
   userAuthorityDelegateAccountPDA,
   authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
+  baseAuthorityAccount,
   handleBytesArray,
+  adminStorageAccount,
   bumpSeed,
-  adminStoragePublicKey,
-  id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
+  action,
+}: ManagePlaylistContentsParams) => {
+  const [playlistPDA] = await findEntityAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    entityType: EntityTypesEnumValues.playlist,
+    id,
+  });
+  const [playlistContentsPDA] = await findPlaylistContentsAddress({
+    programId: program.programId,
+    baseAuthorityAccount,
+    id,
+  });
+  return program.rpc.managePlaylistContents(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.addSave,
-    EntityTypesEnumValues.playlist,
     id,
+    action,
     {
       accounts: {
-        audiusAdmin: adminStoragePublicKey,
+        audiusAdmin: adminStorageAccount,
         user: userStorageAccountPDA,
+        authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
+        playlist: playlistPDA,
+        playlistContents: playlistContentsPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-export const deletePlaylistSave = async ({
-  program,
-  baseAuthorityAccount,
-  userStorageAccountPDA,
-  userAuthorityDelegateAccountPDA,
-  authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
-  handleBytesArray,
-  bumpSeed,
-  adminStoragePublicKey,
+/// Get keypair from secret key
+export const getKeypairFromSecretKey = async (secretKey: Uint8Array) => {
+  return Keypair.fromSecretKey(Uint8Array.from(secretKey));
+};
+
+/// Derive the PDA recording a user's save or repost of an entity
+export const findSocialActionAddress = async ({
+  programId,
+  userStorageAccount,
+  entityType,
+  entitySocialAction,
   id,
-}: EntitySocialActionArgs) => {
+}: {
+  programId: anchor.web3.PublicKey;
+  userStorageAccount: anchor.web3.PublicKey;
+  entityType: typeof EntityTypesEnumValues[keyof typeof EntityTypesEnumValues];
+  entitySocialAction: typeof EntitySocialActions[keyof typeof EntitySocialActions];
+  id: string;
+}) => {
+  const entityTypeSeed = "track" in entityType ? 0 : 1;
+  const socialActionKindSeed =
+    "addSave" in entitySocialAction || "deleteSave" in entitySocialAction
+      ? 0
+      : 1;
+  return PublicKey.findProgramAddress(
+    [
+      Buffer.from("social-action", "utf8"),
+      userStorageAccount.toBytes().slice(0, 32),
+      Buffer.from([entityTypeSeed]),
+      Buffer.from([socialActionKindSeed]),
+      Buffer.from(id, "utf8"),
+    ],
+    programId
+  );
+};
+
+/// Whether a social action removes an existing save or repost
+const isDeleteSocialAction = (
+  entitySocialAction: typeof EntitySocialActions[keyof typeof EntitySocialActions]
+) =>
+  "deleteSave" in entitySocialAction || "deleteRepost" in entitySocialAction;
+
+/// Social actions
+const writeEntitySocialAction = async (
+  {
+    program,
+    baseAuthorityAccount,
+    userStorageAccountPDA,
+    userAuthorityDelegateAccountPDA,
+    authorityDelegationStatusAccountPDA,
+    userAuthorityKeypair,
+    handleBytesArray,
+    bumpSeed,
+    adminStoragePublicKey,
+    id,
+  }: EntitySocialActionArgs,
+  entitySocialAction: typeof EntitySocialActions[keyof typeof EntitySocialActions],
+  entityType: typeof EntityTypesEnumValues[keyof typeof EntityTypesEnumValues]
+) => {
+  const [socialActionPDA] = await findSocialActionAddress({
+    programId: program.programId,
+    userStorageAccount: userStorageAccountPDA,
+    entityType,
+    entitySocialAction,
+    id,
+  });
   return program.rpc.writeEntitySocialAction(
     baseAuthorityAccount,
     { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.deleteSave,
-    EntityTypesEnumValues.playlist,
+    entitySocialAction,
+    entityType,
     id,
     {
       accounts: {
         audiusAdmin: adminStoragePublicKey,
         user: userStorageAccountPDA,
+        authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
+        socialAction: socialActionPDA,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
+      // Deletes refund the rent of the social action to the provider wallet
+      remainingAccounts: isDeleteSocialAction(entitySocialAction)
+        ? [
+            {
+              pubkey: program.provider.wallet.publicKey,
+              isSigner: false,
+              isWritable: true,
+            },
+          ]
+        : [],
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-export const addPlaylistRepost = async ({
+/// Apply a batch of follows, unfollows, saves and reposts for one user in a single instruction
+/// The accounts of each action are derived and passed in order as remaining accounts
+export const writeSocialActions = async ({
   program,
   baseAuthorityAccount,
+  adminStoragePublicKey,
   userStorageAccountPDA,
+  userHandle,
+  userAuthorityKeypair,
   userAuthorityDelegateAccountPDA,
   authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
-  handleBytesArray,
-  bumpSeed,
-  adminStoragePublicKey,
-  id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
+  actions,
+}: WriteSocialActionsParams) => {
+  const args = [];
+  const remainingAccounts = [];
+  for (const action of actions) {
+    if ("userAction" in action) {
+      const [followPDA] = await findFollowAddress(
+        program.programId,
+        userStorageAccountPDA,
+        action.followeeUserStorageAccount
+      );
+      args.push({
+        follow: {
+          userAction: action.userAction,
+          followeeHandle: action.followeeHandle,
+        },
+      });
+      remainingAccounts.push(
+        {
+          pubkey: action.followeeUserStorageAccount,
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: followPDA, isSigner: false, isWritable: true }
+      );
+      // Unfollows refund the rent of the follow to the provider wallet
+      if ("unfollowUser" in action.userAction) {
+        remainingAccounts.push({
+          pubkey: program.provider.wallet.publicKey,
+          isSigner: false,
+          isWritable: true,
+        });
+      }
+    } else {
+      const [socialActionPDA] = await findSocialActionAddress({
+        programId: program.programId,
+        userStorageAccount: userStorageAccountPDA,
+        entityType: action.entityType,
+        entitySocialAction: action.entitySocialAction,
+        id: action.id,
+      });
+      args.push({
+        entitySocialAction: {
+          entitySocialAction: action.entitySocialAction,
+          entityType: action.entityType,
+          id: action.id,
+        },
+      });
+      remainingAccounts.push({
+        pubkey: socialActionPDA,
+        isSigner: false,
+        isWritable: true,
+      });
+      // Deletes refund the rent of the social action to the provider wallet
+      if (isDeleteSocialAction(action.entitySocialAction)) {
+        remainingAccounts.push({
+          pubkey: program.provider.wallet.publicKey,
+          isSigner: false,
+          isWritable: true,
+        });
+      }
+    }
+  }
+
+  return program.rpc.writeSocialActions(
     baseAuthorityAccount,
-    { seed: handleBytesArray, bump: bumpSeed },
-    EntitySocialActions.addRepost,
-    EntityTypesEnumValues.playlist,
-    id,
+    userHandle,
+    args,
     {
       accounts: {
         audiusAdmin: adminStoragePublicKey,
         user: userStorageAccountPDA,
+        authority: userAuthorityKeypair.publicKey,
         userAuthorityDelegate: userAuthorityDelegateAccountPDA,
         authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       },
+      remainingAccounts,
       signers: [userAuthorityKeypair],
     }
   );
 };
 
-export const deletePlaylistRepost = async ({
-  program,
-  baseAuthorityAccount,
-  userStorageAccountPDA,
-  userAuthorityDelegateAccountPDA,
-  authorityDelegationStatusAccountPDA,
-  userAuthorityKeypair,
-  handleBytesArray,
-  bumpSeed,
-  adminStoragePublicKey,
-  id,
-}: EntitySocialActionArgs) => {
-  return program.rpc.writeEntitySocialAction(
-    baseAuthorityAccount,
-    { seed: handleBytesArray, bump: bumpSeed },
+export const addTrackSave = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.addSave,
+    EntityTypesEnumValues.track
+  );
+};
+
+export const deleteTrackSave = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.deleteSave,
+    EntityTypesEnumValues.track
+  );
+};
+
+export const addTrackRepost = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.addRepost,
+    EntityTypesEnumValues.track
+  );
+};
+
+export const deleteTrackRepost = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
     EntitySocialActions.deleteRepost,
-    EntityTypesEnumValues.playlist,
-    id,
-    {
-      accounts: {
-        audiusAdmin: adminStoragePublicKey,
-        user: userStorageAccountPDA,
-        userAuthorityDelegate: userAuthorityDelegateAccountPDA,
-        authorityDelegationStatus: authorityDelegationStatusAccountPDA,
-        authority: userAuthorityKeypair.publicKey,
-      },
-      signers: [userAuthorityKeypair],
-    }
+    EntityTypesEnumValues.track
+  );
+};
+
+export const addPlaylistSave = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.addSave,
+    EntityTypesEnumValues.playlist
+  );
+};
+
+export const deletePlaylistSave = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.deleteSave,
+    EntityTypesEnumValues.playlist
+  );
+};
+
+export const addPlaylistRepost = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.addRepost,
+    EntityTypesEnumValues.playlist
+  );
+};
+
+export const deletePlaylistRepost = async (args: EntitySocialActionArgs) => {
+  return writeEntitySocialAction(
+    args,
+    EntitySocialActions.deleteRepost,
+    EntityTypesEnumValues.playlist
   );
 };
@@ -11,6 +11,55 @@ export const SystemSysVarProgramKey = new PublicKey(
   "Sysvar1nstructions1111111111111111111111111"
 );
 
+/// Prefix of the claim messages signed by a user's eth key, matches CLAIM_MESSAGE_PREFIX
+export const CLAIM_MESSAGE_PREFIX = Buffer.from("audius-data-claim");
+
+/// Prefix of the recovery messages signed by a user's eth key, matches RECOVERY_MESSAGE_PREFIX
+export const RECOVERY_MESSAGE_PREFIX = Buffer.from("audius-data-recovery");
+
+/// Default number of seconds a claim message is valid for
+export const DEFAULT_CLAIM_TTL_SECONDS = 60 * 60;
+
+/// Message signed by a user's eth key to set the Solana authority of their account
+/// Matches the layout of ClaimMessage::to_bytes in the program, recoveries
+/// are signed under RECOVERY_MESSAGE_PREFIX at the user's current epoch
+export const getClaimMessage = ({
+  programId,
+  adminStorageAccount,
+  userStorageAccount,
+  userSolPubkey,
+  authorityEpoch = 0,
+  expiresAt,
+  prefix = CLAIM_MESSAGE_PREFIX,
+}: {
+  programId: anchor.web3.PublicKey;
+  adminStorageAccount: anchor.web3.PublicKey;
+  userStorageAccount: anchor.web3.PublicKey;
+  userSolPubkey: anchor.web3.PublicKey;
+  authorityEpoch?: number;
+  expiresAt: anchor.BN;
+  prefix?: Buffer;
+}) => {
+  const authorityEpochBuffer = Buffer.alloc(4);
+  authorityEpochBuffer.writeUInt32LE(authorityEpoch);
+  return Uint8Array.from(
+    Buffer.concat([
+      prefix,
+      programId.toBuffer(),
+      adminStorageAccount.toBuffer(),
+      userStorageAccount.toBuffer(),
+      userSolPubkey.toBuffer(),
+      authorityEpochBuffer,
+      expiresAt.toArrayLike(Buffer, "le", 8),
+    ])
+  );
+};
+
+/// Unix timestamp `ttlSeconds` from now, used as the expiry of claim messages
+export const claimExpiry = (ttlSeconds = DEFAULT_CLAIM_TTL_SECONDS) => {
+  return new anchor.BN(Math.floor(Date.now() / 1000) + ttlSeconds);
+};
+
 /// Convert a string input to output array of Uint8
 export const ethAddressToArray = (ethAddress: string) => {
   const strippedEthAddress = ethAddress.replace("0x", "");
@@ -92,15 +141,18 @@ export const randomString = (size: number) => {
 };
 
 /// Generate random anchor BN id
+/// Entity ids are unique on chain so the range is kept wide to avoid collisions between tests
 export const randomId = () => {
-  return new anchor.BN(Math.floor(Math.random() * 10000));
+  return new anchor.BN(Math.floor(Math.random() * Number.MAX_SAFE_INTEGER));
 };
 
-/// Generate mock CID by appending `Qm` with a rand string
+/// Random CIDv0, the base58 encoding of a sha2-256 multihash of random bytes
 export const randomCID = () => {
-  const randomSuffix = randomString(44);
-  const cid = `Qm${randomSuffix}`;
-  return cid;
+  const multihash = Buffer.concat([
+    Buffer.from([0x12, 0x20]),
+    randomBytes(32),
+  ]);
+  return anchor.utils.bytes.bs58.encode(multihash);
 };
 
 /// Derive a program address with pubkey as the seed
@@ -168,3 +220,55 @@ export const getContentNode = async (
     derivedAddress,
   };
 };
+
+/// Fetch the content nodes of a replica set in replica set order, with the endpoints and metadata
+/// published by their authorities. Content nodes that do not exist are returned as null.
+export const getReplicaSetContentNodes = async (
+  program: anchor.Program<AudiusData>,
+  adminStoragePublicKey: anchor.web3.PublicKey,
+  replicaSet: number[]
+) => {
+  return Promise.all(
+    replicaSet.map(async (spId) => {
+      const { derivedAddress } = await getContentNode(
+        program,
+        adminStoragePublicKey,
+        spId.toString()
+      );
+      const account = await program.account.contentNode.fetchNullable(
+        derivedAddress
+      );
+      return account && { spId, pda: derivedAddress, ...account };
+    })
+  );
+};
+
+/// Derive the Follow PDA recording that follower user storage account follows followee
+export const findFollowAddress = async (
+  programId: anchor.web3.PublicKey,
+  followerUserStorage: anchor.web3.PublicKey,
+  followeeUserStorage: anchor.web3.PublicKey
+) => {
+  return PublicKey.findProgramAddress(
+    [
+      Buffer.from("follow", "utf8"),
+      followerUserStorage.toBytes().slice(0, 32),
+      followeeUserStorage.toBytes().slice(0, 32),
+    ],
+    programId
+  );
+};
+
+/// Admin multisig PDA derived from the admin storage account
+export const findAdminMultisigAddress = async (
+  programId: anchor.web3.PublicKey,
+  adminStorageAccount: anchor.web3.PublicKey
+) => {
+  return PublicKey.findProgramAddress(
+    [
+      Buffer.from("admin-multisig", "utf8"),
+      adminStorageAccount.toBytes().slice(0, 32),
+    ],
+    programId
+  );
+};
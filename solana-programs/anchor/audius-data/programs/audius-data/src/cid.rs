@@ -0,0 +1,155 @@
+//! Validation of the metadata CIDs logged by user and entity instructions
+//! Accepts CIDv0 (base58btc sha2-256 multihash, `Qm...`) and CIDv1 encoded with the base32 (`b`)
+//! or base58btc (`z`) multibase prefixes.
+use crate::{constants::MAX_METADATA_LENGTH, error::ErrorCode};
+use anchor_lang::prelude::*;
+
+/// Multihash code of sha2-256, the only hash function of CIDv0
+const SHA2_256_CODE: u8 = 0x12;
+/// Digest length of sha2-256
+const SHA2_256_LENGTH: u8 = 32;
+/// String length of a CIDv0
+const CID_V0_LENGTH: usize = 46;
+/// Lowercase RFC 4648 base32 alphabet of the `b` multibase prefix
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Reject metadata that is empty, longer than MAX_METADATA_LENGTH or not a CIDv0 or CIDv1
+pub fn validate_metadata_cid(metadata: &str) -> Result<()> {
+    if metadata.is_empty() || metadata.len() > MAX_METADATA_LENGTH || !is_valid_cid(metadata) {
+        return Err(ErrorCode::InvalidMetadata.into());
+    }
+    Ok(())
+}
+
+/// Whether `cid` is a CIDv0 or a CIDv1 with a supported multibase prefix
+pub fn is_valid_cid(cid: &str) -> bool {
+    if cid.len() == CID_V0_LENGTH && cid.starts_with("Qm") {
+        return match bs58::decode(cid).into_vec() {
+            Ok(bytes) => {
+                bytes.len() == 2 + SHA2_256_LENGTH as usize
+                    && bytes[0] == SHA2_256_CODE
+                    && bytes[1] == SHA2_256_LENGTH
+            }
+            Err(_) => false,
+        };
+    }
+
+    let bytes = match cid.as_bytes().split_first() {
+        Some((b'b', encoded)) => decode_base32(encoded),
+        Some((b'z', encoded)) => bs58::decode(encoded).into_vec().ok(),
+        _ => None,
+    };
+    match bytes {
+        Some(bytes) => is_valid_cid_v1(&bytes),
+        None => false,
+    }
+}
+
+/// Whether `bytes` is a binary CIDv1: version, content codec and a multihash covering the remaining bytes
+fn is_valid_cid_v1(bytes: &[u8]) -> bool {
+    let mut rest = bytes;
+    let version = read_varint(&mut rest);
+    let codec = read_varint(&mut rest);
+    let hash_code = read_varint(&mut rest);
+    let digest_length = read_varint(&mut rest);
+    match (version, codec, hash_code, digest_length) {
+        (Some(1), Some(_), Some(_), Some(digest_length)) => {
+            digest_length > 0 && digest_length == rest.len() as u64
+        }
+        _ => false,
+    }
+}
+
+/// Read an unsigned varint from the front of `bytes`, advancing past it
+/// Returns None if the varint is truncated or longer than the 9 bytes allowed by the multiformats spec
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value: u64 = 0;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[i + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Decode unpadded lowercase base32, returning None on characters outside the alphabet
+/// or trailing bits that do not fit a whole byte
+fn decode_base32(encoded: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for character in encoded {
+        let value = BASE32_ALPHABET.iter().position(|c| c == character)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // Leftover bits are padding and must be zero
+    if bits >= 5 || buffer != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    const CID_V1_BASE32: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    const CID_V1_BASE58: &str = "zdj7WWeQ43G6JJvLWQWZpyHuAMq6uYWRjkBXFad11vE2LHhQ7";
+
+    #[test]
+    fn accepts_valid_cids() {
+        for cid in [CID_V0, CID_V1_BASE32, CID_V1_BASE58] {
+            assert!(validate_metadata_cid(cid).is_ok(), "{}", cid);
+        }
+    }
+
+    #[test]
+    fn rejects_empty_and_oversized_metadata() {
+        assert!(validate_metadata_cid("").is_err());
+        let oversized = format!("b{}", "a".repeat(MAX_METADATA_LENGTH));
+        assert!(validate_metadata_cid(&oversized).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_cid_v0() {
+        // Characters outside the base58 alphabet
+        assert!(!is_valid_cid(&format!("Qm{}", "0".repeat(44))));
+        // Truncated
+        assert!(!is_valid_cid(&CID_V0[..45]));
+        // Valid base58 that does not decode to a sha2-256 multihash
+        assert!(!is_valid_cid(
+            "QmzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzZ"
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_cid_v1() {
+        // Unsupported multibase prefix
+        assert!(!is_valid_cid(&format!("f{}", &CID_V1_BASE32[1..])));
+        // Characters outside the base32 alphabet
+        assert!(!is_valid_cid(&CID_V1_BASE32.to_uppercase()[..]));
+        // Digest shorter than its declared length
+        assert!(!is_valid_cid(&CID_V1_BASE32[..CID_V1_BASE32.len() - 8]));
+        // Version other than 1
+        assert!(!is_valid_cid("baaaa"));
+        assert!(!is_valid_cid("not a cid"));
+    }
+
+    #[test]
+    fn reads_multi_byte_varints() {
+        let mut bytes: &[u8] = &[0x80, 0x01, 0xff];
+        assert_eq!(read_varint(&mut bytes), Some(128));
+        assert_eq!(bytes, &[0xff]);
+        let mut truncated: &[u8] = &[0x80];
+        assert_eq!(read_varint(&mut truncated), None);
+    }
+}
@@ -0,0 +1,210 @@
+//! Messages signed with a user's Ethereum key to set the Solana authority of their account
+//! A claim is bound to the program, admin, user PDA and authority epoch it was signed for, and expires,
+//! so a signature cannot be replayed against another deployment, admin or user, after a recovery,
+//! or after its expiry.
+use crate::{
+    constants::{CLAIM_MESSAGE_PREFIX, RECOVERY_MESSAGE_PREFIX, SECP_INSTRUCTION_INDEX},
+    error::ErrorCode,
+    secp::verify_secp_instruction,
+};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+/// Purpose of a signed claim message, each signed under its own prefix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClaimKind {
+    // First claim of a user initialized by the admin, signed under CLAIM_MESSAGE_PREFIX
+    Claim,
+    // Rotation of a claimed user's authority, signed under RECOVERY_MESSAGE_PREFIX
+    Recovery,
+}
+
+impl ClaimKind {
+    pub fn prefix(self) -> &'static [u8] {
+        match self {
+            ClaimKind::Claim => CLAIM_MESSAGE_PREFIX,
+            ClaimKind::Recovery => RECOVERY_MESSAGE_PREFIX,
+        }
+    }
+}
+
+/// Claim of a user account by a Solana authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimMessage {
+    pub program_id: Pubkey,
+    // Audius admin account the user PDA is derived from
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    // Solana authority set on the user account
+    pub authority: Pubkey,
+    // Authority epoch of the user when the claim was signed
+    pub authority_epoch: u32,
+    // Unix timestamp after which the claim is rejected
+    pub expires_at: i64,
+}
+
+impl ClaimMessage {
+    /// Bytes signed with the user's Ethereum key, the prefix of `kind` followed by the borsh serialized claim
+    pub fn to_bytes(&self, kind: ClaimKind) -> Vec<u8> {
+        let mut bytes = kind.prefix().to_vec();
+        bytes.extend(self.try_to_vec().unwrap());
+        bytes
+    }
+
+    /// Parse signed bytes, returning None unless they are exactly a claim prefixed for `kind`
+    pub fn try_from_bytes(bytes: &[u8], kind: ClaimKind) -> Option<Self> {
+        bytes
+            .strip_prefix(kind.prefix())
+            .and_then(|claim| ClaimMessage::try_from_slice(claim).ok())
+    }
+}
+
+/// Validate that the signed `message` is exactly a claim of `kind` for `expected`,
+/// and that its expiry has not passed at `now`
+pub fn validate_claim_message(
+    message: &[u8],
+    kind: ClaimKind,
+    expected: &ClaimMessage,
+    now: i64,
+) -> Result<()> {
+    if now > expected.expires_at {
+        return Err(ErrorCode::ClaimExpired.into());
+    }
+    let claim = ClaimMessage::try_from_bytes(message, kind).ok_or(ErrorCode::Unauthorized)?;
+    if claim != *expected {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    Ok(())
+}
+
+/// Verify that the secp instruction preceding the current instruction carries a claim of `kind`
+/// for `expected`, signed by `eth_address`
+pub fn verify_user_claim(
+    instructions_sysvar: &AccountInfo,
+    kind: ClaimKind,
+    eth_address: &[u8; 20],
+    expected: &ClaimMessage,
+    now: i64,
+) -> Result<()> {
+    // Instruction must contain at least one prior
+    let index_current_instruction =
+        sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
+    if index_current_instruction <= SECP_INSTRUCTION_INDEX as u16 {
+        return Err(ErrorCode::SignatureVerification.into());
+    }
+
+    let secp_signature = verify_secp_instruction(instructions_sysvar, SECP_INSTRUCTION_INDEX)?;
+    if secp_signature.eth_address != *eth_address {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    validate_claim_message(&secp_signature.message, kind, expected, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_650_000_000;
+
+    fn claim() -> ClaimMessage {
+        ClaimMessage {
+            program_id: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            authority_epoch: 3,
+            expires_at: NOW + 60,
+        }
+    }
+
+    fn validate(expected: &ClaimMessage, message: &[u8]) -> Result<()> {
+        validate_claim_message(message, ClaimKind::Claim, expected, NOW)
+    }
+
+    #[test]
+    fn lays_out_fields_in_order() {
+        let claim = claim();
+        for kind in [ClaimKind::Claim, ClaimKind::Recovery] {
+            let bytes = claim.to_bytes(kind);
+            let prefix = kind.prefix().len();
+            assert_eq!(bytes.len(), prefix + 4 * 32 + 4 + 8);
+            assert_eq!(&bytes[..prefix], kind.prefix());
+            assert_eq!(&bytes[prefix..prefix + 32], claim.program_id.as_ref());
+            assert_eq!(&bytes[prefix + 32..prefix + 64], claim.admin.as_ref());
+            assert_eq!(&bytes[prefix + 64..prefix + 96], claim.user.as_ref());
+            assert_eq!(&bytes[prefix + 96..prefix + 128], claim.authority.as_ref());
+            assert_eq!(
+                &bytes[prefix + 128..prefix + 132],
+                &claim.authority_epoch.to_le_bytes()
+            );
+            assert_eq!(&bytes[prefix + 132..], &claim.expires_at.to_le_bytes());
+            assert_eq!(
+                ClaimMessage::try_from_bytes(&bytes, kind),
+                Some(claim.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_unexpired_claim() {
+        let claim = claim();
+        assert!(validate(&claim, &claim.to_bytes(ClaimKind::Claim)).is_ok());
+        let mut expiring = claim;
+        expiring.expires_at = NOW;
+        assert!(validate(&expiring, &expiring.to_bytes(ClaimKind::Claim)).is_ok());
+    }
+
+    #[test]
+    fn rejects_expired_claim() {
+        let mut claim = claim();
+        claim.expires_at = NOW - 1;
+        assert!(validate(&claim, &claim.to_bytes(ClaimKind::Claim)).is_err());
+    }
+
+    #[test]
+    fn rejects_claim_of_other_kind() {
+        let claim = claim();
+        assert!(validate(&claim, &claim.to_bytes(ClaimKind::Recovery)).is_err());
+        assert!(validate_claim_message(
+            &claim.to_bytes(ClaimKind::Claim),
+            ClaimKind::Recovery,
+            &claim,
+            NOW
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_claim_for_other_fields() {
+        let expected = claim();
+        let mut program_id = expected.clone();
+        program_id.program_id = Pubkey::new_unique();
+        let mut admin = expected.clone();
+        admin.admin = Pubkey::new_unique();
+        let mut user = expected.clone();
+        user.user = Pubkey::new_unique();
+        let mut authority = expected.clone();
+        authority.authority = Pubkey::new_unique();
+        let mut authority_epoch = expected.clone();
+        authority_epoch.authority_epoch -= 1;
+        let mut expires_at = expected.clone();
+        expires_at.expires_at += 1;
+        for signed in [program_id, admin, user, authority, authority_epoch, expires_at] {
+            assert!(validate(&expected, &signed.to_bytes(ClaimKind::Claim)).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        let claim = claim();
+        let bytes = claim.to_bytes(ClaimKind::Claim);
+        // Bare authority pubkey, the message signed before claims were introduced
+        assert!(validate(&claim, claim.authority.as_ref()).is_err());
+        // Missing prefix
+        assert!(validate(&claim, &bytes[CLAIM_MESSAGE_PREFIX.len()..]).is_err());
+        // Truncated and trailing bytes
+        assert!(validate(&claim, &bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(validate(&claim, &trailing).is_err());
+    }
+}
@@ -0,0 +1,2040 @@
+//! Client helpers for the audius-data program, the Rust counterpart of lib/lib.ts
+//! Derives the program addresses of every account type, builds each instruction with the accounts
+//! it validates, and decodes program accounts. Instructions carrying an Ethereum signature
+//! (init_user_sol, recover_user and create_user) must follow the secp256k1 instruction at
+//! SECP_INSTRUCTION_INDEX, built over the bytes of a ClaimMessage.
+use crate::{
+    accounts, constants::*, instruction, AdminMultisig, AudiusAdmin, AuthorityDelegationStatus,
+    ContentNode, Entity, EntitySocialAction, EntitySocialActionValues, EntityTypes, Follow,
+    ManagementActions, PlaylistContents, PlaylistContentsAction, ProposerSeedBump, SocialAction,
+    SocialActionKinds, User, UserAction, UserAuthorityDelegate, UserHandle, UserRedirect,
+    UserTombstone,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    Discriminator, InstructionData,
+};
+
+/// Base PDA derived from the admin account, the base of user, content node, entity and playlist contents PDAs
+pub fn find_base_address(program_id: &Pubkey, admin: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&admin.to_bytes()[..32]], program_id)
+}
+
+/// Handle seed of a user PDA, the utf-8 bytes of the handle truncated or zero padded to 32 bytes
+pub fn handle_seed(handle: &str) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    let bytes = handle.as_bytes();
+    let len = bytes.len().min(seed.len());
+    seed[..len].copy_from_slice(&bytes[..len]);
+    seed
+}
+
+/// User PDA derived from the base and a handle seed
+pub fn find_user_address(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    handle_seed: &[u8; 32],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&base.to_bytes()[..32], handle_seed.as_ref()], program_id)
+}
+
+/// Seed of a content node PDA, CONTENT_NODE_SEED_PREFIX followed by the little endian sp_id
+pub fn content_node_seed(sp_id: u16) -> [u8; 7] {
+    let mut seed = [0u8; 7];
+    seed[..CONTENT_NODE_SEED_PREFIX.len()].copy_from_slice(CONTENT_NODE_SEED_PREFIX);
+    seed[CONTENT_NODE_SEED_PREFIX.len()..].copy_from_slice(&sp_id.to_le_bytes());
+    seed
+}
+
+/// Content node PDA derived from the base and sp_id
+pub fn find_content_node_address(program_id: &Pubkey, base: &Pubkey, sp_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[&base.to_bytes()[..32], &content_node_seed(sp_id)],
+        program_id,
+    )
+}
+
+/// AdminMultisig PDA derived from the admin account
+pub fn find_admin_multisig_address(program_id: &Pubkey, admin: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_MULTISIG_SEED, admin.as_ref()], program_id)
+}
+
+/// UserAuthorityDelegate PDA derived from the user and delegate authority
+pub fn find_user_authority_delegate_address(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    delegate_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[&user.to_bytes()[..32], &delegate_authority.to_bytes()[..32]],
+        program_id,
+    )
+}
+
+/// AuthorityDelegationStatus PDA derived from the delegate authority
+pub fn find_authority_delegation_status_address(
+    program_id: &Pubkey,
+    delegate_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            AUTHORITY_DELEGATION_STATUS_SEED,
+            delegate_authority.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Follow PDA derived from the follower and followee user accounts
+pub fn find_follow_address(
+    program_id: &Pubkey,
+    follower: &Pubkey,
+    followee: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FOLLOW_SEED, follower.as_ref(), followee.as_ref()],
+        program_id,
+    )
+}
+
+/// Entity PDA derived from the base, entity type and id
+pub fn find_entity_address(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    entity_type: &EntityTypes,
+    id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            &base.to_bytes()[..32],
+            ENTITY_SEED,
+            &[entity_type.clone() as u8],
+            &id.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// PlaylistContents PDA derived from the base and playlist id
+pub fn find_playlist_contents_address(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    playlist_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            &base.to_bytes()[..32],
+            PLAYLIST_CONTENTS_SEED,
+            &playlist_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// EntitySocialAction PDA derived from the user, entity type, social action kind and entity id
+pub fn find_social_action_address(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    entity_type: &EntityTypes,
+    social_action_kind: &SocialActionKinds,
+    id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SOCIAL_ACTION_SEED,
+            user.as_ref(),
+            &[entity_type.clone() as u8],
+            &[social_action_kind.clone() as u8],
+            id.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Accounts of an instruction submitted on behalf of a user
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserContext {
+    // Audius admin account the user PDA is derived from
+    pub admin: Pubkey,
+    // Seed of the user's current handle, from which the user PDA is derived
+    pub handle_seed: [u8; 32],
+    // Signer, the user's authority or one of its delegates
+    pub authority: Pubkey,
+    // Whether `authority` acts through a UserAuthorityDelegate rather than as the user's authority
+    pub is_delegate: bool,
+    pub payer: Pubkey,
+}
+
+impl UserContext {
+    pub fn base(&self, program_id: &Pubkey) -> Pubkey {
+        find_base_address(program_id, &self.admin).0
+    }
+
+    /// User PDA and the handle validating it
+    pub fn user(&self, program_id: &Pubkey) -> (Pubkey, UserHandle) {
+        user_handle(program_id, &self.base(program_id), &self.handle_seed)
+    }
+
+    /// UserAuthorityDelegate and AuthorityDelegationStatus accounts of the signer,
+    /// the system program for both when the signer is the user's authority
+    pub fn delegate_accounts(&self, program_id: &Pubkey, user: &Pubkey) -> (Pubkey, Pubkey) {
+        if !self.is_delegate {
+            return (system_program::ID, system_program::ID);
+        }
+        (
+            find_user_authority_delegate_address(program_id, user, &self.authority).0,
+            find_authority_delegation_status_address(program_id, &self.authority).0,
+        )
+    }
+}
+
+/// User PDA derived from the base and handle seed, and the handle validating it
+pub fn user_handle(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    handle_seed: &[u8; 32],
+) -> (Pubkey, UserHandle) {
+    let (user, bump) = find_user_address(program_id, base, handle_seed);
+    (
+        user,
+        UserHandle {
+            seed: *handle_seed,
+            bump,
+        },
+    )
+}
+
+/// Remaining accounts authorizing an admin instruction when the admin authority is an AdminMultisig,
+/// the multisig account followed by `co_signers`, the multisig signers other than the named signer
+/// Append them to the accounts of any instruction signed by the admin authority.
+pub fn admin_multisig_accounts(admin_multisig: &Pubkey, co_signers: &[Pubkey]) -> Vec<AccountMeta> {
+    std::iter::once(AccountMeta::new_readonly(*admin_multisig, false))
+        .chain(
+            co_signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true)),
+        )
+        .collect()
+}
+
+/// Content node addresses of a replica set, in replica set order
+/// Each content node account holds the endpoint and metadata published by its authority.
+pub fn find_replica_set_addresses(program_id: &Pubkey, base: &Pubkey, replica_set: &[u16]) -> Vec<Pubkey> {
+    replica_set
+        .iter()
+        .map(|sp_id| find_content_node_address(program_id, base, *sp_id).0)
+        .collect()
+}
+
+/// Content node accounts of a replica set, in replica set order
+fn replica_set_accounts(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    replica_set: &[u16],
+) -> Vec<AccountMeta> {
+    find_replica_set_addresses(program_id, base, replica_set)
+        .into_iter()
+        .map(|content_node| AccountMeta::new_readonly(content_node, false))
+        .collect()
+}
+
+/// Content node account followed by its signing authority for each proposer
+fn proposer_accounts(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    proposers: &[(u16, Pubkey)],
+) -> Vec<AccountMeta> {
+    proposers
+        .iter()
+        .flat_map(|(sp_id, authority)| {
+            [
+                AccountMeta::new_readonly(
+                    find_content_node_address(program_id, base, *sp_id).0,
+                    false,
+                ),
+                AccountMeta::new_readonly(*authority, true),
+            ]
+        })
+        .collect()
+}
+
+fn build(
+    program_id: &Pubkey,
+    accounts: impl ToAccountMetas,
+    remaining_accounts: Vec<AccountMeta>,
+    data: impl InstructionData,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+    Instruction {
+        program_id: *program_id,
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
+
+/// Initialize the AudiusAdmin account `admin`, which must sign
+pub fn init_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    payer: &Pubkey,
+    authority: Pubkey,
+    verifier: Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::Initialize {
+            admin: *admin,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitAdmin {
+            authority,
+            verifier,
+        },
+    )
+}
+
+/// Set or clear a user's verification status, signed by the admin verifier
+pub fn update_is_verified(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    handle_seed: &[u8; 32],
+    verifier: &Pubkey,
+    is_verified: bool,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::UpdateIsVerified {
+            audius_admin: *admin,
+            user,
+            verifier: *verifier,
+        },
+        vec![],
+        instruction::UpdateIsVerified {
+            base,
+            _user_handle: user_handle,
+            is_verified,
+        },
+    )
+}
+
+/// Upgrade a program account to the current layout of its account type
+pub fn migrate_account(program_id: &Pubkey, account: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::MigrateAccount {
+            account: *account,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::MigrateAccount {},
+    )
+}
+
+/// Upgrade an AuthorityDelegationStatus or UserAuthorityDelegate to the current layout, binding it to `admin`
+/// `payer` must be the admin authority
+pub fn migrate_authority_delegation_status(
+    program_id: &Pubkey,
+    account: &Pubkey,
+    admin: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::MigrateAccount {
+            account: *account,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![AccountMeta::new_readonly(*admin, false)],
+        instruction::MigrateAccount {},
+    )
+}
+
+/// Initialize an unclaimed user, signed by the admin authority
+#[allow(clippy::too_many_arguments)]
+pub fn init_user(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    handle_seed: &[u8; 32],
+    eth_address: [u8; 20],
+    replica_set: Vec<u16>,
+    metadata: String,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::InitializeUser {
+            admin: *admin,
+            user,
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        replica_set_accounts(program_id, &base, &replica_set),
+        instruction::InitUser {
+            base,
+            eth_address,
+            replica_set,
+            handle_seed: *handle_seed,
+            _user_bump: user_handle.bump,
+            metadata,
+        },
+    )
+}
+
+/// Create or replace a content node, signed by the admin authority
+pub fn create_content_node(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    payer: &Pubkey,
+    sp_id: u16,
+    authority: Pubkey,
+    owner_eth_address: [u8; 20],
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    build(
+        program_id,
+        accounts::CreateContentNode {
+            admin: *admin,
+            content_node: find_content_node_address(program_id, &base, sp_id).0,
+            authority: *admin_authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::CreateContentNode {
+            base,
+            sp_id,
+            authority,
+            owner_eth_address,
+        },
+    )
+}
+
+/// Create or update a content node, signed by the authority of each proposer given by sp_id
+pub fn public_create_or_update_content_node(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    payer: &Pubkey,
+    proposers: &[(u16, Pubkey)],
+    sp_id: u16,
+    authority: Pubkey,
+    owner_eth_address: [u8; 20],
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    build(
+        program_id,
+        accounts::PublicCreateOrUpdateContentNode {
+            admin: *admin,
+            content_node: find_content_node_address(program_id, &base, sp_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        proposer_accounts(program_id, &base, proposers),
+        instruction::PublicCreateOrUpdateContentNode {
+            base,
+            proposer_sp_ids: proposers.iter().map(|(sp_id, _)| *sp_id).collect(),
+            sp_id,
+            authority,
+            owner_eth_address,
+        },
+    )
+}
+
+/// Delete a content node, signed by the authority of each proposer given by sp_id
+/// `refund_account` must be the authority of the deleted content node.
+pub fn public_delete_content_node(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    payer: &Pubkey,
+    refund_account: &Pubkey,
+    proposers: &[(u16, Pubkey)],
+    sp_id: u16,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (content_node, bump) = find_content_node_address(program_id, &base, sp_id);
+    build(
+        program_id,
+        accounts::PublicDeleteContentNode {
+            admin: *admin,
+            refund_account: *refund_account,
+            content_node,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        proposer_accounts(program_id, &base, proposers),
+        instruction::PublicDeleteContentNode {
+            base,
+            p_delete: ProposerSeedBump {
+                seed: content_node_seed(sp_id),
+                bump,
+            },
+            proposer_sp_ids: proposers.iter().map(|(sp_id, _)| *sp_id).collect(),
+        },
+    )
+}
+
+/// Rotate a content node's authority, signed by its current authority
+pub fn update_content_node_authority(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    current_authority: &Pubkey,
+    sp_id: u16,
+    authority: Pubkey,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    build(
+        program_id,
+        accounts::UpdateContentNode {
+            admin: *admin,
+            content_node: find_content_node_address(program_id, &base, sp_id).0,
+            authority: *current_authority,
+        },
+        vec![],
+        instruction::UpdateContentNodeAuthority {
+            base,
+            sp_id,
+            authority,
+        },
+    )
+}
+
+/// Publish a content node's endpoint and metadata CID, signed by its authority
+pub fn update_content_node_profile(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    authority: &Pubkey,
+    sp_id: u16,
+    endpoint: String,
+    metadata: String,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    build(
+        program_id,
+        accounts::UpdateContentNode {
+            admin: *admin,
+            content_node: find_content_node_address(program_id, &base, sp_id).0,
+            authority: *authority,
+        },
+        vec![],
+        instruction::UpdateContentNodeProfile {
+            base,
+            sp_id,
+            endpoint,
+            metadata,
+        },
+    )
+}
+
+/// Replace a user's replica set, signed by the user's authority or the authority of a replica set content node
+pub fn update_user_replica_set(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    handle_seed: &[u8; 32],
+    cn_authority: &Pubkey,
+    payer: &Pubkey,
+    replica_set: Vec<u16>,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::UpdateUserReplicaSet {
+            admin: *admin,
+            user,
+            cn_authority: *cn_authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        replica_set_accounts(program_id, &base, &replica_set),
+        instruction::UpdateUserReplicaSet {
+            base,
+            _user_handle: user_handle,
+            replica_set,
+        },
+    )
+}
+
+/// Move a user to the PDA of a new handle, signed by the user's authority
+pub fn change_user_handle(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    handle_seed: &[u8; 32],
+    authority: &Pubkey,
+    payer: &Pubkey,
+    new_handle_seed: [u8; 32],
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::ChangeUserHandle {
+            audius_admin: *admin,
+            user,
+            new_user: find_user_address(program_id, &base, &new_handle_seed).0,
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::ChangeUserHandle {
+            base,
+            _user_handle: user_handle,
+            new_handle_seed,
+        },
+    )
+}
+
+/// Claim an initialized user for `user_authority`
+/// Must follow the secp256k1 instruction carrying the user's signature of the ClaimKind::Claim message
+/// expiring at `expires_at`.
+pub fn init_user_sol(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    user: &Pubkey,
+    user_authority: Pubkey,
+    expires_at: i64,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::InitializeUserSolIdentity {
+            user: *user,
+            audius_admin: *admin,
+            sysvar_program: sysvar::instructions::ID,
+        },
+        vec![],
+        instruction::InitUserSol {
+            user_authority,
+            expires_at,
+        },
+    )
+}
+
+/// Rotate the authority of a claimed user to `user_authority`
+/// Must follow the secp256k1 instruction carrying the user's signature of the ClaimKind::Recovery message
+/// expiring at `expires_at`.
+pub fn recover_user(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    user: &Pubkey,
+    user_authority: Pubkey,
+    expires_at: i64,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::InitializeUserSolIdentity {
+            user: *user,
+            audius_admin: *admin,
+            sysvar_program: sysvar::instructions::ID,
+        },
+        vec![],
+        instruction::RecoverUser {
+            user_authority,
+            expires_at,
+        },
+    )
+}
+
+/// Create a claimed user while admin writes are disabled
+/// Must follow the secp256k1 instruction carrying the user's signature of the ClaimKind::Claim message
+/// expiring at `expires_at`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_user(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    payer: &Pubkey,
+    handle_seed: &[u8; 32],
+    eth_address: [u8; 20],
+    replica_set: Vec<u16>,
+    metadata: String,
+    id: u64,
+    user_authority: Pubkey,
+    expires_at: i64,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::CreateUser {
+            user,
+            payer: *payer,
+            audius_admin: *admin,
+            system_program: system_program::ID,
+            sysvar_program: sysvar::instructions::ID,
+        },
+        replica_set_accounts(program_id, &base, &replica_set),
+        instruction::CreateUser {
+            base,
+            eth_address,
+            replica_set,
+            handle_seed: *handle_seed,
+            _user_bump: user_handle.bump,
+            metadata,
+            id,
+            user_authority,
+            expires_at,
+        },
+    )
+}
+
+/// Log updated user metadata
+pub fn update_user(program_id: &Pubkey, context: &UserContext, metadata: String) -> Instruction {
+    let (user, _) = context.user(program_id);
+    let (user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    build(
+        program_id,
+        accounts::UpdateUser {
+            user,
+            user_authority: context.authority,
+            user_authority_delegate,
+            authority_delegation_status,
+        },
+        vec![],
+        instruction::UpdateUser { metadata },
+    )
+}
+
+/// Deactivate a user, signed by the user's authority or the admin authority
+pub fn deactivate_user(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    handle_seed: &[u8; 32],
+    authority: &Pubkey,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::DeactivateUser {
+            audius_admin: *admin,
+            user,
+            authority: *authority,
+        },
+        vec![],
+        instruction::DeactivateUser {
+            base,
+            _user_handle: user_handle,
+        },
+    )
+}
+
+/// Close a deactivated user, signed by the user's authority or the admin authority, which receives the rent
+pub fn close_user(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    handle_seed: &[u8; 32],
+    authority: &Pubkey,
+) -> Instruction {
+    let base = find_base_address(program_id, admin).0;
+    let (user, user_handle) = user_handle(program_id, &base, handle_seed);
+    build(
+        program_id,
+        accounts::CloseUser {
+            audius_admin: *admin,
+            user,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::CloseUser {
+            base,
+            _user_handle: user_handle,
+        },
+    )
+}
+
+fn update_admin_accounts(admin: &Pubkey, admin_authority: &Pubkey) -> accounts::UpdateAdmin {
+    accounts::UpdateAdmin {
+        admin: *admin,
+        admin_authority: *admin_authority,
+    }
+}
+
+fn accept_admin_rotation_accounts(
+    admin: &Pubkey,
+    pending_signer: &Pubkey,
+) -> accounts::AcceptAdminRotation {
+    accounts::AcceptAdminRotation {
+        admin: *admin,
+        pending_signer: *pending_signer,
+    }
+}
+
+/// Enable or disable admin writes, signed by the admin authority
+pub fn update_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    is_write_enabled: bool,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::UpdateAdmin { is_write_enabled },
+    )
+}
+
+/// Set the maximum replica set length, signed by the admin authority
+pub fn update_max_replica_set_size(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    max_replica_set_size: u8,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::UpdateMaxReplicaSetSize {
+            max_replica_set_size,
+        },
+    )
+}
+
+/// Set the number of proposers required for content node changes, signed by the admin authority
+pub fn update_proposer_threshold(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    proposer_threshold: u8,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::UpdateProposerThreshold { proposer_threshold },
+    )
+}
+
+/// Propose a new admin authority, signed by the admin authority
+pub fn propose_admin_authority(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    pending_authority: Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::ProposeAdminAuthority { pending_authority },
+    )
+}
+
+/// Accept the proposed admin authority, signed by the pending authority
+pub fn accept_admin_authority(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pending_signer: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accept_admin_rotation_accounts(admin, pending_signer),
+        vec![],
+        instruction::AcceptAdminAuthority {},
+    )
+}
+
+/// Cancel a proposed admin authority, signed by the admin authority
+pub fn cancel_admin_authority(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::CancelAdminAuthority {},
+    )
+}
+
+/// Propose a new admin verifier, signed by the admin authority
+pub fn propose_admin_verifier(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    pending_verifier: Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::ProposeAdminVerifier { pending_verifier },
+    )
+}
+
+/// Accept the proposed admin verifier, signed by the pending verifier
+pub fn accept_admin_verifier(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pending_signer: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accept_admin_rotation_accounts(admin, pending_signer),
+        vec![],
+        instruction::AcceptAdminVerifier {},
+    )
+}
+
+/// Cancel a proposed admin verifier, signed by the admin authority
+pub fn cancel_admin_verifier(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        update_admin_accounts(admin, admin_authority),
+        vec![],
+        instruction::CancelAdminVerifier {},
+    )
+}
+
+/// Initialize the AdminMultisig of an admin account, signed by the admin authority
+pub fn init_admin_multisig(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admin_authority: &Pubkey,
+    payer: &Pubkey,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::InitAdminMultisig {
+            admin: *admin,
+            admin_multisig: find_admin_multisig_address(program_id, admin).0,
+            admin_authority: *admin_authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitAdminMultisig { signers, threshold },
+    )
+}
+
+/// Replace the signer set of an AdminMultisig, signed by `signer` and `co_signers`, at least the
+/// current threshold of its signers
+pub fn update_admin_multisig(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    signer: &Pubkey,
+    co_signers: &[Pubkey],
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdateAdminMultisig {
+            admin_multisig: find_admin_multisig_address(program_id, admin).0,
+            signer: *signer,
+        },
+        co_signers
+            .iter()
+            .map(|co_signer| AccountMeta::new_readonly(*co_signer, true))
+            .collect(),
+        instruction::UpdateAdminMultisig { signers, threshold },
+    )
+}
+
+/// Create, update or delete a track or playlist
+/// Deletes refund the entity, and the contents of a playlist, to the context payer, which must be the payer that
+/// allocated them
+pub fn manage_entity(
+    program_id: &Pubkey,
+    context: &UserContext,
+    entity_type: EntityTypes,
+    management_action: ManagementActions,
+    id: u64,
+    metadata: String,
+) -> Instruction {
+    let base = context.base(program_id);
+    let (user, user_handle) = context.user(program_id);
+    let (user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    let mut remaining_accounts = vec![];
+    if management_action == ManagementActions::Delete {
+        remaining_accounts.push(AccountMeta::new(context.payer, false));
+        if entity_type == EntityTypes::Playlist {
+            remaining_accounts.extend([
+                AccountMeta::new(find_playlist_contents_address(program_id, &base, id).0, false),
+                AccountMeta::new(context.payer, false),
+            ]);
+        }
+    }
+    build(
+        program_id,
+        accounts::ManageEntity {
+            audius_admin: context.admin,
+            user,
+            authority: context.authority,
+            user_authority_delegate,
+            authority_delegation_status,
+            entity: find_entity_address(program_id, &base, &entity_type, id).0,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        remaining_accounts,
+        instruction::ManageEntity {
+            base,
+            _user_handle: user_handle,
+            entity_type,
+            management_action,
+            id,
+            metadata,
+        },
+    )
+}
+
+/// Append a track to a playlist, or remove or move one of its tracks
+pub fn manage_playlist_contents(
+    program_id: &Pubkey,
+    context: &UserContext,
+    playlist_id: u64,
+    action: PlaylistContentsAction,
+) -> Instruction {
+    let base = context.base(program_id);
+    let (user, user_handle) = context.user(program_id);
+    let (user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    build(
+        program_id,
+        accounts::ManagePlaylistContents {
+            audius_admin: context.admin,
+            user,
+            authority: context.authority,
+            user_authority_delegate,
+            authority_delegation_status,
+            playlist: find_entity_address(program_id, &base, &EntityTypes::Playlist, playlist_id).0,
+            playlist_contents: find_playlist_contents_address(program_id, &base, playlist_id).0,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::ManagePlaylistContents {
+            base,
+            _user_handle: user_handle,
+            playlist_id,
+            action,
+        },
+    )
+}
+
+/// Save or repost an entity, or remove an existing save or repost
+/// Deletes refund the rent of the social action to the context payer, which must have paid for it
+pub fn write_entity_social_action(
+    program_id: &Pubkey,
+    context: &UserContext,
+    entity_social_action: EntitySocialActionValues,
+    entity_type: EntityTypes,
+    id: String,
+) -> Instruction {
+    let base = context.base(program_id);
+    let (user, user_handle) = context.user(program_id);
+    let (user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    let social_action = find_social_action_address(
+        program_id,
+        &user,
+        &entity_type,
+        &entity_social_action.kind(),
+        &id,
+    )
+    .0;
+    let remaining_accounts = if entity_social_action.is_delete() {
+        vec![AccountMeta::new(context.payer, false)]
+    } else {
+        vec![]
+    };
+    build(
+        program_id,
+        accounts::WriteEntitySocialAction {
+            audius_admin: context.admin,
+            user,
+            authority: context.authority,
+            user_authority_delegate,
+            authority_delegation_status,
+            social_action,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        remaining_accounts,
+        instruction::WriteEntitySocialAction {
+            base,
+            _user_handle: user_handle,
+            entity_social_action,
+            entity_type,
+            id,
+        },
+    )
+}
+
+/// Follow or unfollow the user with `followee_handle_seed` on behalf of the context user
+/// Unfollows refund the rent of the follow to the context payer, which must have paid for it
+pub fn follow_user(
+    program_id: &Pubkey,
+    context: &UserContext,
+    user_action: UserAction,
+    followee_handle_seed: &[u8; 32],
+) -> Instruction {
+    let base = context.base(program_id);
+    let (follower, follower_handle) = context.user(program_id);
+    let (followee, followee_handle) = user_handle(program_id, &base, followee_handle_seed);
+    let (user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &follower);
+    build(
+        program_id,
+        accounts::FollowUser {
+            audius_admin: context.admin,
+            follower_user_storage: follower,
+            followee_user_storage: followee,
+            user_authority_delegate,
+            authority_delegation_status,
+            authority: context.authority,
+            follow: find_follow_address(program_id, &follower, &followee).0,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        match user_action {
+            UserAction::FollowUser => vec![],
+            UserAction::UnfollowUser => vec![AccountMeta::new(context.payer, false)],
+        },
+        instruction::FollowUser {
+            base,
+            user_action,
+            _follower_handle: follower_handle,
+            _followee_handle: followee_handle,
+        },
+    )
+}
+
+/// Apply a batch of follows, saves and reposts on behalf of the context user
+/// The followee handles of follow actions must hold the bump of the followee PDA, see user_handle.
+/// Unfollows and deletes refund the rent of the follow or social action to the context payer, which must have
+/// paid for it
+pub fn write_social_actions(
+    program_id: &Pubkey,
+    context: &UserContext,
+    actions: Vec<SocialAction>,
+) -> Instruction {
+    let base = context.base(program_id);
+    let (user, user_handle) = context.user(program_id);
+    let (user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    let action_accounts = actions
+        .iter()
+        .flat_map(|action| match action {
+            SocialAction::Follow {
+                user_action,
+                followee_handle,
+            } => {
+                let followee = find_user_address(program_id, &base, &followee_handle.seed).0;
+                let mut accounts = vec![
+                    AccountMeta::new_readonly(followee, false),
+                    AccountMeta::new(find_follow_address(program_id, &user, &followee).0, false),
+                ];
+                if *user_action == UserAction::UnfollowUser {
+                    accounts.push(AccountMeta::new(context.payer, false));
+                }
+                accounts
+            }
+            SocialAction::EntitySocialAction {
+                entity_social_action,
+                entity_type,
+                id,
+            } => {
+                let mut accounts = vec![AccountMeta::new(
+                    find_social_action_address(
+                        program_id,
+                        &user,
+                        entity_type,
+                        &entity_social_action.kind(),
+                        id,
+                    )
+                    .0,
+                    false,
+                )];
+                if entity_social_action.is_delete() {
+                    accounts.push(AccountMeta::new(context.payer, false));
+                }
+                accounts
+            }
+        })
+        .collect();
+    build(
+        program_id,
+        accounts::WriteSocialActions {
+            audius_admin: context.admin,
+            user,
+            authority: context.authority,
+            user_authority_delegate,
+            authority_delegation_status,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        action_accounts,
+        instruction::WriteSocialActions {
+            base,
+            _user_handle: user_handle,
+            actions,
+        },
+    )
+}
+
+/// Initialize the AuthorityDelegationStatus of `delegate_authority`, which must sign
+/// `admin` is the admin account that may revoke or reinstate the delegation
+pub fn init_authority_delegation_status(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    delegate_authority: &Pubkey,
+    payer: &Pubkey,
+    authority_name: String,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::InitAuthorityDelegationStatus {
+            admin: *admin,
+            delegate_authority: *delegate_authority,
+            authority_delegation_status_pda: find_authority_delegation_status_address(
+                program_id,
+                delegate_authority,
+            )
+            .0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitAuthorityDelegationStatus {
+            _authority_name: authority_name,
+        },
+    )
+}
+
+fn update_authority_delegation_status_accounts(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    delegate_authority: &Pubkey,
+    authority: &Pubkey,
+) -> (accounts::UpdateAuthorityDelegationStatus, u8) {
+    let (authority_delegation_status_pda, bump) =
+        find_authority_delegation_status_address(program_id, delegate_authority);
+    (
+        accounts::UpdateAuthorityDelegationStatus {
+            admin: *admin,
+            delegate_authority: *delegate_authority,
+            authority_delegation_status_pda,
+            authority: *authority,
+        },
+        bump,
+    )
+}
+
+/// Revoke the delegation of `delegate_authority`, signed by the delegate authority or the admin authority
+pub fn revoke_authority_delegation(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    delegate_authority: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let (accounts, bump) = update_authority_delegation_status_accounts(
+        program_id,
+        admin,
+        delegate_authority,
+        authority,
+    );
+    build(
+        program_id,
+        accounts,
+        vec![],
+        instruction::RevokeAuthorityDelegation {
+            _authority_delegation_bump: bump,
+        },
+    )
+}
+
+/// Reinstate the revoked delegation of `delegate_authority`, signed by the admin authority
+/// or by the delegate authority if it revoked its own delegation
+pub fn reinstate_authority_delegation(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    delegate_authority: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let (accounts, bump) = update_authority_delegation_status_accounts(
+        program_id,
+        admin,
+        delegate_authority,
+        authority,
+    );
+    build(
+        program_id,
+        accounts,
+        vec![],
+        instruction::ReinstateAuthorityDelegation {
+            _authority_delegation_bump: bump,
+        },
+    )
+}
+
+/// Add `delegate_authority` as a delegate of the context user
+pub fn add_user_authority_delegate(
+    program_id: &Pubkey,
+    context: &UserContext,
+    delegate_authority: Pubkey,
+    permissions: u16,
+    expires_at: Option<i64>,
+) -> Instruction {
+    let base = context.base(program_id);
+    let (user, user_handle) = context.user(program_id);
+    let (signer_user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    build(
+        program_id,
+        accounts::AddUserAuthorityDelegate {
+            admin: context.admin,
+            user,
+            current_user_authority_delegate: find_user_authority_delegate_address(
+                program_id,
+                &user,
+                &delegate_authority,
+            )
+            .0,
+            signer_user_authority_delegate,
+            authority_delegation_status,
+            authority: context.authority,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::AddUserAuthorityDelegate {
+            base,
+            _handle_seed: user_handle.seed,
+            _user_bump: user_handle.bump,
+            user_authority_delegate: delegate_authority,
+            permissions,
+            expires_at,
+        },
+    )
+}
+
+/// Remove `delegate_authority` as a delegate of the context user, refunding the rent to the context payer
+pub fn remove_user_authority_delegate(
+    program_id: &Pubkey,
+    context: &UserContext,
+    delegate_authority: Pubkey,
+) -> Instruction {
+    let base = context.base(program_id);
+    let (user, user_handle) = context.user(program_id);
+    let (signer_user_authority_delegate, authority_delegation_status) =
+        context.delegate_accounts(program_id, &user);
+    let (current_user_authority_delegate, delegate_bump) =
+        find_user_authority_delegate_address(program_id, &user, &delegate_authority);
+    build(
+        program_id,
+        accounts::RemoveUserAuthorityDelegate {
+            admin: context.admin,
+            user,
+            current_user_authority_delegate,
+            signer_user_authority_delegate,
+            authority_delegation_status,
+            authority: context.authority,
+            payer: context.payer,
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::RemoveUserAuthorityDelegate {
+            _base: base,
+            _handle_seed: user_handle.seed,
+            _user_bump: user_handle.bump,
+            _user_authority_delegate: delegate_authority,
+            _delegate_bump: delegate_bump,
+        },
+    )
+}
+
+/// Close an expired user authority delegate, refunding the rent to the payer recorded in it
+pub fn close_expired_user_authority_delegate(
+    program_id: &Pubkey,
+    user_authority_delegate: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CloseExpiredUserAuthorityDelegate {
+            user_authority_delegate: *user_authority_delegate,
+            payer: *payer,
+        },
+        vec![],
+        instruction::CloseExpiredUserAuthorityDelegate {},
+    )
+}
+
+/// Program account decoded by its discriminator
+pub enum AudiusDataAccount {
+    AudiusAdmin(AudiusAdmin),
+    AdminMultisig(AdminMultisig),
+    User(User),
+    UserRedirect(UserRedirect),
+    UserTombstone(UserTombstone),
+    ContentNode(ContentNode),
+    Follow(Follow),
+    Entity(Entity),
+    PlaylistContents(PlaylistContents),
+    EntitySocialAction(EntitySocialAction),
+    UserAuthorityDelegate(UserAuthorityDelegate),
+    AuthorityDelegationStatus(AuthorityDelegationStatus),
+}
+
+impl AudiusDataAccount {
+    /// Decode the data of a program account in the current layout of its account type
+    /// Accounts in earlier layouts fail to decode until they are upgraded with migrate_account.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let discriminator = data
+            .get(..8)
+            .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+        let data = &mut &data[..];
+        Ok(if discriminator == AudiusAdmin::discriminator() {
+            Self::AudiusAdmin(AudiusAdmin::try_deserialize(data)?)
+        } else if discriminator == AdminMultisig::discriminator() {
+            Self::AdminMultisig(AdminMultisig::try_deserialize(data)?)
+        } else if discriminator == User::discriminator() {
+            Self::User(User::try_deserialize(data)?)
+        } else if discriminator == UserRedirect::discriminator() {
+            Self::UserRedirect(UserRedirect::try_deserialize(data)?)
+        } else if discriminator == UserTombstone::discriminator() {
+            Self::UserTombstone(UserTombstone::try_deserialize(data)?)
+        } else if discriminator == ContentNode::discriminator() {
+            Self::ContentNode(ContentNode::try_deserialize(data)?)
+        } else if discriminator == Follow::discriminator() {
+            Self::Follow(Follow::try_deserialize(data)?)
+        } else if discriminator == Entity::discriminator() {
+            Self::Entity(Entity::try_deserialize(data)?)
+        } else if discriminator == PlaylistContents::discriminator() {
+            Self::PlaylistContents(PlaylistContents::try_deserialize(data)?)
+        } else if discriminator == EntitySocialAction::discriminator() {
+            Self::EntitySocialAction(EntitySocialAction::try_deserialize(data)?)
+        } else if discriminator == UserAuthorityDelegate::discriminator() {
+            Self::UserAuthorityDelegate(UserAuthorityDelegate::try_deserialize(data)?)
+        } else if discriminator == AuthorityDelegationStatus::discriminator() {
+            Self::AuthorityDelegationStatus(AuthorityDelegationStatus::try_deserialize(data)?)
+        } else {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        multisig::validate_admin_signers,
+        utils::{
+            apply_entity_social_action, apply_follow, validate_proposers, validate_replica_set,
+            validate_user_authority,
+        },
+        ChangeUserHandle, DeactivateUser, ManageEntity, ManagePlaylistContents,
+        PublicDeleteContentNode, RemoveUserAuthorityDelegate, UpdateAdmin, UpdateAdminMultisig,
+        UpdateAuthorityDelegationStatus, UpdateContentNode, UpdateIsVerified, UpdateUserReplicaSet,
+        WriteSocialActions,
+    };
+    use anchor_lang::Accounts;
+    use std::collections::BTreeMap;
+
+    /// Account state backing an AccountInfo
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+        executable: bool,
+    }
+
+    impl TestAccount {
+        fn program<T: AccountSerialize>(key: Pubkey, state: &T) -> Self {
+            let mut data = Vec::new();
+            state.try_serialize(&mut data).unwrap();
+            TestAccount {
+                key,
+                owner: crate::ID,
+                lamports: 1_000_000,
+                data,
+                executable: false,
+            }
+        }
+
+        fn system(key: Pubkey) -> Self {
+            TestAccount {
+                key,
+                owner: system_program::ID,
+                lamports: 1_000_000,
+                data: Vec::new(),
+                executable: key == system_program::ID,
+            }
+        }
+    }
+
+    /// Run `f` with the account infos of `ix`, backed by `accounts` or empty system accounts
+    fn with_account_infos(
+        ix: &Instruction,
+        mut accounts: Vec<TestAccount>,
+        f: impl FnOnce(&[AccountInfo]),
+    ) {
+        for meta in &ix.accounts {
+            if !accounts.iter().any(|account| account.key == meta.pubkey) {
+                accounts.push(TestAccount::system(meta.pubkey));
+            }
+        }
+        let infos: Vec<AccountInfo> = accounts
+            .iter_mut()
+            .map(|account| {
+                AccountInfo::new(
+                    &account.key,
+                    false,
+                    false,
+                    &mut account.lamports,
+                    &mut account.data,
+                    &account.owner,
+                    account.executable,
+                    0,
+                )
+            })
+            .collect();
+        // Repeated accounts share their lamports and data like they do in the runtime
+        let ix_infos: Vec<AccountInfo> = ix
+            .accounts
+            .iter()
+            .map(|meta| {
+                let mut info = infos
+                    .iter()
+                    .find(|info| *info.key == meta.pubkey)
+                    .unwrap()
+                    .clone();
+                info.is_signer = meta.is_signer;
+                info.is_writable = meta.is_writable;
+                info
+            })
+            .collect();
+        f(&ix_infos)
+    }
+
+    /// Validate the accounts of `ix` with the constraints of `T`, returning them and the remaining accounts
+    fn try_accounts<'a, 'info, T: Accounts<'info>>(
+        ix: &Instruction,
+        infos: &'a [AccountInfo<'info>],
+    ) -> (T, &'a [AccountInfo<'info>]) {
+        assert_eq!(ix.program_id, crate::ID);
+        let mut remaining = infos;
+        let accounts = T::try_accounts(
+            &crate::ID,
+            &mut remaining,
+            &ix.data[8..],
+            &mut BTreeMap::new(),
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+        (accounts, remaining)
+    }
+
+    struct Fixture {
+        admin: Pubkey,
+        admin_state: AudiusAdmin,
+        base: Pubkey,
+        user_authority: Pubkey,
+        context: UserContext,
+        user: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let admin = Pubkey::new_unique();
+            let user_authority = Pubkey::new_unique();
+            let context = UserContext {
+                admin,
+                handle_seed: handle_seed("alice"),
+                authority: user_authority,
+                is_delegate: false,
+                payer: Pubkey::new_unique(),
+            };
+            let (user, _) = context.user(&crate::ID);
+            Fixture {
+                admin,
+                admin_state: AudiusAdmin {
+                    version: ACCOUNT_VERSION,
+                    authority: Pubkey::new_unique(),
+                    verifier: Pubkey::new_unique(),
+                    is_write_enabled: false,
+                    pending_authority: Pubkey::default(),
+                    pending_verifier: Pubkey::default(),
+                    max_replica_set_size: DEFAULT_MAX_REPLICA_SET_SIZE,
+                    proposer_threshold: DEFAULT_PROPOSER_THRESHOLD,
+                },
+                base: find_base_address(&crate::ID, &admin).0,
+                user_authority,
+                context,
+                user,
+            }
+        }
+
+        fn user_state(&self) -> User {
+            User {
+                version: ACCOUNT_VERSION,
+                eth_address: [1; 20],
+                authority: self.user_authority,
+                replica_set: vec![1, 2, 3],
+                is_verified: false,
+                authority_epoch: 0,
+                authority_updated_at: 1,
+                deactivated_at: 0,
+            }
+        }
+
+        fn accounts(&self) -> Vec<TestAccount> {
+            vec![
+                TestAccount::program(self.admin, &self.admin_state),
+                TestAccount::program(self.user, &self.user_state()),
+            ]
+        }
+
+        fn content_node(&self, sp_id: u16, authority: Pubkey) -> TestAccount {
+            TestAccount::program(
+                find_content_node_address(&crate::ID, &self.base, sp_id).0,
+                &ContentNode {
+                    version: ACCOUNT_VERSION,
+                    owner_eth_address: [sp_id as u8; 20],
+                    authority,
+                    endpoint: String::new(),
+                    metadata: String::new(),
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn derives_handle_and_content_node_seeds() {
+        let seed = handle_seed("alice");
+        assert_eq!(&seed[..5], b"alice");
+        assert!(seed[5..].iter().all(|byte| *byte == 0));
+        assert_eq!(handle_seed(&"a".repeat(40)), [b'a'; 32]);
+
+        let seed = content_node_seed(0x0102);
+        assert_eq!(&seed[..5], CONTENT_NODE_SEED_PREFIX);
+        assert_eq!(&seed[5..], &[0x02, 0x01]);
+    }
+
+    #[test]
+    fn builds_user_instructions_with_validated_accounts() {
+        let fixture = Fixture::new();
+        let context = &fixture.context;
+
+        let ix = manage_entity(
+            &crate::ID,
+            context,
+            EntityTypes::Track,
+            ManagementActions::Create,
+            7,
+            String::new(),
+        );
+        with_account_infos(&ix, fixture.accounts(), |infos| {
+            let (accounts, _) = try_accounts::<ManageEntity>(&ix, infos);
+            validate_user_authority(
+                &crate::ID,
+                &accounts.user,
+                &accounts.user_authority_delegate,
+                &accounts.authority,
+                &accounts.authority_delegation_status,
+                DELEGATE_PERMISSION_MANAGE_ENTITY,
+            )
+            .unwrap();
+        });
+
+        let action = PlaylistContentsAction::Append { track_id: 7 };
+        let ix = manage_playlist_contents(&crate::ID, context, 8, action);
+        with_account_infos(&ix, fixture.accounts(), |infos| {
+            try_accounts::<ManagePlaylistContents>(&ix, infos);
+        });
+
+        let ix = change_user_handle(
+            &crate::ID,
+            &fixture.admin,
+            &context.handle_seed,
+            &fixture.user_authority,
+            &context.payer,
+            handle_seed("bob"),
+        );
+        with_account_infos(&ix, fixture.accounts(), |infos| {
+            try_accounts::<ChangeUserHandle>(&ix, infos);
+        });
+
+        let ix = deactivate_user(
+            &crate::ID,
+            &fixture.admin,
+            &context.handle_seed,
+            &fixture.user_authority,
+        );
+        with_account_infos(&ix, fixture.accounts(), |infos| {
+            try_accounts::<DeactivateUser>(&ix, infos);
+        });
+
+        let verifier = fixture.admin_state.verifier;
+        let ix = update_is_verified(
+            &crate::ID,
+            &fixture.admin,
+            &context.handle_seed,
+            &verifier,
+            true,
+        );
+        with_account_infos(&ix, fixture.accounts(), |infos| {
+            try_accounts::<UpdateIsVerified>(&ix, infos);
+        });
+    }
+
+    #[test]
+    fn derives_delegate_accounts_validated_on_chain() {
+        let fixture = Fixture::new();
+        let delegate = Pubkey::new_unique();
+        let context = UserContext {
+            authority: delegate,
+            is_delegate: true,
+            ..fixture.context.clone()
+        };
+        let mut accounts = fixture.accounts();
+        accounts.push(TestAccount::program(
+            find_user_authority_delegate_address(&crate::ID, &fixture.user, &delegate).0,
+            &UserAuthorityDelegate {
+                version: ACCOUNT_VERSION,
+                delegate_authority: delegate,
+                user_storage_account: fixture.user,
+                admin: fixture.admin,
+                permissions: DELEGATE_PERMISSION_MANAGE_ENTITY,
+                expires_at: None,
+                payer: context.payer,
+                authority_epoch: 0,
+            },
+        ));
+        accounts.push(TestAccount::program(
+            find_authority_delegation_status_address(&crate::ID, &delegate).0,
+            &AuthorityDelegationStatus {
+                version: ACCOUNT_VERSION,
+                admin: fixture.admin,
+                is_revoked: false,
+                revoked_by: Pubkey::default(),
+                revoked_at: 0,
+            },
+        ));
+
+        let ix = manage_entity(
+            &crate::ID,
+            &context,
+            EntityTypes::Playlist,
+            ManagementActions::Update,
+            9,
+            String::new(),
+        );
+        with_account_infos(&ix, accounts, |infos| {
+            let (accounts, _) = try_accounts::<ManageEntity>(&ix, infos);
+            validate_user_authority(
+                &crate::ID,
+                &accounts.user,
+                &accounts.user_authority_delegate,
+                &accounts.authority,
+                &accounts.authority_delegation_status,
+                DELEGATE_PERMISSION_MANAGE_ENTITY,
+            )
+            .unwrap();
+        });
+
+        let ix = remove_user_authority_delegate(&crate::ID, &fixture.context, delegate);
+        let mut accounts = fixture.accounts();
+        accounts.push(TestAccount::program(
+            find_user_authority_delegate_address(&crate::ID, &fixture.user, &delegate).0,
+            &UserAuthorityDelegate {
+                version: ACCOUNT_VERSION,
+                delegate_authority: delegate,
+                user_storage_account: fixture.user,
+                admin: fixture.admin,
+                permissions: DELEGATE_PERMISSION_ALL,
+                expires_at: None,
+                payer: context.payer,
+                authority_epoch: 0,
+            },
+        ));
+        with_account_infos(&ix, accounts, |infos| {
+            try_accounts::<RemoveUserAuthorityDelegate>(&ix, infos);
+        });
+
+        let ix = revoke_authority_delegation(&crate::ID, &fixture.admin, &delegate, &delegate);
+        let mut accounts = fixture.accounts();
+        accounts.push(TestAccount::program(
+            find_authority_delegation_status_address(&crate::ID, &delegate).0,
+            &AuthorityDelegationStatus {
+                version: ACCOUNT_VERSION,
+                admin: fixture.admin,
+                is_revoked: false,
+                revoked_by: Pubkey::default(),
+                revoked_at: 0,
+            },
+        ));
+        with_account_infos(&ix, accounts, |infos| {
+            try_accounts::<UpdateAuthorityDelegationStatus>(&ix, infos);
+        });
+    }
+
+    #[test]
+    fn derives_content_node_accounts_validated_on_chain() {
+        let fixture = Fixture::new();
+        let authorities: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let content_nodes = || {
+            (1..=3)
+                .zip(&authorities)
+                .map(|(sp_id, authority)| fixture.content_node(sp_id, *authority))
+        };
+
+        let ix = update_user_replica_set(
+            &crate::ID,
+            &fixture.admin,
+            &fixture.context.handle_seed,
+            &fixture.user_authority,
+            &fixture.context.payer,
+            vec![3, 1],
+        );
+        let mut accounts = fixture.accounts();
+        accounts.extend(content_nodes());
+        with_account_infos(&ix, accounts, |infos| {
+            let (_, remaining) = try_accounts::<UpdateUserReplicaSet>(&ix, infos);
+            validate_replica_set(
+                &crate::ID,
+                &fixture.base,
+                &fixture.admin_state,
+                &[3, 1],
+                remaining,
+            )
+            .unwrap();
+        });
+
+        let proposers: Vec<(u16, Pubkey)> = (1..=3).zip(authorities.iter().copied()).collect();
+        let content_node_authority = Pubkey::new_unique();
+        let ix = public_delete_content_node(
+            &crate::ID,
+            &fixture.admin,
+            &fixture.context.payer,
+            &content_node_authority,
+            &proposers,
+            4,
+        );
+        let mut accounts = fixture.accounts();
+        accounts.extend(content_nodes());
+        accounts.push(fixture.content_node(4, content_node_authority));
+        with_account_infos(&ix, accounts, |infos| {
+            let (_, remaining) = try_accounts::<PublicDeleteContentNode>(&ix, infos);
+            validate_proposers(
+                &crate::ID,
+                &fixture.base,
+                &fixture.admin_state,
+                &[1, 2, 3],
+                remaining,
+            )
+            .unwrap();
+        });
+
+        let ix = update_content_node_profile(
+            &crate::ID,
+            &fixture.admin,
+            &authorities[1],
+            2,
+            "https://cn2.audius.co".to_string(),
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string(),
+        );
+        let mut accounts = fixture.accounts();
+        accounts.extend(content_nodes());
+        with_account_infos(&ix, accounts, |infos| {
+            let (accounts, _) = try_accounts::<UpdateContentNode>(&ix, infos);
+            assert_eq!(accounts.content_node.authority, authorities[1]);
+        });
+
+        let replica_set = find_replica_set_addresses(&crate::ID, &fixture.base, &[3, 1]);
+        assert_eq!(
+            replica_set,
+            vec![
+                content_nodes().nth(2).unwrap().key,
+                content_nodes().next().unwrap().key,
+            ]
+        );
+    }
+
+    #[test]
+    fn derives_social_action_accounts_validated_on_chain() {
+        let fixture = Fixture::new();
+        let followee_seed = handle_seed("bob");
+        let (followee, followee_handle) = user_handle(&crate::ID, &fixture.base, &followee_seed);
+        let id = "42".to_string();
+        let ix = write_social_actions(
+            &crate::ID,
+            &fixture.context,
+            vec![
+                SocialAction::Follow {
+                    user_action: UserAction::UnfollowUser,
+                    followee_handle,
+                },
+                SocialAction::EntitySocialAction {
+                    entity_social_action: EntitySocialActionValues::DeleteSave,
+                    entity_type: EntityTypes::Track,
+                    id: id.clone(),
+                },
+            ],
+        );
+
+        let mut accounts = fixture.accounts();
+        accounts.push(TestAccount::program(followee, &fixture.user_state()));
+        accounts.push(TestAccount::program(
+            find_follow_address(&crate::ID, &fixture.user, &followee).0,
+            &Follow {
+                version: ACCOUNT_VERSION,
+                follower: fixture.user,
+                followee,
+                payer: fixture.context.payer,
+            },
+        ));
+        accounts.push(TestAccount::program(
+            find_social_action_address(
+                &crate::ID,
+                &fixture.user,
+                &EntityTypes::Track,
+                &SocialActionKinds::Save,
+                &id,
+            )
+            .0,
+            &EntitySocialAction {
+                version: ACCOUNT_VERSION,
+                user: fixture.user,
+                entity_type: EntityTypes::Track,
+                social_action_kind: SocialActionKinds::Save,
+                payer: fixture.context.payer,
+            },
+        ));
+        with_account_infos(&ix, accounts, |infos| {
+            let (accounts, remaining) = try_accounts::<WriteSocialActions>(&ix, infos);
+            assert_eq!(remaining.len(), 5);
+            assert_eq!(*remaining[0].key, followee);
+            apply_follow(
+                &crate::ID,
+                &fixture.user,
+                &followee,
+                &UserAction::UnfollowUser,
+                &remaining[1],
+                &accounts.payer,
+                remaining.get(2),
+                &accounts.system_program,
+            )
+            .unwrap();
+            apply_entity_social_action(
+                &crate::ID,
+                &fixture.user,
+                &EntitySocialActionValues::DeleteSave,
+                &EntityTypes::Track,
+                &id,
+                &remaining[3],
+                &accounts.payer,
+                remaining.get(4),
+                &accounts.system_program,
+            )
+            .unwrap();
+        });
+    }
+
+    #[test]
+    fn derives_admin_multisig_accounts_validated_on_chain() {
+        let mut fixture = Fixture::new();
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let (admin_multisig, _) = find_admin_multisig_address(&crate::ID, &fixture.admin);
+        fixture.admin_state.authority = admin_multisig;
+        let multisig = || {
+            TestAccount::program(
+                admin_multisig,
+                &AdminMultisig {
+                    version: ACCOUNT_VERSION,
+                    admin: fixture.admin,
+                    signers: signers.clone(),
+                    threshold: 2,
+                },
+            )
+        };
+
+        let mut ix = update_admin(&crate::ID, &fixture.admin, &signers[0], false);
+        ix.accounts
+            .extend(admin_multisig_accounts(&admin_multisig, &signers[1..2]));
+        let mut accounts = fixture.accounts();
+        accounts.push(multisig());
+        with_account_infos(&ix, accounts, |infos| {
+            let (accounts, remaining) = try_accounts::<UpdateAdmin>(&ix, infos);
+            validate_admin_signers(
+                &crate::ID,
+                &accounts.admin,
+                &accounts.admin_authority,
+                remaining,
+            )
+            .unwrap();
+        });
+
+        let ix = update_admin_multisig(
+            &crate::ID,
+            &fixture.admin,
+            &signers[0],
+            &signers[1..2],
+            signers.clone(),
+            3,
+        );
+        with_account_infos(&ix, vec![multisig()], |infos| {
+            let (_, remaining) = try_accounts::<UpdateAdminMultisig>(&ix, infos);
+            assert_eq!(remaining.len(), 1);
+        });
+    }
+
+    #[test]
+    fn decodes_accounts_by_discriminator() {
+        let fixture = Fixture::new();
+        let mut data = Vec::new();
+        fixture.user_state().try_serialize(&mut data).unwrap();
+        match AudiusDataAccount::decode(&data).unwrap() {
+            AudiusDataAccount::User(user) => assert_eq!(user.authority, fixture.user_authority),
+            _ => panic!("decoded as another account type"),
+        }
+
+        let mut data = Vec::new();
+        let contents = PlaylistContents {
+            version: ACCOUNT_VERSION,
+            owner: fixture.user,
+            payer: Pubkey::new_unique(),
+            playlist_id: 8,
+            track_ids: vec![3, 1],
+        };
+        contents.try_serialize(&mut data).unwrap();
+        // Playlist contents are allocated beyond their serialized length
+        data.resize(
+            playlist_contents_account_size(PLAYLIST_CONTENTS_CAPACITY_INCREMENT),
+            0,
+        );
+        match AudiusDataAccount::decode(&data).unwrap() {
+            AudiusDataAccount::PlaylistContents(decoded) => {
+                assert_eq!(decoded.track_ids, contents.track_ids)
+            }
+            _ => panic!("decoded as another account type"),
+        }
+
+        assert!(AudiusDataAccount::decode(&[0; 8]).is_err());
+        assert!(AudiusDataAccount::decode(&data[..4]).is_err());
+    }
+}
@@ -1,34 +1,198 @@
+/// Layout version written to every account allocated by this program
+/// Accounts allocated before versioning are treated as version 0 and upgraded by migrate_account
+pub const ACCOUNT_VERSION: u8 = 1;
+
 /// SECP Offset Struct constants
-pub const ETH_ADDRESS_OFFSET: usize = 12;
-pub const MESSAGE_OFFSET: usize = 97;
+/// Index of the secp256k1 program instruction in transactions carrying an Ethereum signature
+pub const SECP_INSTRUCTION_INDEX: u8 = 0;
+/// Prefix of the claim messages signed by a user's Ethereum key, separating them from other signed messages
+pub const CLAIM_MESSAGE_PREFIX: &[u8; 17] = b"audius-data-claim";
+/// Prefix of the recovery messages signed by a user's Ethereum key, so claims cannot be replayed as recoveries
+pub const RECOVERY_MESSAGE_PREFIX: &[u8; 20] = b"audius-data-recovery";
+/// Serialized size of SecpSignatureOffsets
+pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 11;
+/// Signature count (1) + offsets struct (11)
+pub const ETH_ADDRESS_OFFSET: usize = 1 + SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+/// Eth address offset (12) + eth address (20)
+pub const SIGNATURE_OFFSET: usize = ETH_ADDRESS_OFFSET + 20;
+/// Signature offset (32) + signature (64) + recovery id (1)
+pub const MESSAGE_OFFSET: usize = SIGNATURE_OFFSET + 65;
 
 /// Size of admin account
 pub const ADMIN_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
 32 + // authority: Pubkey
 32 + // verifier: Pubkey
-1; // is_write_enabled: bool
+1 + // is_write_enabled: bool
+32 + // pending_authority: Pubkey
+32 + // pending_verifier: Pubkey
+1 + // max_replica_set_size: u8
+1; // proposer_threshold: u8
+
+/// Maximum number of signers of an admin multisig
+pub const MAX_ADMIN_MULTISIG_SIGNERS: usize = 10;
+
+/// Size of admin multisig account, allocated for MAX_ADMIN_MULTISIG_SIGNERS
+pub const ADMIN_MULTISIG_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+32 + // admin: Pubkey
+4 + 32 * MAX_ADMIN_MULTISIG_SIGNERS + // signers: Vec<Pubkey>
+1; // threshold: u8
+
+/// Seed for AdminMultisig PDA
+pub const ADMIN_MULTISIG_SEED: &[u8; 14] = b"admin-multisig";
+
+/// Size of user account without its replica set entries
+pub const USER_ACCOUNT_BASE_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+20 + // eth_address: [u8; 20]
+32 + // authority: Pubkey
+4 + // replica set length: Vec<u16>
+1 + // is_verified: bool
+4 + // authority_epoch: u32
+8 + // authority_updated_at: i64
+8; // deactivated_at: i64
 
-/// Size of user account
-pub const USER_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+/// Size of user account for the given replica set length
+pub const fn user_account_size(replica_set_len: usize) -> usize {
+    USER_ACCOUNT_BASE_SIZE + 2 * replica_set_len // replica set entries: u16
+}
+
+/// Size of user accounts allocated before versioning, with a fixed replica set, migrated by migrate_account
+pub const LEGACY_USER_ACCOUNT_SIZE: usize = 8 + // anchor prefix
 20 + // eth_address: [u8; 20]
 6 + // replica set: [u16; 3]
 32; // authority: Pubkey
 
+/// Maximum length of the metadata CID logged by user and entity instructions, and stored by content nodes
+/// Fits CIDv1 strings of digests up to 64 bytes, a sha2-256 CIDv0 is 46 characters
+pub const MAX_METADATA_LENGTH: usize = 128;
+
+/// Default maximum replica set length, configurable by the admin authority
+pub const DEFAULT_MAX_REPLICA_SET_SIZE: u8 = 3;
+
+/// Default number of content node proposers required to create, update or delete a content node
+pub const DEFAULT_PROPOSER_THRESHOLD: u8 = 3;
+
+/// Size of user tombstone account, left at the PDA of a closed user
+pub const USER_TOMBSTONE_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+8; // closed_at: i64
+
+/// Size of user redirect account, left at a user's previous handle PDA
+pub const USER_REDIRECT_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+32; // user: Pubkey
+
 /// Size of user authority delegation account
 pub const USER_AUTHORITY_DELEGATE_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
 32 + // delegate_authority: Pubkey
-32; // user_storage_account: Pubkey
+32 + // user_storage_account: Pubkey
+32 + // admin: Pubkey
+2 + // permissions: u16
+1 + 8 + // expires_at: Option<i64>
+32 + // payer: Pubkey
+4; // authority_epoch: u32
+
+/// Delegate permission to update user metadata
+pub const DELEGATE_PERMISSION_UPDATE_USER: u16 = 1 << 0;
+/// Delegate permission to create, update and delete tracks and playlists
+pub const DELEGATE_PERMISSION_MANAGE_ENTITY: u16 = 1 << 1;
+/// Delegate permission to save and repost tracks and playlists
+pub const DELEGATE_PERMISSION_SOCIAL_ACTION: u16 = 1 << 2;
+/// Delegate permission to follow and unfollow users
+pub const DELEGATE_PERMISSION_FOLLOW: u16 = 1 << 3;
+/// Delegate permission to add and remove other delegates
+pub const DELEGATE_PERMISSION_MANAGE_DELEGATES: u16 = 1 << 4;
+/// All delegate permissions, held by the user's own authority
+pub const DELEGATE_PERMISSION_ALL: u16 = DELEGATE_PERMISSION_UPDATE_USER
+    | DELEGATE_PERMISSION_MANAGE_ENTITY
+    | DELEGATE_PERMISSION_SOCIAL_ACTION
+    | DELEGATE_PERMISSION_FOLLOW
+    | DELEGATE_PERMISSION_MANAGE_DELEGATES;
 
-/// Size of user content node account
-/// 8 bytes (anchor prefix) + 32 (PublicKey) + 20 (Ethereum PublicKey Bytes)
-pub const CONTENT_NODE_ACCOUNT_SIZE: usize = 8 + 32 + 20;
+/// Maximum length of the endpoint published by a content node's authority
+pub const MAX_CONTENT_NODE_ENDPOINT_LENGTH: usize = 128;
+
+/// Size of content node account, allocated for the maximum endpoint and metadata lengths
+pub const CONTENT_NODE_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+20 + // owner_eth_address: [u8; 20]
+32 + // authority: Pubkey
+4 + MAX_CONTENT_NODE_ENDPOINT_LENGTH + // endpoint: String
+4 + MAX_METADATA_LENGTH; // metadata: String
 
 /// Seed for content node accounts
 pub const CONTENT_NODE_SEED_PREFIX: &[u8; 5] = b"sp_id";
 
 /// Size of authority delegation account
 pub const AUTHORITY_DELEGATION_STATUS_ACCOUNT_SIZE: usize = 8 + // anchor prefix
-1; // is_revoked: bool
+1 + // version: u8
+32 + // admin: Pubkey
+1 + // is_revoked: bool
+32 + // revoked_by: Pubkey
+8; // revoked_at: i64
 
 /// Seed for AuthorityDelegation PDA
 pub const AUTHORITY_DELEGATION_STATUS_SEED: &[u8; 27] = b"authority-delegation-status";
+
+/// Size of follow account
+pub const FOLLOW_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+32 + // follower: Pubkey
+32 + // followee: Pubkey
+32; // payer: Pubkey
+
+/// Seed for Follow PDA
+pub const FOLLOW_SEED: &[u8; 6] = b"follow";
+
+/// Size of entity social action account
+pub const SOCIAL_ACTION_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+32 + // user: Pubkey
+1 + // entity_type: EntityTypes
+1 + // social_action_kind: SocialActionKinds
+32; // payer: Pubkey
+
+/// Seed for EntitySocialAction PDA
+pub const SOCIAL_ACTION_SEED: &[u8; 13] = b"social-action";
+
+/// Maximum number of actions in a write_social_actions batch
+/// Bounded by two limits, both asserted for full batches of follows and unfollows signed by a delegate:
+/// - Transaction size: the batch must fit in a PACKET_DATA_SIZE (1232 byte) transaction. Unfollows are the
+///   largest actions, and a batch of 7 takes 1224 bytes.
+/// - Compute: the batch must fit in the default budget of 200_000 compute units per instruction. Compute is only
+///   metered when the tests run against the SBF build of the program, with cargo test-sbf.
+pub const MAX_SOCIAL_ACTION_BATCH_SIZE: usize = 7;
+
+/// Size of entity account
+pub const ENTITY_ACCOUNT_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+32 + // owner: Pubkey
+32; // payer: Pubkey
+
+/// Seed for Entity PDA
+pub const ENTITY_SEED: &[u8; 6] = b"entity";
+
+/// Maximum number of tracks in a playlist
+pub const MAX_PLAYLIST_TRACKS: usize = 1000;
+
+/// Number of tracks a PlaylistContents account grows by when it is full
+pub const PLAYLIST_CONTENTS_CAPACITY_INCREMENT: usize = 16;
+
+/// Size of playlist contents account without its track entries
+pub const PLAYLIST_CONTENTS_ACCOUNT_BASE_SIZE: usize = 8 + // anchor prefix
+1 + // version: u8
+32 + // owner: Pubkey
+32 + // payer: Pubkey
+8 + // playlist_id: u64
+4; // track_ids length: Vec<u64>
+
+/// Size of playlist contents account allocated for the given number of tracks
+pub const fn playlist_contents_account_size(capacity: usize) -> usize {
+    PLAYLIST_CONTENTS_ACCOUNT_BASE_SIZE + 8 * capacity // track entries: u64
+}
+
+/// Seed for PlaylistContents PDA
+pub const PLAYLIST_CONTENTS_SEED: &[u8; 17] = b"playlist-contents";
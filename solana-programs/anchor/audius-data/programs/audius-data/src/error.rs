@@ -17,4 +17,60 @@ pub enum ErrorCode {
     SignatureVerification,
     #[msg("Invalid Id.")]
     InvalidId,
+    #[msg("This user is already followed.")]
+    FollowAlreadyExists,
+    #[msg("This user is not followed.")]
+    FollowNotFound,
+    #[msg("This social action has already been applied to the entity.")]
+    SocialActionAlreadyExists,
+    #[msg("This social action has not been applied to the entity.")]
+    SocialActionNotFound,
+    #[msg("An entity with this id already exists.")]
+    EntityAlreadyExists,
+    #[msg("No entity with this id exists.")]
+    EntityNotFound,
+    #[msg("This handle is already taken.")]
+    HandleAlreadyTaken,
+    #[msg("Invalid replica set.")]
+    InvalidReplicaSet,
+    #[msg("No content node with this sp_id exists.")]
+    ContentNodeNotFound,
+    #[msg("Not enough content node proposers.")]
+    InsufficientProposers,
+    #[msg("The proposer threshold must be at least one.")]
+    InvalidProposerThreshold,
+    #[msg("This delegate does not have permission to perform this action.")]
+    MissingPermission,
+    #[msg("This delegate authority has expired.")]
+    DelegateExpired,
+    #[msg("This delegate authority has not expired.")]
+    DelegateNotExpired,
+    #[msg("The delegate expiry must be in the future and no later than the expiry of the signing delegate.")]
+    InvalidDelegateExpiry,
+    #[msg("This account layout is not recognized.")]
+    UnknownAccountLayout,
+    #[msg("The social action batch is empty, too large or missing accounts.")]
+    InvalidSocialActionBatch,
+    #[msg("The metadata is not a valid CID or is too long.")]
+    InvalidMetadata,
+    #[msg("This signed claim has expired.")]
+    ClaimExpired,
+    #[msg("This user has already been claimed.")]
+    UserAlreadyClaimed,
+    #[msg("This user has not been claimed.")]
+    UserNotClaimed,
+    #[msg("This delegate was invalidated by a recovery of the user's account.")]
+    DelegateInvalidated,
+    #[msg("This user has been deactivated.")]
+    UserDeactivated,
+    #[msg("This user must be deactivated before it can be closed.")]
+    UserNotDeactivated,
+    #[msg("Multisig signers must be unique and no more than the maximum, with a threshold between 1 and their number.")]
+    InvalidMultisig,
+    #[msg("The playlist index is out of range or does not hold the expected track.")]
+    InvalidPlaylistIndex,
+    #[msg("This playlist has reached the maximum number of tracks.")]
+    PlaylistFull,
+    #[msg("The endpoint is not an http or https URL or is too long.")]
+    InvalidEndpoint,
 }
@@ -0,0 +1,247 @@
+//! Events emitted by the program instructions
+//! Each event carries the validated inputs of its instruction, so indexers can follow program activity
+//! from transaction logs without decoding instruction data.
+use crate::{
+    AudiusAdmin, EntitySocialActionValues, EntityTypes, ManagementActions, PlaylistContentsAction,
+    UserAction,
+};
+use anchor_lang::prelude::*;
+
+/// Emitted when the admin account is initialized or updated, with the resulting admin state
+#[event]
+pub struct AdminUpdated {
+    pub admin: Pubkey,
+    pub authority: Pubkey,
+    pub verifier: Pubkey,
+    pub is_write_enabled: bool,
+    pub pending_authority: Pubkey,
+    pub pending_verifier: Pubkey,
+    pub max_replica_set_size: u8,
+    pub proposer_threshold: u8,
+}
+
+impl AdminUpdated {
+    pub fn new(admin: &Account<AudiusAdmin>) -> Self {
+        AdminUpdated {
+            admin: admin.key(),
+            authority: admin.authority,
+            verifier: admin.verifier,
+            is_write_enabled: admin.is_write_enabled,
+            pending_authority: admin.pending_authority,
+            pending_verifier: admin.pending_verifier,
+            max_replica_set_size: admin.max_replica_set_size,
+            proposer_threshold: admin.proposer_threshold,
+        }
+    }
+}
+
+/// Emitted when an admin multisig is initialized or its signer set is changed
+#[event]
+pub struct AdminMultisigUpdated {
+    pub admin: Pubkey,
+    pub admin_multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Emitted when an account is upgraded to the current layout of its account type
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub version: u8,
+}
+
+/// Emitted when a user account is initialized by the admin or created by the user
+#[event]
+pub struct UserCreated {
+    pub user: Pubkey,
+    pub base: Pubkey,
+    pub handle_seed: [u8; 32],
+    pub eth_address: [u8; 20],
+    // Default Pubkey for users initialized by the admin until they claim their account
+    pub authority: Pubkey,
+    pub replica_set: Vec<u16>,
+    pub metadata: String,
+    // Only provided for users created without the admin
+    pub id: Option<u64>,
+}
+
+/// Emitted when a user claims their account and sets their Solana authority
+#[event]
+pub struct UserAuthorityUpdated {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when a user recovers their account with their Ethereum key and rotates its authority
+#[event]
+pub struct UserRecovered {
+    pub user: Pubkey,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    // Epoch started by the recovery, delegates added in earlier epochs are invalid
+    pub authority_epoch: u32,
+}
+
+/// Emitted when a user's metadata is updated
+#[event]
+pub struct UserUpdated {
+    pub user: Pubkey,
+    // Signer, the user's authority or one of its delegates
+    pub authority: Pubkey,
+    pub metadata: String,
+}
+
+/// Emitted when the admin verifier sets or clears a user's verification status
+#[event]
+pub struct UserVerificationUpdated {
+    pub user: Pubkey,
+    pub is_verified: bool,
+}
+
+/// Emitted when a user is deactivated by their authority or the admin
+#[event]
+pub struct UserDeactivated {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub deactivated_at: i64,
+}
+
+/// Emitted when a deactivated user account is closed and its rent reclaimed
+#[event]
+pub struct UserClosed {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when a user moves to the PDA derived from a new handle
+#[event]
+pub struct UserHandleChanged {
+    // User PDA of the previous handle, now a UserRedirect
+    pub previous_user: Pubkey,
+    pub user: Pubkey,
+    pub handle_seed: [u8; 32],
+}
+
+/// Emitted when a user's replica set changes
+#[event]
+pub struct ReplicaSetUpdated {
+    pub user: Pubkey,
+    // Signer, the user's authority or the authority of a content node in the replica set
+    pub authority: Pubkey,
+    pub replica_set: Vec<u16>,
+}
+
+/// Emitted when a content node is created or updated
+#[event]
+pub struct ContentNodeUpdated {
+    pub content_node: Pubkey,
+    pub sp_id: u16,
+    pub authority: Pubkey,
+    pub owner_eth_address: [u8; 20],
+    // Empty for content nodes created by the admin
+    pub proposer_sp_ids: Vec<u16>,
+}
+
+/// Emitted when a content node's authority rotates it to a new authority
+#[event]
+pub struct ContentNodeAuthorityUpdated {
+    pub content_node: Pubkey,
+    pub sp_id: u16,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when a content node's authority publishes its endpoint and metadata
+#[event]
+pub struct ContentNodeProfileUpdated {
+    pub content_node: Pubkey,
+    pub sp_id: u16,
+    pub authority: Pubkey,
+    pub endpoint: String,
+    pub metadata: String,
+}
+
+/// Emitted when a content node is deleted
+#[event]
+pub struct ContentNodeDeleted {
+    pub content_node: Pubkey,
+    pub sp_id: u16,
+    pub proposer_sp_ids: Vec<u16>,
+}
+
+/// Emitted when a track or playlist is created, updated or deleted
+#[event]
+pub struct EntityManaged {
+    pub user: Pubkey,
+    // Signer, the user's authority or one of its delegates
+    pub authority: Pubkey,
+    pub entity_type: EntityTypes,
+    pub management_action: ManagementActions,
+    pub id: u64,
+    pub metadata: String,
+}
+
+/// Emitted when a track is appended to, removed from or moved within a playlist
+#[event]
+pub struct PlaylistContentsUpdated {
+    pub user: Pubkey,
+    // Signer, the user's authority or one of its delegates
+    pub authority: Pubkey,
+    pub playlist_id: u64,
+    pub action: PlaylistContentsAction,
+    // Number of tracks in the playlist after the action
+    pub track_count: u32,
+}
+
+/// Emitted when a save or repost is added or deleted
+#[event]
+pub struct EntitySocialActionWritten {
+    pub user: Pubkey,
+    // Signer, the user's authority or one of its delegates
+    pub authority: Pubkey,
+    pub entity_social_action: EntitySocialActionValues,
+    pub entity_type: EntityTypes,
+    pub id: String,
+}
+
+/// Emitted when a user follows or unfollows another user
+#[event]
+pub struct FollowUpdated {
+    pub follower: Pubkey,
+    pub followee: Pubkey,
+    // Signer, the follower's authority or one of its delegates
+    pub authority: Pubkey,
+    pub user_action: UserAction,
+}
+
+/// Emitted when an authority's delegation status is initialized, revoked or reinstated
+#[event]
+pub struct AuthorityDelegationStatusUpdated {
+    pub delegate_authority: Pubkey,
+    pub is_revoked: bool,
+    // Signer, the delegate authority itself or the admin authority
+    pub authority: Pubkey,
+}
+
+/// Emitted when a delegate is added to a user
+#[event]
+pub struct UserAuthorityDelegateAdded {
+    pub user: Pubkey,
+    pub user_authority_delegate: Pubkey,
+    pub delegate_authority: Pubkey,
+    pub permissions: u16,
+    pub expires_at: Option<i64>,
+    // Signer, the user's authority or one of its delegates
+    pub authority: Pubkey,
+}
+
+/// Emitted when a delegate is removed from a user, or closed after expiring
+#[event]
+pub struct UserAuthorityDelegateRemoved {
+    pub user: Pubkey,
+    pub user_authority_delegate: Pubkey,
+    pub delegate_authority: Pubkey,
+    // Signer, the user's authority or one of its delegates - None when an expired delegate is closed
+    pub authority: Option<Pubkey>,
+}
@@ -1,21 +1,36 @@
 //! The Audius Data Program is intended to bring all user data functionality to Solana through the
 //! Anchor framework
+pub mod cid;
+// Off-chain instruction builders, not compiled into the on-chain program
+#[cfg(not(target_arch = "bpf"))]
+pub mod client;
+pub mod claim;
 pub mod constants;
 pub mod error;
+pub mod events;
+pub mod migration;
+pub mod multisig;
+pub mod playlist;
+pub mod secp;
 pub mod utils;
 
-use crate::{constants::*, error::ErrorCode, utils::*};
+use crate::{
+    cid::validate_metadata_cid,
+    claim::{verify_user_claim, ClaimKind, ClaimMessage},
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    migration::migrate_account_data,
+    multisig::*,
+    playlist::{apply_playlist_contents_action, close_playlist_contents, playlist_contents_capacity},
+    utils::*,
+};
 use anchor_lang::prelude::*;
-use std::collections::BTreeMap;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"); // default program ID to be replaced in start.sh
 
 #[program]
 pub mod audius_data {
-    use anchor_lang::solana_program::{
-        secp256k1_program,
-        sysvar
-    };
     use std::str::FromStr;
 
     /*
@@ -31,17 +46,23 @@ pub mod audius_data {
         verifier: Pubkey,
     ) -> Result<()> {
         let audius_admin = &mut ctx.accounts.admin;
+        audius_admin.version = ACCOUNT_VERSION;
         audius_admin.authority = authority;
         audius_admin.verifier = verifier;
         audius_admin.is_write_enabled = true;
+        audius_admin.max_replica_set_size = DEFAULT_MAX_REPLICA_SET_SIZE;
+        audius_admin.proposer_threshold = DEFAULT_PROPOSER_THRESHOLD;
+        emit!(AdminUpdated::new(audius_admin));
         Ok(())
     }
 
-    /// Verifies a user by asserting that the audius_admin's verifier matches the signed verifier account
+    /// Sets or clears a user's verification status
+    /// Asserts that the audius_admin's verifier matches the signed verifier account
     pub fn update_is_verified(
         ctx: Context<UpdateIsVerified>,
         base: Pubkey,
         _user_handle: UserHandle,
+        is_verified: bool,
     ) -> Result<()> {
         // Validate that the audius admin verifier matches the verifier passed in
         if ctx.accounts.audius_admin.verifier != ctx.accounts.verifier.key() {
@@ -57,6 +78,54 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
+        ctx.accounts.user.is_verified = is_verified;
+        emit!(UserVerificationUpdated {
+            user: ctx.accounts.user.key(),
+            is_verified,
+        });
+        Ok(())
+    }
+
+    /// Upgrade an account owned by this program from its layout before versioning to the current layout.
+    /// The account is reallocated with the payer covering the additional rent, and its fields are preserved.
+    /// Accounts are only ever grown, so no lamports leave the account. Current accounts are left untouched.
+    /// Delegation statuses and user delegates are bound to an admin when migrated, passed as the first
+    /// remaining account with the payer as its authority, followed by any multisig co-signers.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let account = &ctx.accounts.account;
+        if !is_program_account_initialized(ctx.program_id, account) {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        let admin = match ctx.remaining_accounts.split_first() {
+            Some((admin, co_signers)) => {
+                let audius_admin: Account<AudiusAdmin> = Account::try_from(admin)?;
+                validate_admin_signers(ctx.program_id, &audius_admin, &ctx.accounts.payer, co_signers)?;
+                Some(admin.key())
+            }
+            None => None,
+        };
+        let migrated = migrate_account_data(
+            &account.try_borrow_data()?,
+            &ctx.accounts.payer.key(),
+            admin.as_ref(),
+        )?;
+        if let Some(data) = migrated {
+            if data.len() < account.data_len() {
+                return Err(ErrorCode::UnknownAccountLayout.into());
+            }
+            resize_program_account(
+                account,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                data.len(),
+            )?;
+            account.try_borrow_mut_data()?.copy_from_slice(&data);
+            emit!(AccountMigrated {
+                account: account.key(),
+                version: ACCOUNT_VERSION,
+            });
+        }
         Ok(())
     }
 
@@ -64,16 +133,16 @@ pub mod audius_data {
     /// The user's account is derived from the admin base PDA + handle bytes.
     /// Populates the user account with their Ethereum address as bytes and an empty Pubkey for their Solana identity.
     /// Allows the user to later "claim" their account by submitting a signed object and setting their own identity.
-    /// Important to note that the metadata object is simply logged out to be picked up by the Audius indexing layer.
+    /// Important to note that the metadata CID is validated and simply logged out to be picked up by the Audius indexing layer.
+    /// The content node account for each replica set entry is passed in order as a remaining account.
     pub fn init_user(
         ctx: Context<InitializeUser>,
         base: Pubkey,
         eth_address: [u8; 20],
-        replica_set: [u16; 3],
-        _replica_set_bumps: [u8; 3],
+        replica_set: Vec<u16>,
         handle_seed: [u8; 32],
         _user_bump: u8,
-        _metadata: String,
+        metadata: String,
     ) -> Result<()> {
         // Confirm that the base used for user account seed is derived from this Audius admin storage account
         let (derived_base, _) = Pubkey::find_program_address(
@@ -94,14 +163,41 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
-        if ctx.accounts.authority.key() != ctx.accounts.admin.authority {
-            return Err(ErrorCode::Unauthorized.into());
-        }
+        // Replica set content nodes are followed by any additional admin signers
+        let (content_nodes, co_signers) = ctx
+            .remaining_accounts
+            .split_at(replica_set.len().min(ctx.remaining_accounts.len()));
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.authority,
+            co_signers,
+        )?;
+
+        validate_replica_set(
+            ctx.program_id,
+            &base,
+            &ctx.accounts.admin,
+            &replica_set,
+            content_nodes,
+        )?;
+        validate_metadata_cid(&metadata)?;
 
         let audius_user_acct = &mut ctx.accounts.user;
+        audius_user_acct.version = ACCOUNT_VERSION;
         audius_user_acct.eth_address = eth_address;
-        audius_user_acct.replica_set = replica_set;
-
+        audius_user_acct.replica_set = replica_set.clone();
+
+        emit!(UserCreated {
+            user: audius_user_acct.key(),
+            base,
+            handle_seed,
+            eth_address,
+            authority: audius_user_acct.authority,
+            replica_set,
+            metadata,
+            id: None,
+        });
         Ok(())
     }
 
@@ -134,46 +230,38 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
-        if ctx.accounts.authority.key() != ctx.accounts.admin.authority {
-            return Err(ErrorCode::Unauthorized.into());
-        }
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+        )?;
 
         let content_node = &mut ctx.accounts.content_node;
+        content_node.version = ACCOUNT_VERSION;
         content_node.owner_eth_address = owner_eth_address;
         content_node.authority = authority;
 
+        emit!(ContentNodeUpdated {
+            content_node: content_node.key(),
+            sp_id,
+            authority,
+            owner_eth_address,
+            proposer_sp_ids: vec![],
+        });
         Ok(())
     }
 
     /// Create a content node account from other content nodes.
+    /// Each proposer's content node account and signing authority are passed in order as remaining accounts.
     pub fn public_create_or_update_content_node(
         ctx: Context<PublicCreateOrUpdateContentNode>,
         base: Pubkey,
-        _p1: ProposerSeedBump,
-        _p2: ProposerSeedBump,
-        _p3: ProposerSeedBump,
-        _sp_id: u16,
+        proposer_sp_ids: Vec<u16>,
+        sp_id: u16,
         authority: Pubkey,
         owner_eth_address: [u8; 20],
     ) -> Result<()> {
-
-        // validate that there are no dupes in the replica
-        let proposers = [
-            (&ctx.accounts.proposer1, &ctx.accounts.proposer1_authority),
-            (&ctx.accounts.proposer2, &ctx.accounts.proposer2_authority),
-            (&ctx.accounts.proposer3, &ctx.accounts.proposer3_authority),
-        ];
-
-        // Ensure that no proposer's owner eth address is repeated
-        let mut eth_addresses = BTreeMap::new();
-        if !proposers.iter().all(move |(proposer, authority)| return match eth_addresses.insert(proposer.owner_eth_address, true) {
-            Some(_) => false,
-            None => proposer.authority == authority.key()
-        }) {
-            // duplicate owner eth address - err
-            return Err(ErrorCode::Unauthorized.into());
-        }
-
         // Confirm that the base used for user account seed is derived from this Audius admin storage account
         let (derived_base, _) = Pubkey::find_program_address(
             &[&ctx.accounts.admin.key().to_bytes()[..32]],
@@ -184,70 +272,149 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
+        validate_proposers(
+            ctx.program_id,
+            &base,
+            &ctx.accounts.admin,
+            &proposer_sp_ids,
+            ctx.remaining_accounts,
+        )?;
+
         let content_node = &mut ctx.accounts.content_node;
+        content_node.version = ACCOUNT_VERSION;
         content_node.owner_eth_address = owner_eth_address;
         content_node.authority = authority;
 
+        emit!(ContentNodeUpdated {
+            content_node: content_node.key(),
+            sp_id,
+            authority,
+            owner_eth_address,
+            proposer_sp_ids,
+        });
         Ok(())
     }
 
     /// Closes a content node account from other content nodes.
+    /// The content node's lamports are refunded to its authority, passed as the refund account.
+    /// Each proposer's content node account and signing authority are passed in order as remaining accounts.
     pub fn public_delete_content_node(
         ctx: Context<PublicDeleteContentNode>,
         base: Pubkey,
-        _p_delete: ProposerSeedBump,
-        _p1: ProposerSeedBump,
-        _p2: ProposerSeedBump,
-        _p3: ProposerSeedBump,
+        p_delete: ProposerSeedBump,
+        proposer_sp_ids: Vec<u16>,
     ) -> Result<()> {
+        // Confirm that the base used for user account seed is derived from this Audius admin storage account
+        let (derived_base, _) = Pubkey::find_program_address(
+            &[&ctx.accounts.admin.key().to_bytes()[..32]],
+            ctx.program_id,
+        );
+
+        if derived_base != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        validate_proposers(
+            ctx.program_id,
+            &base,
+            &ctx.accounts.admin,
+            &proposer_sp_ids,
+            ctx.remaining_accounts,
+        )?;
+
+        // The seed is the content node seed prefix followed by the little endian sp_id
+        let sp_id_offset = CONTENT_NODE_SEED_PREFIX.len();
+        emit!(ContentNodeDeleted {
+            content_node: ctx.accounts.content_node.key(),
+            sp_id: u16::from_le_bytes([
+                p_delete.seed[sp_id_offset],
+                p_delete.seed[sp_id_offset + 1],
+            ]),
+            proposer_sp_ids,
+        });
+
+        // The content node account is closed to the refund account on exit
+        Ok(())
+    }
 
-        if &ctx.accounts.admin.authority != &ctx.accounts.admin_authority.key() {
-            // todoL break
+    /// Rotate a content node's authority, signed by its current authority
+    /// The admin authority and the content node proposers can still replace the authority.
+    pub fn update_content_node_authority(
+        ctx: Context<UpdateContentNode>,
+        base: Pubkey,
+        sp_id: u16,
+        authority: Pubkey,
+    ) -> Result<()> {
+        // Confirm that the base used for content node account seed is derived from this Audius admin storage account
+        let (derived_base, _) = Pubkey::find_program_address(
+            &[&ctx.accounts.admin.key().to_bytes()[..32]],
+            ctx.program_id,
+        );
+        if derived_base != base {
             return Err(ErrorCode::Unauthorized.into());
         }
-        // validate that there are no dupes in the replica
-        let proposers = [
-            (&ctx.accounts.proposer1, &ctx.accounts.proposer1_authority),
-            (&ctx.accounts.proposer2, &ctx.accounts.proposer2_authority),
-            (&ctx.accounts.proposer3, &ctx.accounts.proposer3_authority),
-        ];
-
-        // Ensure that no proposer's owner eth address is repeated
-        let mut eth_addresses = BTreeMap::new();
-        if !proposers.iter().all(move |(proposer, authority)| return match eth_addresses.insert(proposer.owner_eth_address, true) {
-            Some(_) => false,
-            None => proposer.authority == authority.key()
-        }) {
-            // duplicate owner eth address - err
+
+        let content_node = &mut ctx.accounts.content_node;
+        if content_node.authority != ctx.accounts.authority.key() {
             return Err(ErrorCode::Unauthorized.into());
         }
+        content_node.authority = authority;
 
-        // Confirm that the base used for user account seed is derived from this Audius admin storage account
+        emit!(ContentNodeAuthorityUpdated {
+            content_node: content_node.key(),
+            sp_id,
+            previous_authority: ctx.accounts.authority.key(),
+            authority,
+        });
+        Ok(())
+    }
+
+    /// Publish a content node's endpoint and metadata CID, signed by its authority
+    /// Clients read the endpoints of a user's replica set from its content node accounts.
+    pub fn update_content_node_profile(
+        ctx: Context<UpdateContentNode>,
+        base: Pubkey,
+        sp_id: u16,
+        endpoint: String,
+        metadata: String,
+    ) -> Result<()> {
+        // Confirm that the base used for content node account seed is derived from this Audius admin storage account
         let (derived_base, _) = Pubkey::find_program_address(
             &[&ctx.accounts.admin.key().to_bytes()[..32]],
             ctx.program_id,
         );
-
         if derived_base != base {
             return Err(ErrorCode::Unauthorized.into());
         }
 
-        // Refer to context here - https://docs.solana.com/developing/programming-model/transactions#multiple-instructions-in-a-single-transaction
-        let dummy_owner_field = Pubkey::from_str("11111111111111111111111111111111").unwrap();
-
         let content_node = &mut ctx.accounts.content_node;
-        content_node.authority = dummy_owner_field;
-
+        if content_node.authority != ctx.accounts.authority.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        validate_content_node_endpoint(&endpoint)?;
+        validate_metadata_cid(&metadata)?;
+        content_node.endpoint = endpoint.clone();
+        content_node.metadata = metadata.clone();
+
+        emit!(ContentNodeProfileUpdated {
+            content_node: content_node.key(),
+            sp_id,
+            authority: ctx.accounts.authority.key(),
+            endpoint,
+            metadata,
+        });
         Ok(())
     }
 
     /// Update a user's replica set
+    /// The content node account for each replica set entry is passed in order as a remaining account.
+    /// The user account grows to fit a larger replica set, funded by the payer.
+    /// It is never shrunk, so no rent is refunded to whichever payer or content node signed.
     pub fn update_user_replica_set(
         ctx: Context<UpdateUserReplicaSet>,
         base: Pubkey,
         _user_handle: UserHandle,
-        replica_set: [u16;3],
-        _replica_set_bumps: [u8; 3]
+        replica_set: Vec<u16>,
     ) -> Result<()> {
 
         // Confirm that the base used for user account seed is derived from this Audius admin storage account
@@ -260,15 +427,22 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
+        let proposers = validate_replica_set(
+            ctx.program_id,
+            &base,
+            &ctx.accounts.admin,
+            &replica_set,
+            ctx.remaining_accounts,
+        )?;
+
         let user_replica_set = &ctx.accounts.user.replica_set;
 
         let did_user_sign = ctx.accounts.user.authority == ctx.accounts.cn_authority.key();
         let mut is_valid_authority = did_user_sign;
-        let proposers = [&ctx.accounts.cn1, &ctx.accounts.cn2, &ctx.accounts.cn3];
 
         // Validate that a content node in the replica signed the tx if the user's authority did not
         if !is_valid_authority {
-            for proposer in proposers {
+            for proposer in proposers.iter() {
                 if proposer.authority == ctx.accounts.cn_authority.key() {
                     is_valid_authority = true;
                     // Validate that one of the user's replica set nodes signed the transaction
@@ -278,8 +452,8 @@ pub mod audius_data {
                                 &base.to_bytes()[..32],
                                 CONTENT_NODE_SEED_PREFIX,
                                 &cn.to_le_bytes()
-                            ], &ctx.program_id);
-                        return cn_account_pda == proposer.key();
+                            ], ctx.program_id);
+                        cn_account_pda == proposer.key()
                     }) {
                         return Err(ErrorCode::Unauthorized.into());
                     }
@@ -291,63 +465,193 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
+        let user_info = ctx.accounts.user.to_account_info();
+        let space = user_account_size(replica_set.len());
+        if user_info.data_len() < space {
+            resize_program_account(
+                &user_info,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                space,
+            )?;
+        }
+
         let user_acct = &mut ctx.accounts.user;
-        user_acct.replica_set = replica_set;
+        user_acct.replica_set = replica_set.clone();
+        emit!(ReplicaSetUpdated {
+            user: user_acct.key(),
+            authority: ctx.accounts.cn_authority.key(),
+            replica_set,
+        });
         Ok(())
     }
     
+    /// Move a user account to the PDA derived from a new handle.
+    /// Copies the user into the new PDA and shrinks the old PDA into a UserRedirect pointing to it,
+    /// refunding the excess rent to the payer. The old handle stays reserved by the redirect.
+    /// Delegates are derived from the user PDA and must be added again for the new account.
+    /// Entities, follows and social actions remain keyed to the previous PDA, readers follow its redirect.
+    pub fn change_user_handle(
+        ctx: Context<ChangeUserHandle>,
+        base: Pubkey,
+        _user_handle: UserHandle,
+        new_handle_seed: [u8; 32],
+    ) -> Result<()> {
+        // Confirm that the base used for user account seed is derived from this Audius admin storage account
+        let (derived_base, _) = Pubkey::find_program_address(
+            &[&ctx.accounts.audius_admin.key().to_bytes()[..32]],
+            ctx.program_id,
+        );
+        if derived_base != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        let user = &ctx.accounts.user;
+        let new_user = &ctx.accounts.new_user;
+        let user_account = User::try_deserialize(&mut &user.try_borrow_data()?[..])?;
+        if user_account.authority != ctx.accounts.authority.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if user_account.deactivated_at != 0 {
+            return Err(ErrorCode::UserDeactivated.into());
+        }
+        if is_program_account_initialized(ctx.program_id, new_user) {
+            return Err(ErrorCode::HandleAlreadyTaken.into());
+        }
+
+        let new_user_bump = [*ctx.bumps.get("new_user").unwrap()];
+        create_program_account(
+            ctx.program_id,
+            new_user,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            &[&base.to_bytes()[..32], &new_handle_seed, &new_user_bump],
+            user_account_size(user_account.replica_set.len()),
+            &user_account,
+        )?;
+
+        resize_program_account(
+            user,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            USER_REDIRECT_ACCOUNT_SIZE,
+        )?;
+        let mut data = user.try_borrow_mut_data()?;
+        UserRedirect {
+            version: ACCOUNT_VERSION,
+            user: new_user.key(),
+        }.try_serialize(&mut &mut data[..])?;
+
+        emit!(UserHandleChanged {
+            previous_user: user.key(),
+            user: new_user.key(),
+            handle_seed: new_handle_seed,
+        });
+        Ok(())
+    }
+
     /// Functionality to confirm signed object and add a Solana Pubkey to a user's account.
     /// Performs instruction introspection and expects a minimum of 2 instructions [secp, current instruction].
+    /// The signed object is a ClaimMessage for this program, admin, user and authority expiring at `expires_at`,
+    /// which must not have passed.
+    /// A user can only be claimed once, while its authority is unset - later changes go through recover_user.
     pub fn init_user_sol(
         ctx: Context<InitializeUserSolIdentity>,
         user_authority: Pubkey,
+        expires_at: i64,
     ) -> Result<()> {
         let audius_user_acct = &mut ctx.accounts.user;
-        let index_current_instruction =
-            sysvar::instructions::load_current_index_checked(&ctx.accounts.sysvar_program)?;
-
-        // Instruction must contain at least one prior
-        if index_current_instruction < 1 {
-            return Err(ErrorCode::SignatureVerification.into());
+        if audius_user_acct.authority != Pubkey::default() {
+            return Err(ErrorCode::UserAlreadyClaimed.into());
         }
 
-        // Eth_address offset (12) + address (20) + signature (65) + message (32)
-        let secp_data =
-            sysvar::instructions::load_instruction_at_checked(0, &ctx.accounts.sysvar_program)?;
-
-        if secp_data.program_id != secp256k1_program::id() {
-            return Err(ErrorCode::Unauthorized.into());
-        }
-        let instruction_signer =
-            secp_data.data[ETH_ADDRESS_OFFSET..ETH_ADDRESS_OFFSET + 20].to_vec();
-
-        if instruction_signer != audius_user_acct.eth_address {
-            return Err(ErrorCode::Unauthorized.into());
-        }
+        let now = Clock::get()?.unix_timestamp;
+        verify_user_claim(
+            &ctx.accounts.sysvar_program,
+            ClaimKind::Claim,
+            &audius_user_acct.eth_address,
+            &ClaimMessage {
+                program_id: *ctx.program_id,
+                admin: ctx.accounts.audius_admin.key(),
+                user: audius_user_acct.key(),
+                authority: user_authority,
+                authority_epoch: audius_user_acct.authority_epoch,
+                expires_at,
+            },
+            now,
+        )?;
 
         audius_user_acct.authority = user_authority;
+        audius_user_acct.authority_updated_at = now;
 
-        let message = secp_data.data[MESSAGE_OFFSET..].to_vec();
+        emit!(UserAuthorityUpdated {
+            user: audius_user_acct.key(),
+            authority: user_authority,
+        });
+        Ok(())
+    }
 
-        if message != user_authority.to_bytes() {
-            return Err(ErrorCode::Unauthorized.into());
+    /// Rotate the authority of a claimed user with a signed RecoveryMessage from the user's Ethereum key
+    /// Performs the same instruction introspection as init_user_sol. Recovery starts a new authority epoch,
+    /// invalidating every delegate added before it and every claim or recovery signed for the previous epoch.
+    pub fn recover_user(
+        ctx: Context<InitializeUserSolIdentity>,
+        user_authority: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        let audius_user_acct = &mut ctx.accounts.user;
+        if audius_user_acct.authority == Pubkey::default() {
+            return Err(ErrorCode::UserNotClaimed.into());
         }
 
+        let now = Clock::get()?.unix_timestamp;
+        verify_user_claim(
+            &ctx.accounts.sysvar_program,
+            ClaimKind::Recovery,
+            &audius_user_acct.eth_address,
+            &ClaimMessage {
+                program_id: *ctx.program_id,
+                admin: ctx.accounts.audius_admin.key(),
+                user: audius_user_acct.key(),
+                authority: user_authority,
+                authority_epoch: audius_user_acct.authority_epoch,
+                expires_at,
+            },
+            now,
+        )?;
+
+        let previous_authority = audius_user_acct.authority;
+        audius_user_acct.authority = user_authority;
+        audius_user_acct.authority_epoch = audius_user_acct
+            .authority_epoch
+            .checked_add(1)
+            .ok_or(ErrorCode::Unauthorized)?;
+        audius_user_acct.authority_updated_at = now;
+
+        emit!(UserRecovered {
+            user: audius_user_acct.key(),
+            previous_authority,
+            authority: user_authority,
+            authority_epoch: audius_user_acct.authority_epoch,
+        });
         Ok(())
     }
 
     /// Functionality to create user without admin privileges
+    /// The content node account for each replica set entry is passed in order as a remaining account.
+    /// The secp instruction must carry a ClaimMessage for this program, admin, user and authority expiring at
+    /// `expires_at`, which must not have passed.
     pub fn create_user(
         ctx: Context<CreateUser>,
         base: Pubkey,
         eth_address: [u8; 20],
-        replica_set: [u16; 3],
-        _replica_set_bumps: [u8; 3],
-        _handle_seed: [u8; 32],
+        replica_set: Vec<u16>,
+        handle_seed: [u8; 32],
         _user_bump: u8,
-        _metadata: String,
-        _id: u64,
+        metadata: String,
+        id: u64,
         user_authority: Pubkey,
+        expires_at: i64,
     ) -> Result<()> {
         // Confirm that the base used for user account seed is derived from this Audius admin storage account
         let (derived_base, _) = Pubkey::find_program_address(
@@ -364,65 +668,371 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
-        // Eth_address offset (12) + address (20) + signature (65) + message (32)
-        let secp_data =
-            sysvar::instructions::load_instruction_at_checked(0, &ctx.accounts.sysvar_program)?;
-
-        if secp_data.program_id != secp256k1_program::id() {
-            return Err(ErrorCode::Unauthorized.into());
-        }
-        let instruction_signer =
-            secp_data.data[ETH_ADDRESS_OFFSET..ETH_ADDRESS_OFFSET + 20].to_vec();
-        if instruction_signer != eth_address {
-            return Err(ErrorCode::Unauthorized.into());
-        }
+        validate_replica_set(
+            ctx.program_id,
+            &base,
+            &ctx.accounts.audius_admin,
+            &replica_set,
+            ctx.remaining_accounts,
+        )?;
+        validate_metadata_cid(&metadata)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        verify_user_claim(
+            &ctx.accounts.sysvar_program,
+            ClaimKind::Claim,
+            &eth_address,
+            &ClaimMessage {
+                program_id: *ctx.program_id,
+                admin: ctx.accounts.audius_admin.key(),
+                user: ctx.accounts.user.key(),
+                authority: user_authority,
+                authority_epoch: 0,
+                expires_at,
+            },
+            now,
+        )?;
 
         let audius_user_acct = &mut ctx.accounts.user;
+        audius_user_acct.version = ACCOUNT_VERSION;
         audius_user_acct.eth_address = eth_address;
         audius_user_acct.authority = user_authority;
-        audius_user_acct.replica_set = replica_set;
-
-        let message = secp_data.data[MESSAGE_OFFSET..].to_vec();
-
-        if message != user_authority.to_bytes() {
-            return Err(ErrorCode::Unauthorized.into());
-        }
+        audius_user_acct.replica_set = replica_set.clone();
+        audius_user_acct.authority_updated_at = now;
+
+        emit!(UserCreated {
+            user: audius_user_acct.key(),
+            base,
+            handle_seed,
+            eth_address,
+            authority: user_authority,
+            replica_set,
+            metadata,
+            id: Some(id),
+        });
 
         Ok(())
     }
 
     /// Permissioned function to log an update to User metadata
-    pub fn update_user(ctx: Context<UpdateUser>, _metadata: String) -> Result<()> {
+    pub fn update_user(ctx: Context<UpdateUser>, metadata: String) -> Result<()> {
         validate_user_authority(
             ctx.program_id,
             &ctx.accounts.user,
             &ctx.accounts.user_authority_delegate,
             &ctx.accounts.user_authority,
             &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_UPDATE_USER,
         )?;
+        validate_metadata_cid(&metadata)?;
+        emit!(UserUpdated {
+            user: ctx.accounts.user.key(),
+            authority: ctx.accounts.user_authority.key(),
+            metadata,
+        });
+        Ok(())
+    }
+
+    /// Deactivate a user, signed by the user's authority or the admin authority
+    /// Deactivated users can no longer be updated, manage entities, write social actions or change delegates.
+    pub fn deactivate_user(
+        ctx: Context<DeactivateUser>,
+        base: Pubkey,
+        _user_handle: UserHandle,
+    ) -> Result<()> {
+        // Confirm that the base used for user account seed is derived from this Audius admin storage account
+        let (derived_base, _) = Pubkey::find_program_address(
+            &[&ctx.accounts.audius_admin.key().to_bytes()[..32]],
+            ctx.program_id,
+        );
+        if derived_base != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        validate_user_or_admin_authority(
+            ctx.program_id,
+            &ctx.accounts.audius_admin,
+            &ctx.accounts.user,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+        )?;
+
+        let user = &mut ctx.accounts.user;
+        if user.deactivated_at != 0 {
+            return Err(ErrorCode::UserDeactivated.into());
+        }
+        user.deactivated_at = Clock::get()?.unix_timestamp;
+        emit!(UserDeactivated {
+            user: user.key(),
+            authority: ctx.accounts.authority.key(),
+            deactivated_at: user.deactivated_at,
+        });
+        Ok(())
+    }
+
+    /// Close a deactivated user, refunding its rent to the signing user or admin authority
+    /// The user PDA is shrunk into a UserTombstone rather than freed, so that the handle can not be taken
+    /// again by a user inheriting the delegates, entities and follows keyed to the closed user.
+    /// Delegates of the user are left to expire and be closed by their payers.
+    pub fn close_user(
+        ctx: Context<CloseUser>,
+        base: Pubkey,
+        _user_handle: UserHandle,
+    ) -> Result<()> {
+        // Confirm that the base used for user account seed is derived from this Audius admin storage account
+        let (derived_base, _) = Pubkey::find_program_address(
+            &[&ctx.accounts.audius_admin.key().to_bytes()[..32]],
+            ctx.program_id,
+        );
+        if derived_base != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        let user = &ctx.accounts.user;
+        let user_account = User::try_deserialize(&mut &user.try_borrow_data()?[..])?;
+        validate_user_or_admin_authority(
+            ctx.program_id,
+            &ctx.accounts.audius_admin,
+            &user_account,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+        )?;
+        if user_account.deactivated_at == 0 {
+            return Err(ErrorCode::UserNotDeactivated.into());
+        }
+
+        resize_program_account(
+            user,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            USER_TOMBSTONE_ACCOUNT_SIZE,
+        )?;
+        let mut data = user.try_borrow_mut_data()?;
+        UserTombstone {
+            version: ACCOUNT_VERSION,
+            closed_at: Clock::get()?.unix_timestamp,
+        }.try_serialize(&mut &mut data[..])?;
+
+        emit!(UserClosed {
+            user: user.key(),
+            authority: ctx.accounts.authority.key(),
+        });
         Ok(())
     }
 
     /// Permissioned function to log an update to Admin metadata
     pub fn update_admin(ctx: Context<UpdateAdmin>, is_write_enabled: bool) -> Result<()> {
-        if ctx.accounts.admin.authority != ctx.accounts.admin_authority.key() {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        ctx.accounts.admin.is_write_enabled = is_write_enabled;
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Set the maximum replica set length accepted for users
+    pub fn update_max_replica_set_size(
+        ctx: Context<UpdateAdmin>,
+        max_replica_set_size: u8,
+    ) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        if max_replica_set_size == 0 {
+            return Err(ErrorCode::InvalidReplicaSet.into());
+        }
+        ctx.accounts.admin.max_replica_set_size = max_replica_set_size;
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Set the number of content node proposers required to create, update or delete a content node
+    pub fn update_proposer_threshold(
+        ctx: Context<UpdateAdmin>,
+        proposer_threshold: u8,
+    ) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        if proposer_threshold == 0 {
+            return Err(ErrorCode::InvalidProposerThreshold.into());
+        }
+        ctx.accounts.admin.proposer_threshold = proposer_threshold;
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Propose a new admin authority, to be accepted by the proposed authority
+    pub fn propose_admin_authority(
+        ctx: Context<UpdateAdmin>,
+        pending_authority: Pubkey,
+    ) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        ctx.accounts.admin.pending_authority = pending_authority;
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Accept a proposed admin authority, signed by the pending authority
+    /// A pending AdminMultisig accepts with its multisig account and threshold of signers as remaining accounts,
+    /// with one of the signers as the pending signer.
+    pub fn accept_admin_authority(ctx: Context<AcceptAdminRotation>) -> Result<()> {
+        let admin = &mut ctx.accounts.admin;
+        if admin.pending_authority == Pubkey::default() {
             return Err(ErrorCode::Unauthorized.into());
         }
-        ctx.accounts.admin.is_write_enabled = is_write_enabled;
+        validate_authority_signers(
+            ctx.program_id,
+            &admin.pending_authority,
+            &ctx.accounts.pending_signer,
+            ctx.remaining_accounts,
+        )?;
+        admin.authority = admin.pending_authority;
+        admin.pending_authority = Pubkey::default();
+        emit!(AdminUpdated::new(admin));
+        Ok(())
+    }
+
+    /// Cancel a proposed admin authority
+    pub fn cancel_admin_authority(ctx: Context<UpdateAdmin>) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        ctx.accounts.admin.pending_authority = Pubkey::default();
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Propose a new verifier, to be accepted by the proposed verifier
+    pub fn propose_admin_verifier(
+        ctx: Context<UpdateAdmin>,
+        pending_verifier: Pubkey,
+    ) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        ctx.accounts.admin.pending_verifier = pending_verifier;
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Accept a proposed verifier, signed by the pending verifier
+    pub fn accept_admin_verifier(ctx: Context<AcceptAdminRotation>) -> Result<()> {
+        let admin = &mut ctx.accounts.admin;
+        if admin.pending_verifier == Pubkey::default()
+            || admin.pending_verifier != ctx.accounts.pending_signer.key()
+        {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        admin.verifier = admin.pending_verifier;
+        admin.pending_verifier = Pubkey::default();
+        emit!(AdminUpdated::new(admin));
+        Ok(())
+    }
+
+    /// Cancel a proposed verifier
+    pub fn cancel_admin_verifier(ctx: Context<UpdateAdmin>) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        ctx.accounts.admin.pending_verifier = Pubkey::default();
+        emit!(AdminUpdated::new(&ctx.accounts.admin));
+        Ok(())
+    }
+
+    /// Initialize the admin multisig, an M-of-N signer set derived from the admin account
+    /// Signed by the admin authority. The multisig becomes the admin authority once proposed with
+    /// propose_admin_authority and accepted by its threshold of signers.
+    pub fn init_admin_multisig(
+        ctx: Context<InitAdminMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        validate_admin_signers(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.admin_authority,
+            ctx.remaining_accounts,
+        )?;
+        validate_multisig_config(&signers, threshold)?;
+
+        let admin_multisig = &mut ctx.accounts.admin_multisig;
+        admin_multisig.version = ACCOUNT_VERSION;
+        admin_multisig.admin = ctx.accounts.admin.key();
+        admin_multisig.signers = signers.clone();
+        admin_multisig.threshold = threshold;
+        emit!(AdminMultisigUpdated {
+            admin: admin_multisig.admin,
+            admin_multisig: admin_multisig.key(),
+            signers,
+            threshold,
+        });
+        Ok(())
+    }
+
+    /// Change the signer set and threshold of the admin multisig
+    /// Signed by the current threshold of multisig signers, one as the signer and the rest as remaining accounts.
+    pub fn update_admin_multisig(
+        ctx: Context<UpdateAdminMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        validate_multisig_signers(
+            &ctx.accounts.admin_multisig,
+            &signer_keys(&ctx.accounts.signer, ctx.remaining_accounts),
+        )?;
+        validate_multisig_config(&signers, threshold)?;
+
+        let admin_multisig = &mut ctx.accounts.admin_multisig;
+        admin_multisig.signers = signers.clone();
+        admin_multisig.threshold = threshold;
+        emit!(AdminMultisigUpdated {
+            admin: admin_multisig.admin,
+            admin_multisig: admin_multisig.key(),
+            signers,
+            threshold,
+        });
         Ok(())
     }
 
     /*
         Entity related functions
     */
-    pub fn manage_entity(
-        ctx: Context<ManageEntity>,
+    /// Create, update or delete a track or playlist
+    /// Ownership is recorded in an Entity PDA derived from the base, entity type and id - allocated on create
+    /// and closed on delete. Update and delete must be submitted on behalf of the owning user.
+    /// Deletes return the rent to the payer recorded in the entity, passed as the first remaining account.
+    /// Playlist deletes also close the playlist's PlaylistContents PDA, passed as the second remaining account
+    /// followed by the payer recorded in it.
+    pub fn manage_entity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ManageEntity<'info>>,
         base: Pubkey,
         _user_handle: UserHandle,
-        _entity_type: EntityTypes,
-        _management_action: ManagementActions,
-        _id: u64,
-        _metadata: String,
+        entity_type: EntityTypes,
+        management_action: ManagementActions,
+        id: u64,
+        metadata: String,
     ) -> Result<()> {
         // Confirm the base PDA matches the expected value provided the target audius admin
         let admin_key: &Pubkey = &ctx.accounts.audius_admin.key();
@@ -439,18 +1049,258 @@ pub mod audius_data {
             &ctx.accounts.user_authority_delegate,
             &ctx.accounts.authority,
             &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_MANAGE_ENTITY,
         )?;
-        
+
+        // Deletes carry no metadata, the CID is only validated when one is provided
+        if management_action != ManagementActions::Delete || !metadata.is_empty() {
+            validate_metadata_cid(&metadata)?;
+        }
+
+        let entity = &ctx.accounts.entity;
+        let user = ctx.accounts.user.key();
+
+        if management_action == ManagementActions::Create {
+            if is_program_account_initialized(ctx.program_id, entity) {
+                return Err(ErrorCode::EntityAlreadyExists.into());
+            }
+            let entity_type_seed = [entity_type.clone() as u8];
+            let entity_bump = [*ctx.bumps.get("entity").unwrap()];
+            create_program_account(
+                ctx.program_id,
+                entity,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                &[
+                    &base.to_bytes()[..32],
+                    ENTITY_SEED,
+                    &entity_type_seed,
+                    &id.to_le_bytes(),
+                    &entity_bump,
+                ],
+                ENTITY_ACCOUNT_SIZE,
+                &Entity {
+                    version: ACCOUNT_VERSION,
+                    owner: user,
+                    payer: ctx.accounts.payer.key(),
+                },
+            )?;
+        } else {
+            if !is_program_account_initialized(ctx.program_id, entity) {
+                return Err(ErrorCode::EntityNotFound.into());
+            }
+            let entity_account = Entity::try_deserialize(&mut &entity.try_borrow_data()?[..])?;
+
+            // Reject if the entity is not owned by the user the authority acts on behalf of
+            if entity_account.owner != user {
+                return Err(ErrorCode::Unauthorized.into());
+            }
+
+            if management_action == ManagementActions::Delete {
+                let entity_payer = recorded_payer_account(
+                    ctx.remaining_accounts.first(),
+                    &entity_account.payer,
+                )?;
+                // A playlist re-created with the same id must not inherit the tracks of the deleted one
+                if entity_type == EntityTypes::Playlist {
+                    close_playlist_contents(
+                        ctx.program_id,
+                        &base,
+                        id,
+                        ctx.remaining_accounts.get(1..).unwrap_or_default(),
+                    )?;
+                }
+                close_program_account(entity, entity_payer)?;
+            }
+        }
+
+        emit!(EntityManaged {
+            user,
+            authority: ctx.accounts.authority.key(),
+            entity_type,
+            management_action,
+            id,
+            metadata,
+        });
         Ok(())
     }
 
-    pub fn write_entity_social_action(
-        ctx: Context<WriteEntitySocialAction>,
+    /// Append a track to a playlist, or remove or move one of its tracks
+    /// The track list is stored in a PlaylistContents PDA derived from the base and playlist id - allocated
+    /// by the first append and grown by PLAYLIST_CONTENTS_CAPACITY_INCREMENT tracks whenever it is full.
+    /// The payer of the first append funds every later growth, and is refunded when the playlist is deleted.
+    /// Must be submitted on behalf of the user owning the playlist Entity.
+    pub fn manage_playlist_contents(
+        ctx: Context<ManagePlaylistContents>,
+        base: Pubkey,
+        _user_handle: UserHandle,
+        playlist_id: u64,
+        action: PlaylistContentsAction,
+    ) -> Result<()> {
+        // Confirm the base PDA matches the expected value provided the target audius admin
+        let admin_key: &Pubkey = &ctx.accounts.audius_admin.key();
+        let (base_pda, _bump) =
+            Pubkey::find_program_address(&[&admin_key.to_bytes()[..32]], ctx.program_id);
+        if base_pda != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        // Reject if update submitted with invalid user authority
+        validate_user_authority(
+            ctx.program_id,
+            &ctx.accounts.user,
+            &ctx.accounts.user_authority_delegate,
+            &ctx.accounts.authority,
+            &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_MANAGE_ENTITY,
+        )?;
+
+        // Reject if the playlist is not owned by the user the authority acts on behalf of
+        let playlist = &ctx.accounts.playlist;
+        let user = ctx.accounts.user.key();
+        if !is_program_account_initialized(ctx.program_id, playlist) {
+            return Err(ErrorCode::EntityNotFound.into());
+        }
+        let playlist_account = Entity::try_deserialize(&mut &playlist.try_borrow_data()?[..])?;
+        if playlist_account.owner != user {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        let playlist_contents = &ctx.accounts.playlist_contents;
+        let is_initialized = is_program_account_initialized(ctx.program_id, playlist_contents);
+        let mut contents = if is_initialized {
+            let contents = PlaylistContents::try_deserialize(
+                &mut &playlist_contents.try_borrow_data()?[..],
+            )?;
+            if contents.owner != user {
+                return Err(ErrorCode::Unauthorized.into());
+            }
+            contents
+        } else {
+            PlaylistContents {
+                version: ACCOUNT_VERSION,
+                owner: user,
+                payer: ctx.accounts.payer.key(),
+                playlist_id,
+                track_ids: Vec::new(),
+            }
+        };
+        apply_playlist_contents_action(&mut contents.track_ids, &action)?;
+
+        let space = playlist_contents_account_size(playlist_contents_capacity(
+            contents.track_ids.len(),
+        ));
+        if !is_initialized {
+            let contents_bump = [*ctx.bumps.get("playlist_contents").unwrap()];
+            create_program_account(
+                ctx.program_id,
+                playlist_contents,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                &[
+                    &base.to_bytes()[..32],
+                    PLAYLIST_CONTENTS_SEED,
+                    &playlist_id.to_le_bytes(),
+                    &contents_bump,
+                ],
+                space,
+                &contents,
+            )?;
+        } else {
+            // Accounts only grow, removing tracks leaves their capacity for later appends
+            if playlist_contents.data_len() < space {
+                // All of the rent is refunded to the recorded payer, so only it may fund the growth
+                if ctx.accounts.payer.key() != contents.payer {
+                    return Err(ErrorCode::Unauthorized.into());
+                }
+                resize_program_account(
+                    playlist_contents,
+                    &ctx.accounts.payer,
+                    &ctx.accounts.system_program,
+                    space,
+                )?;
+            }
+            contents.try_serialize(&mut &mut playlist_contents.try_borrow_mut_data()?[..])?;
+        }
+
+        emit!(PlaylistContentsUpdated {
+            user,
+            authority: ctx.accounts.authority.key(),
+            playlist_id,
+            action,
+            track_count: contents.track_ids.len() as u32,
+        });
+        Ok(())
+    }
+
+    /// Save or repost an entity, or remove an existing save or repost
+    /// Each save and repost is recorded as an EntitySocialAction PDA derived from the user, entity type,
+    /// social action kind and entity id - allocated by the add actions and closed by the delete actions.
+    /// The delete actions return the rent to the payer of the social action, passed as the first remaining account.
+    pub fn write_entity_social_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, WriteEntitySocialAction<'info>>,
+        base: Pubkey,
+        _user_handle: UserHandle,
+        entity_social_action: EntitySocialActionValues,
+        entity_type: EntityTypes,
+        id: String,
+    ) -> Result<()> {
+        let admin_key: &Pubkey = &ctx.accounts.audius_admin.key();
+        let (base_pda, _bump) =
+            Pubkey::find_program_address(&[&admin_key.to_bytes()[..32]], ctx.program_id);
+
+        // Confirm the base PDA matches the expected value provided the target audius admin
+        if base_pda != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        // validate user has authority and check for delegate
+        validate_user_authority(
+            ctx.program_id,
+            &ctx.accounts.user,
+            &ctx.accounts.user_authority_delegate,
+            &ctx.accounts.authority,
+            &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_SOCIAL_ACTION,
+        )?;
+
+        apply_entity_social_action(
+            ctx.program_id,
+            &ctx.accounts.user.key(),
+            &entity_social_action,
+            &entity_type,
+            &id,
+            &ctx.accounts.social_action,
+            &ctx.accounts.payer,
+            ctx.remaining_accounts.first(),
+            &ctx.accounts.system_program,
+        )?;
+
+        emit!(EntitySocialActionWritten {
+            user: ctx.accounts.user.key(),
+            authority: ctx.accounts.authority.key(),
+            entity_social_action,
+            entity_type,
+            id,
+        });
+        Ok(())
+    }
+
+    /// Follow a user, transaction sent from 1 known valid user to another
+    /// Both User accounts are re-derived from the handle seed and validated
+    /// Only the follower must have already claimed their solana public key -
+    /// in order to facilitate the scenario where an 'initialized' user follows an 'unitialized' user
+    /// Note that both follow and unfollow are handled in this single function through an enum, with identical
+    /// validation for both paths.
+    /// A follow is recorded as a Follow PDA derived from the follower and followee accounts, allocated on
+    /// follow and closed on unfollow with rent returned to the payer of the follow, passed as the first
+    /// remaining account.
+    pub fn follow_user<'info>(
+        ctx: Context<'_, '_, '_, 'info, FollowUser<'info>>,
         base: Pubkey,
-        _user_handle: UserHandle,
-        _entity_social_action: EntitySocialActionValues,
-        _entity_type: EntityTypes,
-        _id: String,
+        user_action: UserAction,
+        _follower_handle: UserHandle,
+        _followee_handle: UserHandle,
     ) -> Result<()> {
         let admin_key: &Pubkey = &ctx.accounts.audius_admin.key();
         let (base_pda, _bump) =
@@ -464,27 +1314,46 @@ pub mod audius_data {
         // validate user has authority and check for delegate
         validate_user_authority(
             ctx.program_id,
-            &ctx.accounts.user,
+            &ctx.accounts.follower_user_storage,
             &ctx.accounts.user_authority_delegate,
             &ctx.accounts.authority,
             &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_FOLLOW,
+        )?;
+
+        let follower = ctx.accounts.follower_user_storage.key();
+        let followee = ctx.accounts.followee_user_storage.key();
+        apply_follow(
+            ctx.program_id,
+            &follower,
+            &followee,
+            &user_action,
+            &ctx.accounts.follow,
+            &ctx.accounts.payer,
+            ctx.remaining_accounts.first(),
+            &ctx.accounts.system_program,
         )?;
 
+        emit!(FollowUpdated {
+            follower,
+            followee,
+            authority: ctx.accounts.authority.key(),
+            user_action,
+        });
         Ok(())
     }
-
-    /// Follow a user, transaction sent from 1 known valid user to another
-    /// Both User accounts are re-derived from the handle seed and validated
-    /// Only the follower must have already claimed their solana public key -
-    /// in order to facilitate the scenario where an 'initialized' user follows an 'unitialized' user
-    /// Note that both follow and unfollow are handled in this single function through an enum, with identical
-    /// validation for both paths.
-    pub fn follow_user(
-        ctx: Context<FollowUser>,
+    
+    /// Apply a batch of follows, unfollows, saves and reposts on behalf of one user.
+    /// Authority is validated once for the batch, and a delegate signer must hold every permission the batch requires.
+    /// Batches hold at most MAX_SOCIAL_ACTION_BATCH_SIZE actions.
+    /// The accounts of each action are passed in order as remaining accounts: the followee user account and Follow PDA
+    /// for a follow or unfollow, followed by the payer of the follow for an unfollow, the EntitySocialAction PDA for
+    /// a save or repost, followed by the payer of the social action for a delete.
+    pub fn write_social_actions<'info>(
+        ctx: Context<'_, '_, '_, 'info, WriteSocialActions<'info>>,
         base: Pubkey,
-        _user_action: UserAction,
-        _follower_handle: UserHandle,
-        _followee_handle: UserHandle,
+        _user_handle: UserHandle,
+        actions: Vec<SocialAction>,
     ) -> Result<()> {
         let admin_key: &Pubkey = &ctx.accounts.audius_admin.key();
         let (base_pda, _bump) =
@@ -495,64 +1364,280 @@ pub mod audius_data {
             return Err(ErrorCode::Unauthorized.into());
         }
 
+        if actions.is_empty() || actions.len() > MAX_SOCIAL_ACTION_BATCH_SIZE {
+            return Err(ErrorCode::InvalidSocialActionBatch.into());
+        }
+
         // validate user has authority and check for delegate
+        let required_permission = actions.iter().fold(0, |permissions, action| {
+            permissions
+                | match action {
+                    SocialAction::Follow { .. } => DELEGATE_PERMISSION_FOLLOW,
+                    SocialAction::EntitySocialAction { .. } => DELEGATE_PERMISSION_SOCIAL_ACTION,
+                }
+        });
         validate_user_authority(
             ctx.program_id,
-            &ctx.accounts.follower_user_storage,
+            &ctx.accounts.user,
             &ctx.accounts.user_authority_delegate,
             &ctx.accounts.authority,
             &ctx.accounts.authority_delegation_status,
+            required_permission,
         )?;
 
+        let user = ctx.accounts.user.key();
+        let authority = ctx.accounts.authority.key();
+        let mut remaining_accounts = ctx.remaining_accounts.iter();
+        for action in actions {
+            match action {
+                SocialAction::Follow {
+                    user_action,
+                    followee_handle,
+                } => {
+                    let (followee, follow) =
+                        match (remaining_accounts.next(), remaining_accounts.next()) {
+                            (Some(followee), Some(follow)) => (followee, follow),
+                            _ => return Err(ErrorCode::InvalidSocialActionBatch.into()),
+                        };
+
+                    // Confirm the followee PDA matches the expected value provided the target handle and base
+                    let derived_followee = Pubkey::create_program_address(
+                        &[
+                            &base.to_bytes()[..32],
+                            followee_handle.seed.as_ref(),
+                            &[followee_handle.bump],
+                        ],
+                        ctx.program_id,
+                    )
+                    .map_err(|_| ErrorCode::ProgramDerivedAddressNotFound)?;
+                    if derived_followee != followee.key() {
+                        return Err(ErrorCode::ProgramDerivedAddressNotFound.into());
+                    }
+                    // Confirm the followee is a user account
+                    Account::<User>::try_from(followee)?;
+
+                    let recorded_payer = match user_action {
+                        UserAction::FollowUser => None,
+                        UserAction::UnfollowUser => Some(
+                            remaining_accounts
+                                .next()
+                                .ok_or(ErrorCode::InvalidSocialActionBatch)?,
+                        ),
+                    };
+                    apply_follow(
+                        ctx.program_id,
+                        &user,
+                        &followee.key(),
+                        &user_action,
+                        follow,
+                        &ctx.accounts.payer,
+                        recorded_payer,
+                        &ctx.accounts.system_program,
+                    )?;
+                    emit!(FollowUpdated {
+                        follower: user,
+                        followee: followee.key(),
+                        authority,
+                        user_action,
+                    });
+                }
+                SocialAction::EntitySocialAction {
+                    entity_social_action,
+                    entity_type,
+                    id,
+                } => {
+                    let social_action = remaining_accounts
+                        .next()
+                        .ok_or(ErrorCode::InvalidSocialActionBatch)?;
+                    let recorded_payer = if entity_social_action.is_delete() {
+                        Some(
+                            remaining_accounts
+                                .next()
+                                .ok_or(ErrorCode::InvalidSocialActionBatch)?,
+                        )
+                    } else {
+                        None
+                    };
+                    apply_entity_social_action(
+                        ctx.program_id,
+                        &user,
+                        &entity_social_action,
+                        &entity_type,
+                        &id,
+                        social_action,
+                        &ctx.accounts.payer,
+                        recorded_payer,
+                        &ctx.accounts.system_program,
+                    )?;
+                    emit!(EntitySocialActionWritten {
+                        user,
+                        authority,
+                        entity_social_action,
+                        entity_type,
+                        id,
+                    });
+                }
+            }
+        }
+
+        // Reject accounts that do not belong to any action
+        if remaining_accounts.next().is_some() {
+            return Err(ErrorCode::InvalidSocialActionBatch.into());
+        }
+
         Ok(())
     }
-    
+
     /// Initializes a AuthorityDelegation PDA for an authority
     pub fn init_authority_delegation_status(
         ctx: Context<InitAuthorityDelegationStatus>,
         _authority_name: String,
     ) -> Result<()> {
 
+        ctx.accounts.authority_delegation_status_pda.version = ACCOUNT_VERSION;
+        ctx.accounts.authority_delegation_status_pda.admin = ctx.accounts.admin.key();
         ctx.accounts.authority_delegation_status_pda.is_revoked = false;
 
+        emit!(AuthorityDelegationStatusUpdated {
+            delegate_authority: ctx.accounts.delegate_authority.key(),
+            is_revoked: false,
+            authority: ctx.accounts.delegate_authority.key(),
+        });
         Ok(())
     }
 
-    /// Revokes an authority's delegation
+    /// Revokes an authority's delegation, recording the signer and time of the revocation
+    /// Must be signed by the delegated authority itself or the admin authority
+    /// A delegation already revoked is left recorded as is when the delegated authority revokes it again
     pub fn revoke_authority_delegation(
-        ctx: Context<RevokeAuthorityDelegationStatus>,
+        ctx: Context<UpdateAuthorityDelegationStatus>,
+        _authority_delegation_bump: u8,
+    ) -> Result<()> {
+        validate_delegation_status_authority(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.delegate_authority,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+            true,
+        )?;
+
+        let is_delegate_signer = ctx.accounts.authority.key() == ctx.accounts.delegate_authority.key();
+        let authority_delegation_status = &mut ctx.accounts.authority_delegation_status_pda;
+        if !(authority_delegation_status.is_revoked && is_delegate_signer) {
+            authority_delegation_status.is_revoked = true;
+            authority_delegation_status.revoked_by = ctx.accounts.authority.key();
+            authority_delegation_status.revoked_at = Clock::get()?.unix_timestamp;
+        }
+
+        emit!(AuthorityDelegationStatusUpdated {
+            delegate_authority: ctx.accounts.delegate_authority.key(),
+            is_revoked: true,
+            authority: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    /// Reinstates a revoked authority's delegation
+    /// Must be signed by the admin authority, or by the delegated authority itself if it revoked its own delegation
+    /// The most recent revocation remains recorded in revoked_by and revoked_at
+    pub fn reinstate_authority_delegation(
+        ctx: Context<UpdateAuthorityDelegationStatus>,
         _authority_delegation_bump: u8,
     ) -> Result<()> {
-        // TODO add user validation
+        let authority_delegation_status = &ctx.accounts.authority_delegation_status_pda;
+        validate_delegation_status_authority(
+            ctx.program_id,
+            &ctx.accounts.admin,
+            &ctx.accounts.delegate_authority,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+            authority_delegation_status.revoked_by == ctx.accounts.delegate_authority.key(),
+        )?;
 
-        ctx.accounts.authority_delegation_status_pda.is_revoked = true;
+        ctx.accounts.authority_delegation_status_pda.is_revoked = false;
 
+        emit!(AuthorityDelegationStatusUpdated {
+            delegate_authority: ctx.accounts.delegate_authority.key(),
+            is_revoked: false,
+            authority: ctx.accounts.authority.key(),
+        });
         Ok(())
     }
 
     /// Enable an account to perform actions on behalf of a given user
+    /// `permissions` is a bitmask of the DELEGATE_PERMISSION_* actions the delegate may perform.
+    /// `expires_at` is an optional unix timestamp from which the delegate can no longer act for the user.
+    /// A delegate signer may only grant permissions it holds itself, and may not grant a later expiry than its own.
+    /// The delegate records the admin of the user, and may only act while its delegation status is bound to it.
     pub fn add_user_authority_delegate(
         ctx: Context<AddUserAuthorityDelegate>,
-        _base: Pubkey,
+        base: Pubkey,
         _handle_seed: [u8; 32],
         _user_bump: u8,
         user_authority_delegate: Pubkey,
+        permissions: u16,
+        expires_at: Option<i64>,
     ) -> Result<()> {
+        // Confirm that the base used for user account seed is derived from this Audius admin storage account
+        let (derived_base, _) = Pubkey::find_program_address(
+            &[&ctx.accounts.admin.key().to_bytes()[..32]],
+            ctx.program_id,
+        );
+        if derived_base != base {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
         // validate signer is the user or delegate
-        validate_user_authority(
+        let (signer_permissions, signer_expires_at) = validate_user_authority(
             ctx.program_id,
             &ctx.accounts.user,
             &ctx.accounts.signer_user_authority_delegate,
             &ctx.accounts.authority,
             &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_MANAGE_DELEGATES,
         )?;
 
+        // Reject unknown permissions and permissions the signer does not hold
+        if permissions & !signer_permissions != 0 {
+            return Err(ErrorCode::MissingPermission.into());
+        }
+
+        // Reject expiries in the past and delegates that would outlive the signing delegate
+        let now = Clock::get()?.unix_timestamp;
+        match (expires_at, signer_expires_at) {
+            (Some(expires_at), _) if expires_at <= now => {
+                return Err(ErrorCode::InvalidDelegateExpiry.into());
+            }
+            (Some(expires_at), Some(signer_expires_at)) if expires_at > signer_expires_at => {
+                return Err(ErrorCode::InvalidDelegateExpiry.into());
+            }
+            (None, Some(_)) => return Err(ErrorCode::InvalidDelegateExpiry.into()),
+            _ => {}
+        }
+
         // Assign incoming delegate fields
         // Maintain the user's storage account and the incoming delegate authority key
+        ctx.accounts.current_user_authority_delegate.version = ACCOUNT_VERSION;
         ctx.accounts
             .current_user_authority_delegate
             .user_storage_account = ctx.accounts.user.key();
+        ctx.accounts.current_user_authority_delegate.admin = ctx.accounts.admin.key();
         ctx.accounts.current_user_authority_delegate.delegate_authority = user_authority_delegate;
+        ctx.accounts.current_user_authority_delegate.permissions = permissions;
+        ctx.accounts.current_user_authority_delegate.expires_at = expires_at;
+        ctx.accounts.current_user_authority_delegate.payer = ctx.accounts.payer.key();
+        ctx.accounts.current_user_authority_delegate.authority_epoch =
+            ctx.accounts.user.authority_epoch;
+
+        emit!(UserAuthorityDelegateAdded {
+            user: ctx.accounts.user.key(),
+            user_authority_delegate: ctx.accounts.current_user_authority_delegate.key(),
+            delegate_authority: user_authority_delegate,
+            permissions,
+            expires_at,
+            authority: ctx.accounts.authority.key(),
+        });
         Ok(())
     }
 
@@ -572,8 +1657,16 @@ pub mod audius_data {
             &ctx.accounts.signer_user_authority_delegate,
             &ctx.accounts.authority,
             &ctx.accounts.authority_delegation_status,
+            DELEGATE_PERMISSION_MANAGE_DELEGATES,
         )?;
 
+        emit!(UserAuthorityDelegateRemoved {
+            user: ctx.accounts.user.key(),
+            user_authority_delegate: ctx.accounts.current_user_authority_delegate.key(),
+            delegate_authority: ctx.accounts.current_user_authority_delegate.delegate_authority,
+            authority: Some(ctx.accounts.authority.key()),
+        });
+
         // Refer to context here - https://docs.solana.com/developing/programming-model/transactions#multiple-instructions-in-a-single-transaction
         let dummy_owner_field = Pubkey::from_str("11111111111111111111111111111111").unwrap();
         ctx.accounts.current_user_authority_delegate.delegate_authority = dummy_owner_field;
@@ -582,6 +1675,27 @@ pub mod audius_data {
             .user_storage_account = dummy_owner_field;
         Ok(())
     }
+
+    /// Close an expired user authority delegate, refunding its rent to the account that paid for it
+    /// Permissionless, so that expired delegates can be cleaned up by anyone
+    pub fn close_expired_user_authority_delegate(
+        ctx: Context<CloseExpiredUserAuthorityDelegate>,
+    ) -> Result<()> {
+        if !is_delegate_expired(&ctx.accounts.user_authority_delegate)? {
+            return Err(ErrorCode::DelegateNotExpired.into());
+        }
+
+        let user_authority_delegate = &ctx.accounts.user_authority_delegate;
+        emit!(UserAuthorityDelegateRemoved {
+            user: user_authority_delegate.user_storage_account,
+            user_authority_delegate: user_authority_delegate.key(),
+            delegate_authority: user_authority_delegate.delegate_authority,
+            authority: None,
+        });
+
+        // The delegate account is closed to the payer on exit
+        Ok(())
+    }
 }
 
 /// Instructions
@@ -599,11 +1713,14 @@ pub struct Initialize<'info> {
 /// `admin` account.
 /// `user` is a PDA derived from the Audius account and handle.
 /// `authority` is a signer key matching the admin value stored in AudiusAdmin root. Only the
-///  admin of this Audius root program may initialize users through this function
+///  admin of this Audius root program may initialize users through this function. When the admin
+///  authority is an AdminMultisig, `authority` is one of its signers and the multisig account and
+///  other signers follow the replica set content nodes in the remaining accounts
 /// `payer` is the account responsible for the lamports required to allocate this account.
 /// `system_program` is required for PDA derivation.
+/// The replica set content nodes are passed as remaining accounts.
 #[derive(Accounts)]
-#[instruction(base: Pubkey, eth_address: [u8;20], replica_set: [u16; 3], replica_set_bumps:[u8; 3], handle_seed: [u8;32])]
+#[instruction(base: Pubkey, eth_address: [u8;20], replica_set: Vec<u16>, handle_seed: [u8;32])]
 pub struct InitializeUser<'info> {
     pub admin: Account<'info, AudiusAdmin>,
     #[account(
@@ -611,15 +1728,9 @@ pub struct InitializeUser<'info> {
         payer = payer,
         seeds = [&base.to_bytes()[..32], handle_seed.as_ref()],
         bump,
-        space = USER_ACCOUNT_SIZE
+        space = user_account_size(replica_set.len())
     )]
     pub user: Account<'info, User>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[0].to_le_bytes()], bump = replica_set_bumps[0])]
-    pub cn1: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[1].to_le_bytes()], bump = replica_set_bumps[1])]
-    pub cn2: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[2].to_le_bytes()], bump = replica_set_bumps[2])]
-    pub cn3: Account<'info, ContentNode>,
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(mut)]
@@ -648,9 +1759,10 @@ pub struct CreateContentNode<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Instruction container for creating a content node with 3 proposers
+/// Instruction container for creating or updating a content node with content node proposers
+/// The proposers and their signing authorities are passed as remaining accounts.
 #[derive(Accounts)]
-#[instruction(base: Pubkey, p1: ProposerSeedBump, p2: ProposerSeedBump, p3: ProposerSeedBump, sp_id: u16)]
+#[instruction(base: Pubkey, proposer_sp_ids: Vec<u16>, sp_id: u16)]
 pub struct PublicCreateOrUpdateContentNode<'info> {
     pub admin: Account<'info, AudiusAdmin>,
     #[account(
@@ -661,77 +1773,92 @@ pub struct PublicCreateOrUpdateContentNode<'info> {
         space = CONTENT_NODE_ACCOUNT_SIZE
     )]
     pub content_node: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], p1.seed.as_ref()], bump = p1.bump)]
-    pub proposer1: Account<'info, ContentNode>,
-    pub proposer1_authority: Signer<'info>,
-    #[account(seeds = [&base.to_bytes()[..32], p2.seed.as_ref()], bump = p2.bump)]
-    pub proposer2: Account<'info, ContentNode>,
-    pub proposer2_authority: Signer<'info>,
-    #[account(seeds = [&base.to_bytes()[..32], p3.seed.as_ref()], bump = p3.bump)]
-    pub proposer3: Account<'info, ContentNode>,
-    pub proposer3_authority: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// Instruction container for deleteing a content node with 3 proposers
+/// Instruction container for deleteing a content node with content node proposers
+/// `refund_account` must be the authority of the content node, it receives the lamports of the closed account.
+/// The proposers and their signing authorities are passed as remaining accounts.
 #[derive(Accounts)]
-#[instruction(base: Pubkey, p_delete: ProposerSeedBump, p1: ProposerSeedBump, p2: ProposerSeedBump, p3: ProposerSeedBump)]
+#[instruction(base: Pubkey, p_delete: ProposerSeedBump)]
 pub struct PublicDeleteContentNode<'info> {
     pub admin: Account<'info, AudiusAdmin>,
-    /// CHECK: Delegate authority account, can be defaulted to SystemProgram for no-op
+    /// CHECK: Refund destination for the closed content node account, validated against its authority
     #[account(mut)]
-    pub admin_authority: AccountInfo<'info>,    
+    pub refund_account: AccountInfo<'info>,
     #[account(
         mut,
-        close = admin_authority,
+        close = refund_account,
+        constraint = content_node.authority == refund_account.key() @ ErrorCode::Unauthorized,
         seeds = [&base.to_bytes()[..32], p_delete.seed.as_ref()],
         bump = p_delete.bump
     )]
     pub content_node: Account<'info, ContentNode>,
-    // NOTE: Potentially use remain_accounts and then can set a variable number of proposers 
-    // https://book.anchor-lang.com/chapter_3/the_program_module.html#context
-    #[account(seeds = [&base.to_bytes()[..32], p1.seed.as_ref()], bump = p1.bump)]
-    pub proposer1: Account<'info, ContentNode>,
-    pub proposer1_authority: Signer<'info>,
-    #[account(seeds = [&base.to_bytes()[..32], p2.seed.as_ref()], bump = p2.bump)]
-    pub proposer2: Account<'info, ContentNode>,
-    pub proposer2_authority: Signer<'info>,
-    #[account(seeds = [&base.to_bytes()[..32], p3.seed.as_ref()], bump = p3.bump)]
-    pub proposer3: Account<'info, ContentNode>,
-    pub proposer3_authority: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Instruction container for a content node's authority to update its content node
+/// The content node account is allocated for the maximum endpoint and metadata lengths, so it is not resized.
+#[derive(Accounts)]
+#[instruction(base: Pubkey, sp_id: u16)]
+pub struct UpdateContentNode<'info> {
+    pub admin: Account<'info, AudiusAdmin>,
+    #[account(
+        mut,
+        seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, sp_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub content_node: Account<'info, ContentNode>,
+    pub authority: Signer<'info>,
+}
+
 /// Instruction container for updating a user's replica set signed by the user's authority or a content node
+/// The replica set content nodes are passed as remaining accounts.
+/// `payer` funds the rent when the user account grows.
 #[derive(Accounts)]
-#[instruction(base: Pubkey, user_handle: UserHandle, replica_set: [u16; 3], replica_set_bumps: [u8; 3])]
+#[instruction(base: Pubkey, user_handle: UserHandle)]
 pub struct UpdateUserReplicaSet<'info> {
     pub admin: Account<'info, AudiusAdmin>,
     #[account(mut, seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump=user_handle.bump)]
     pub user: Account<'info, User>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[0].to_le_bytes()], bump = replica_set_bumps[0])]
-    pub cn1: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[1].to_le_bytes()], bump = replica_set_bumps[1])]
-    pub cn2: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[2].to_le_bytes()], bump = replica_set_bumps[2])]
-    pub cn3: Account<'info, ContentNode>,
     pub cn_authority: Signer<'info>,    
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Instruction container to move a user to the PDA derived from a new handle.
+/// `user` is the current user PDA, rewritten as a UserRedirect to `new_user`.
+/// `authority` must match the `authority` field in the User account.
+#[derive(Accounts)]
+#[instruction(base: Pubkey, user_handle: UserHandle, new_handle_seed: [u8; 32])]
+pub struct ChangeUserHandle<'info> {
+    pub audius_admin: Account<'info, AudiusAdmin>,
+    /// CHECK: User PDA, deserialized in the instruction since it is rewritten as a UserRedirect
+    #[account(mut, seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump = user_handle.bump)]
+    pub user: AccountInfo<'info>,
+    /// CHECK: User PDA for the new handle, allocated in the instruction
+    #[account(mut, seeds = [&base.to_bytes()[..32], new_handle_seed.as_ref()], bump)]
+    pub new_user: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 /// Instruction container to allow a user to add their Solana public key as part of their identity.
 /// `user` is the target user PDA.
+/// `audius_admin` is the admin account the user PDA is derived from, as signed in the claim message.
 /// The global sys var program is required to enable instruction introspection.
 #[derive(Accounts)]
 pub struct InitializeUserSolIdentity<'info> {
     #[account(mut)]
     pub user: Account<'info, User>,
+    pub audius_admin: Account<'info, AudiusAdmin>,
     /// CHECK: This is required since we load an instruction at index - 1 to verify eth signature
     pub sysvar_program: AccountInfo<'info>,
 }
@@ -739,12 +1866,12 @@ pub struct InitializeUserSolIdentity<'info> {
 /// Instruction container to create a user account.
 /// `user` is the target user PDA.
 /// The global sys var program is required to enable instruction introspection.
+/// The replica set content nodes are passed as remaining accounts.
 #[derive(Accounts)]
 #[instruction(
     base: Pubkey,
     eth_address: [u8;20],
-    replica_set: [u16; 3],
-    replica_set_bumps:[u8; 3],
+    replica_set: Vec<u16>,
     handle_seed: [u8;32],
     _user_bump: u8,
     _metadata: String,
@@ -757,15 +1884,9 @@ pub struct CreateUser<'info> {
         payer = payer,
         seeds = [&base.to_bytes()[..32], handle_seed.as_ref()],
         bump,
-        space = USER_ACCOUNT_SIZE
+        space = user_account_size(replica_set.len())
     )]
     pub user: Account<'info, User>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[0].to_le_bytes()], bump = replica_set_bumps[0])]
-    pub cn1: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[1].to_le_bytes()], bump = replica_set_bumps[1])]
-    pub cn2: Account<'info, ContentNode>,
-    #[account(seeds = [&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &replica_set[2].to_le_bytes()], bump = replica_set_bumps[2])]
-    pub cn3: Account<'info, ContentNode>,
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(mut)]
@@ -792,6 +1913,32 @@ pub struct UpdateUser<'info> {
     pub authority_delegation_status: AccountInfo<'info>,
 }
 
+/// Instruction container to deactivate a user.
+/// `authority` must be the user's authority or the authority of `audius_admin`.
+#[derive(Accounts)]
+#[instruction(base: Pubkey, user_handle: UserHandle)]
+pub struct DeactivateUser<'info> {
+    pub audius_admin: Account<'info, AudiusAdmin>,
+    #[account(mut, seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump = user_handle.bump)]
+    pub user: Account<'info, User>,
+    pub authority: Signer<'info>,
+}
+
+/// Instruction container to close a deactivated user.
+/// `user` is rewritten as a UserTombstone.
+/// `authority` must be the user's authority or the authority of `audius_admin`, and receives the rent.
+#[derive(Accounts)]
+#[instruction(base: Pubkey, user_handle: UserHandle)]
+pub struct CloseUser<'info> {
+    pub audius_admin: Account<'info, AudiusAdmin>,
+    /// CHECK: User PDA, deserialized in the instruction since it is rewritten as a UserTombstone
+    #[account(mut, seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump = user_handle.bump)]
+    pub user: AccountInfo<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateAdmin<'info> {
     #[account(mut)]
@@ -800,13 +1947,53 @@ pub struct UpdateAdmin<'info> {
     pub admin_authority: Signer<'info>,
 }
 
+/// Instruction container to accept a proposed admin authority or verifier.
+/// `pending_signer` must match the pending key stored in the AudiusAdmin account.
+#[derive(Accounts)]
+pub struct AcceptAdminRotation<'info> {
+    #[account(mut)]
+    pub admin: Account<'info, AudiusAdmin>,
+    pub pending_signer: Signer<'info>,
+}
+
+/// Instruction container to initialize the admin multisig.
+/// `admin_multisig` is the PDA derived from the admin account, funded by `payer`.
+#[derive(Accounts)]
+pub struct InitAdminMultisig<'info> {
+    pub admin: Account<'info, AudiusAdmin>,
+    #[account(
+        init,
+        payer = payer,
+        space = ADMIN_MULTISIG_ACCOUNT_SIZE,
+        seeds = [ADMIN_MULTISIG_SEED, admin.key().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+    pub admin_authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction container to change the admin multisig signer set.
+/// `signer` must be one of the current multisig signers.
+#[derive(Accounts)]
+pub struct UpdateAdminMultisig<'info> {
+    #[account(mut, seeds = [ADMIN_MULTISIG_SEED, admin_multisig.admin.as_ref()], bump)]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+    pub signer: Signer<'info>,
+}
+
 /// Instruction container to initialize an AuthorityDelegationStatus.
 /// The authority initializes itself as a delegate.
+/// `admin` is the Audius admin account that may revoke or reinstate the delegation
 /// `delegate_authority` is the authority that will become a delegate
 /// `authority_delegation_status_pda` is the target PDA for the authority's delegation
 #[derive(Accounts)]
 #[instruction(authority_name: String)]
 pub struct InitAuthorityDelegationStatus<'info> {
+    #[account()]
+    pub admin: Account<'info, AudiusAdmin>,
     /// CHECK: Delegate authority account
     #[account()]
     pub delegate_authority: Signer<'info>,
@@ -823,24 +2010,28 @@ pub struct InitAuthorityDelegationStatus<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Instruction container to revoke an AuthorityDelegationStatus.
-/// The authority revokes itself as a delegate.
-/// `delegate_authority` is the authority that will become a delegate
-/// `authority_delegation_pda` is the target PDA for the authority's delegation
+/// Instruction container to revoke or reinstate an AuthorityDelegationStatus.
+/// `delegate_authority` is the authority whose delegation is updated
+/// `authority_delegation_status_pda` is the target PDA for the authority's delegation
+/// `admin` must be the admin account recorded in the delegation status
+/// `authority` must be the delegate authority itself or the admin authority
 #[derive(Accounts)]
 #[instruction(authority_delegation_status_bump: u8)]
-pub struct RevokeAuthorityDelegationStatus<'info> {
+pub struct UpdateAuthorityDelegationStatus<'info> {
     #[account()]
-    pub delegate_authority: Signer<'info>,
+    pub admin: Account<'info, AudiusAdmin>,
+    /// CHECK: Delegate authority account, used to derive the AuthorityDelegationStatus PDA
+    #[account()]
+    pub delegate_authority: AccountInfo<'info>,
     #[account(
-        mut, 
+        mut,
         seeds = [AUTHORITY_DELEGATION_STATUS_SEED, delegate_authority.key().as_ref()],
         bump = authority_delegation_status_bump,
+        has_one = admin,
     )]
     pub authority_delegation_status_pda: Account<'info, AuthorityDelegationStatus>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    #[account()]
+    pub authority: Signer<'info>,
 }
 
 /// Instruction container to allow user delegation
@@ -908,15 +2099,26 @@ pub struct RemoveUserAuthorityDelegate<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Instruction container to close an expired user authority delegate
+/// Returns funds to the payer of the delegate account
+#[derive(Accounts)]
+pub struct CloseExpiredUserAuthorityDelegate<'info> {
+    #[account(mut, close = payer, has_one = payer)]
+    pub user_authority_delegate: Account<'info, UserAuthorityDelegate>,
+    /// CHECK: Payer of the delegate account, validated against user_authority_delegate.payer
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+}
+
 /// Instruction container for entity management
 /// Confirms that user.authority matches signer authority field
 #[derive(Accounts)]
 #[instruction(
     base: Pubkey,
     user_handle: UserHandle,
-    _entity_type: EntityTypes,
+    entity_type: EntityTypes,
     _management_action:ManagementActions,
-    _id: u64,
+    id: u64,
     _metadata: String
 )]
 // Instruction base pda, handle
@@ -937,6 +2139,53 @@ pub struct ManageEntity<'info> {
     /// CHECK: When signer is a delegate, validate AuthorityDelegationStatus PDA  (default SystemProgram when signer is user)
     #[account()]
     pub authority_delegation_status: AccountInfo<'info>,
+    /// CHECK: Entity PDA, allocated on create and closed on delete
+    #[account(
+        mut,
+        seeds = [&base.to_bytes()[..32], ENTITY_SEED, &[entity_type.clone() as u8], &id.to_le_bytes()],
+        bump
+    )]
+    pub entity: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction container to append, remove or move the tracks of a playlist
+#[derive(Accounts)]
+#[instruction(base: Pubkey, user_handle: UserHandle, playlist_id: u64)]
+pub struct ManagePlaylistContents<'info> {
+    #[account()]
+    pub audius_admin: Account<'info, AudiusAdmin>,
+    #[account(
+        seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()],
+        bump = user_handle.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account()]
+    pub authority: Signer<'info>,
+    /// CHECK: When signer is a delegate, validate UserAuthorityDelegate PDA  (default SystemProgram when signer is user)
+    #[account()]
+    pub user_authority_delegate: AccountInfo<'info>,
+    /// CHECK: When signer is a delegate, validate AuthorityDelegationStatus PDA  (default SystemProgram when signer is user)
+    #[account()]
+    pub authority_delegation_status: AccountInfo<'info>,
+    /// CHECK: Playlist Entity PDA, validated as allocated and owned by the user
+    #[account(
+        seeds = [&base.to_bytes()[..32], ENTITY_SEED, &[EntityTypes::Playlist as u8], &playlist_id.to_le_bytes()],
+        bump
+    )]
+    pub playlist: AccountInfo<'info>,
+    /// CHECK: PlaylistContents PDA, allocated by the first append and resized as tracks are added
+    #[account(
+        mut,
+        seeds = [&base.to_bytes()[..32], PLAYLIST_CONTENTS_SEED, &playlist_id.to_le_bytes()],
+        bump
+    )]
+    pub playlist_contents: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Instruction container for track social action event
@@ -957,6 +2206,12 @@ pub struct WriteEntitySocialAction<'info> {
     /// CHECK: When signer is a delegate, validate AuthorityDelegationStatus PDA  (default SystemProgram when signer is user)
     #[account()]
     pub authority_delegation_status: AccountInfo<'info>,
+    /// CHECK: EntitySocialAction PDA, derived and validated in the instruction since the entity id seed is user provided
+    #[account(mut)]
+    pub social_action: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Instruction container for follow
@@ -980,6 +2235,38 @@ pub struct FollowUser<'info> {
     // User update authority field
     #[account(mut)]
     pub authority: Signer<'info>,
+    /// CHECK: Follow PDA derived from the follower and followee, allocated or closed depending on the user action
+    #[account(
+        mut,
+        seeds = [FOLLOW_SEED, follower_user_storage.key().as_ref(), followee_user_storage.key().as_ref()],
+        bump
+    )]
+    pub follow: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction container for a batch of social actions on behalf of one user
+/// The accounts of each action are passed as remaining accounts.
+#[derive(Accounts)]
+#[instruction(base: Pubkey, user_handle: UserHandle)]
+pub struct WriteSocialActions<'info> {
+    #[account()]
+    pub audius_admin: Account<'info, AudiusAdmin>,
+    #[account(seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump = user_handle.bump)]
+    pub user: Account<'info, User>,
+    #[account()]
+    pub authority: Signer<'info>,
+    /// CHECK: When signer is a delegate, validate UserAuthorityDelegate PDA  (default SystemProgram when signer is user)
+    #[account()]
+    pub user_authority_delegate: AccountInfo<'info>,
+    /// CHECK: When signer is a delegate, validate AuthorityDelegationStatus PDA  (default SystemProgram when signer is user)
+    #[account()]
+    pub authority_delegation_status: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Instruction container for verifying a user
@@ -987,51 +2274,194 @@ pub struct FollowUser<'info> {
 #[instruction(base: Pubkey, user_handle: UserHandle)]
 pub struct UpdateIsVerified<'info> {
     pub audius_admin: Account<'info, AudiusAdmin>,
-    // Confirm the follower PDA matches the expected value provided the target handle and base
-    #[account(seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump = user_handle.bump)]
+    // Confirm the user PDA matches the expected value provided the target handle and base
+    #[account(mut, seeds = [&base.to_bytes()[..32], user_handle.seed.as_ref()], bump = user_handle.bump)]
     pub user: Account<'info, User>,
     pub verifier: Signer<'info>,
 }
 
+/// Instruction container to migrate a program account to its current layout.
+/// `account` is taken as an AccountInfo since accounts in the layout before versioning can not be deserialized.
+/// `payer` funds the additional rent.
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Any account owned by this program, its type and layout are validated in the instruction
+    #[account(mut)]
+    pub account: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 // END Instructions
 
 /// Audius root account
 #[account]
 pub struct AudiusAdmin {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
     pub authority: Pubkey,
     pub verifier: Pubkey,
     pub is_write_enabled: bool,
+    // Proposed authority, default Pubkey when no rotation is pending
+    pub pending_authority: Pubkey,
+    // Proposed verifier, default Pubkey when no rotation is pending
+    pub pending_verifier: Pubkey,
+    // Maximum replica set length accepted for users
+    pub max_replica_set_size: u8,
+    // Number of content node proposers required to create, update or delete a content node
+    pub proposer_threshold: u8,
+}
+
+/// Admin multisig account, an M-of-N signer set that may hold the admin authority
+#[account]
+pub struct AdminMultisig {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // Admin account the multisig is derived from
+    pub admin: Pubkey,
+    // Unique signers, at most MAX_ADMIN_MULTISIG_SIGNERS
+    pub signers: Vec<Pubkey>,
+    // Number of distinct signers required, between 1 and the number of signers
+    pub threshold: u8,
 }
 
 /// User storage account
 #[account]
 pub struct User {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
     pub eth_address: [u8; 20],
     pub authority: Pubkey,
-    pub replica_set: [u16; 3]
+    // sp_ids of the user's content nodes, bounded by the admin max_replica_set_size
+    pub replica_set: Vec<u16>,
+    // Set and cleared by the admin verifier
+    pub is_verified: bool,
+    // Incremented by each recovery, delegates added in an earlier epoch can no longer act for the user
+    pub authority_epoch: u32,
+    // Unix timestamp of the last claim or recovery, 0 until the user is claimed
+    pub authority_updated_at: i64,
+    // Unix timestamp the user was deactivated at, 0 while the user is active
+    pub deactivated_at: i64,
+}
+
+/// User tombstone account, left at the PDA of a closed user so that its handle is never reused
+#[account]
+pub struct UserTombstone {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // Unix timestamp the user was closed at
+    pub closed_at: i64,
+}
+
+/// User redirect account, left at a user's previous handle PDA after a handle change
+#[account]
+pub struct UserRedirect {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // User storage account for the new handle
+    pub user: Pubkey,
 }
 
 /// Content Node storage account
 #[account]
 pub struct ContentNode {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
     pub owner_eth_address: [u8; 20],
     pub authority: Pubkey,
+    // http or https URL published by the authority, empty until published
+    pub endpoint: String,
+    // Metadata CID published by the authority, empty until published
+    pub metadata: String,
+}
+
+/// Follow relationship account
+#[account]
+pub struct Follow {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // User storage account that is following
+    pub follower: Pubkey,
+    // User storage account being followed
+    pub followee: Pubkey,
+    // Account that paid the rent of the follow, refunded when it is unfollowed
+    pub payer: Pubkey,
+}
+
+/// Entity account, records the owner of a track or playlist
+#[account]
+pub struct Entity {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // User storage account that owns the entity
+    pub owner: Pubkey,
+    // Account that paid the rent of the entity, refunded when it is deleted
+    pub payer: Pubkey,
+}
+
+/// Playlist contents account, the ordered track list of a playlist
+#[account]
+pub struct PlaylistContents {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // User storage account that owns the playlist
+    pub owner: Pubkey,
+    // Account that paid for the contents, refunded when the playlist is deleted
+    pub payer: Pubkey,
+    // Id of the playlist Entity
+    pub playlist_id: u64,
+    // Track ids in playlist order, the account is allocated for a multiple of PLAYLIST_CONTENTS_CAPACITY_INCREMENT
+    pub track_ids: Vec<u64>,
+}
+
+/// Entity social action account, present while a user's save or repost of an entity is active
+#[account]
+pub struct EntitySocialAction {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // User storage account that applied the social action
+    pub user: Pubkey,
+    pub entity_type: EntityTypes,
+    pub social_action_kind: SocialActionKinds,
+    // Account that paid the rent of the social action, refunded when it is deleted
+    pub payer: Pubkey,
 }
 
 /// User delegated authority account
 #[account]
 pub struct UserAuthorityDelegate {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
     // The account that is given permission to operate on this user's behalf
     pub delegate_authority: Pubkey,
     // PDA of user storage account enabling operations
     pub user_storage_account: Pubkey,
+    // Audius admin account the user PDA is derived from, which must match the delegation status admin
+    pub admin: Pubkey,
+    // Bitmask of DELEGATE_PERMISSION_* actions the delegate may perform
+    pub permissions: u16,
+    // Unix timestamp from which the delegate can no longer act for the user, None if it never expires
+    pub expires_at: Option<i64>,
+    // Account that paid for this delegate account, refunded when it is closed after expiry
+    pub payer: Pubkey,
+    // User authority epoch the delegate was added in
+    pub authority_epoch: u32,
 }
 
 /// Authority delegation status account
 #[account]
 pub struct AuthorityDelegationStatus {
+    // Layout version, ACCOUNT_VERSION for accounts allocated or migrated by this program
+    pub version: u8,
+    // Audius admin account that may revoke or reinstate the delegation
+    pub admin: Pubkey,
     // Revoke status for an authority's delegation eligibility
     pub is_revoked: bool,
+    // Signer of the most recent revocation, default Pubkey if never revoked
+    pub revoked_by: Pubkey,
+    // Unix timestamp of the most recent revocation, 0 if never revoked
+    pub revoked_at: i64,
 }
 
 // User actions enum, used to follow/unfollow based on function arguments
@@ -1050,6 +2480,51 @@ pub enum EntitySocialActionValues {
     DeleteRepost,
 }
 
+impl EntitySocialActionValues {
+    /// The kind of social action shared by the add and delete variants
+    pub fn kind(&self) -> SocialActionKinds {
+        match self {
+            EntitySocialActionValues::AddSave | EntitySocialActionValues::DeleteSave => {
+                SocialActionKinds::Save
+            }
+            EntitySocialActionValues::AddRepost | EntitySocialActionValues::DeleteRepost => {
+                SocialActionKinds::Repost
+            }
+        }
+    }
+
+    /// Whether the action removes an existing save or repost
+    pub fn is_delete(&self) -> bool {
+        matches!(
+            self,
+            EntitySocialActionValues::DeleteSave | EntitySocialActionValues::DeleteRepost
+        )
+    }
+}
+
+// A single action in a write_social_actions batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum SocialAction {
+    // Follow or unfollow the user with the followee handle
+    Follow {
+        user_action: UserAction,
+        followee_handle: UserHandle,
+    },
+    // Save or repost an entity, or remove an existing save or repost
+    EntitySocialAction {
+        entity_social_action: EntitySocialActionValues,
+        entity_type: EntityTypes,
+        id: String,
+    },
+}
+
+// Social action kinds, used as a seed for the EntitySocialAction PDA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum SocialActionKinds {
+    Save,
+    Repost,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum ManagementActions {
     Create,
@@ -1063,6 +2538,14 @@ pub enum EntityTypes {
     Playlist,
 }
 
+// Edit of a playlist's track list, removes and moves name the track expected at the index
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum PlaylistContentsAction {
+    Append { track_id: u64 },
+    Remove { index: u32, track_id: u64 },
+    Move { from: u32, to: u32, track_id: u64 },
+}
+
 // Seed & bump used to validate the user's handle with the account base
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub struct UserHandle {
@@ -0,0 +1,414 @@
+//! Account layouts deployed before versioning, and their upgrade to the current layouts
+//! Only the admin, user, content node, delegate and delegation status accounts predate versioning.
+//! Their unversioned layouts have a fixed length that no current account has, every other account
+//! must already be in the current layout.
+use crate::{
+    constants::*, error::ErrorCode, AdminMultisig, AudiusAdmin, AuthorityDelegationStatus,
+    ContentNode, Entity, EntitySocialAction, Follow, PlaylistContents, User, UserAuthorityDelegate,
+    UserRedirect, UserTombstone,
+};
+use anchor_lang::{prelude::*, Discriminator};
+
+/// Admin layout before versioning
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyAudiusAdmin {
+    pub authority: Pubkey,
+    pub verifier: Pubkey,
+    pub is_write_enabled: bool,
+}
+
+/// User layout before versioning, allocated with LEGACY_USER_ACCOUNT_SIZE
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUser {
+    pub eth_address: [u8; 20],
+    pub authority: Pubkey,
+    pub replica_set: [u16; 3],
+}
+
+/// Content node layout before versioning
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyContentNode {
+    pub owner_eth_address: [u8; 20],
+    pub authority: Pubkey,
+}
+
+/// Delegate layout before versioning
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserAuthorityDelegate {
+    pub delegate_authority: Pubkey,
+    pub user_storage_account: Pubkey,
+}
+
+/// Authority delegation status layout before versioning
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyAuthorityDelegationStatus {
+    pub is_revoked: bool,
+}
+
+/// Upgrade the data of a program account to the current layout of its account type
+/// Returns the serialized account in its current layout, or None if it is already current
+/// `payer` is recorded as the rent payer of migrated delegates, which did not track one
+/// `admin` is recorded as the admin of migrated delegation statuses, which are rejected without one
+pub fn migrate_account_data(
+    data: &[u8],
+    payer: &Pubkey,
+    admin: Option<&Pubkey>,
+) -> Result<Option<Vec<u8>>> {
+    if data.len() < 8 {
+        return Err(ErrorCode::UnknownAccountLayout.into());
+    }
+    let (discriminator, body) = data.split_at(8);
+
+    if discriminator == AudiusAdmin::discriminator() {
+        match legacy_body(body, ADMIN_ACCOUNT_SIZE, 65)? {
+            Some(body) => serialize(migrate_admin(body)?, ADMIN_ACCOUNT_SIZE),
+            None => Ok(None),
+        }
+    } else if discriminator == User::discriminator() {
+        // User accounts are sized by their replica set, and always longer than legacy accounts
+        if body.len() == LEGACY_USER_ACCOUNT_SIZE - 8 {
+            let user = migrate_user(body)?;
+            let space = user_account_size(user.replica_set.len());
+            return serialize(user, space);
+        }
+        current(body, None)
+    } else if discriminator == ContentNode::discriminator() {
+        match legacy_body(body, CONTENT_NODE_ACCOUNT_SIZE, 52)? {
+            Some(body) => serialize(migrate_content_node(body)?, CONTENT_NODE_ACCOUNT_SIZE),
+            None => Ok(None),
+        }
+    } else if discriminator == UserAuthorityDelegate::discriminator() {
+        match legacy_body(body, USER_AUTHORITY_DELEGATE_ACCOUNT_SIZE, 64)? {
+            Some(body) => serialize(
+                migrate_user_authority_delegate(body, payer, admin)?,
+                USER_AUTHORITY_DELEGATE_ACCOUNT_SIZE,
+            ),
+            None => Ok(None),
+        }
+    } else if discriminator == AuthorityDelegationStatus::discriminator() {
+        match legacy_body(body, AUTHORITY_DELEGATION_STATUS_ACCOUNT_SIZE, 1)? {
+            Some(body) => serialize(
+                migrate_authority_delegation_status(body, admin)?,
+                AUTHORITY_DELEGATION_STATUS_ACCOUNT_SIZE,
+            ),
+            None => Ok(None),
+        }
+    } else if discriminator == AdminMultisig::discriminator() {
+        current(body, Some(ADMIN_MULTISIG_ACCOUNT_SIZE))
+    } else if discriminator == UserRedirect::discriminator() {
+        current(body, Some(USER_REDIRECT_ACCOUNT_SIZE))
+    } else if discriminator == UserTombstone::discriminator() {
+        current(body, Some(USER_TOMBSTONE_ACCOUNT_SIZE))
+    } else if discriminator == Follow::discriminator() {
+        current(body, Some(FOLLOW_ACCOUNT_SIZE))
+    } else if discriminator == Entity::discriminator() {
+        current(body, Some(ENTITY_ACCOUNT_SIZE))
+    } else if discriminator == EntitySocialAction::discriminator() {
+        current(body, Some(SOCIAL_ACTION_ACCOUNT_SIZE))
+    } else if discriminator == PlaylistContents::discriminator() {
+        // Playlist contents are sized by their capacity
+        current(body, None)
+    } else {
+        Err(ErrorCode::UnknownAccountLayout.into())
+    }
+}
+
+/// Return `body` if it has the legacy length, None if it is in the current layout of `account_size`
+fn legacy_body(body: &[u8], account_size: usize, legacy_len: usize) -> Result<Option<&[u8]>> {
+    if body.len() == legacy_len {
+        return Ok(Some(body));
+    }
+    current(body, Some(account_size)).map(|_| None)
+}
+
+/// Accept an account body in the current layout, of `account_size` for fixed size account types
+fn current(body: &[u8], account_size: Option<usize>) -> Result<Option<Vec<u8>>> {
+    if let Some(account_size) = account_size {
+        if body.len() != account_size - 8 {
+            return Err(ErrorCode::UnknownAccountLayout.into());
+        }
+    }
+    match body.first() {
+        Some(&ACCOUNT_VERSION) => Ok(None),
+        _ => Err(ErrorCode::UnknownAccountLayout.into()),
+    }
+}
+
+/// Serialize a migrated account, zero padded to the account size since borsh encodes None in a single byte
+fn serialize<T: AccountSerialize>(account: T, space: usize) -> Result<Option<Vec<u8>>> {
+    let mut data = Vec::with_capacity(space);
+    account.try_serialize(&mut data)?;
+    data.resize(space, 0);
+    Ok(Some(data))
+}
+
+fn migrate_admin(body: &[u8]) -> Result<AudiusAdmin> {
+    let admin = LegacyAudiusAdmin::try_from_slice(body)?;
+    Ok(AudiusAdmin {
+        version: ACCOUNT_VERSION,
+        authority: admin.authority,
+        verifier: admin.verifier,
+        is_write_enabled: admin.is_write_enabled,
+        pending_authority: Pubkey::default(),
+        pending_verifier: Pubkey::default(),
+        max_replica_set_size: DEFAULT_MAX_REPLICA_SET_SIZE,
+        proposer_threshold: DEFAULT_PROPOSER_THRESHOLD,
+    })
+}
+
+fn migrate_user(body: &[u8]) -> Result<User> {
+    let user = LegacyUser::try_from_slice(body)?;
+    Ok(User {
+        version: ACCOUNT_VERSION,
+        eth_address: user.eth_address,
+        authority: user.authority,
+        replica_set: user.replica_set.to_vec(),
+        is_verified: false,
+        authority_epoch: 0,
+        authority_updated_at: 0,
+        deactivated_at: 0,
+    })
+}
+
+fn migrate_content_node(body: &[u8]) -> Result<ContentNode> {
+    let content_node = LegacyContentNode::try_from_slice(body)?;
+    Ok(ContentNode {
+        version: ACCOUNT_VERSION,
+        owner_eth_address: content_node.owner_eth_address,
+        authority: content_node.authority,
+        endpoint: String::new(),
+        metadata: String::new(),
+    })
+}
+
+/// Legacy delegates could perform every action and never expired, so they are granted all permissions
+/// Legacy delegates did not record the admin of their user, which must be provided
+fn migrate_user_authority_delegate(
+    body: &[u8],
+    payer: &Pubkey,
+    admin: Option<&Pubkey>,
+) -> Result<UserAuthorityDelegate> {
+    let delegate = LegacyUserAuthorityDelegate::try_from_slice(body)?;
+    Ok(UserAuthorityDelegate {
+        version: ACCOUNT_VERSION,
+        delegate_authority: delegate.delegate_authority,
+        user_storage_account: delegate.user_storage_account,
+        admin: *admin.ok_or(ErrorCode::Unauthorized)?,
+        permissions: DELEGATE_PERMISSION_ALL,
+        expires_at: None,
+        payer: *payer,
+        authority_epoch: 0,
+    })
+}
+
+/// Legacy statuses did not record an admin, and could be revoked by any admin
+fn migrate_authority_delegation_status(
+    body: &[u8],
+    admin: Option<&Pubkey>,
+) -> Result<AuthorityDelegationStatus> {
+    let status = LegacyAuthorityDelegationStatus::try_from_slice(body)?;
+    Ok(AuthorityDelegationStatus {
+        version: ACCOUNT_VERSION,
+        admin: *admin.ok_or(ErrorCode::Unauthorized)?,
+        is_revoked: status.is_revoked,
+        revoked_by: Pubkey::default(),
+        revoked_at: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serialize a legacy account body behind the discriminator of its account type
+    fn legacy_account<T: AnchorSerialize>(discriminator: [u8; 8], body: &T) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.extend(body.try_to_vec().unwrap());
+        data
+    }
+
+    /// Migrate `data`, checking the migrated account size and that migrating again is a no-op
+    fn migrate<T: AccountDeserialize>(data: &[u8], expected_size: usize) -> T {
+        let migrated = migrate_account_data(data, &Pubkey::default(), None)
+            .unwrap()
+            .expect("account should be migrated");
+        assert_eq!(migrated.len(), expected_size);
+        assert_eq!(migrated[8], ACCOUNT_VERSION);
+        assert!(migrate_account_data(&migrated, &Pubkey::default(), None)
+            .unwrap()
+            .is_none());
+        T::try_deserialize(&mut &migrated[..]).unwrap()
+    }
+
+    #[test]
+    fn migrates_legacy_admin() {
+        let (authority, verifier) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = legacy_account(
+            AudiusAdmin::discriminator(),
+            &LegacyAudiusAdmin {
+                authority,
+                verifier,
+                is_write_enabled: true,
+            },
+        );
+        let admin: AudiusAdmin = migrate(&data, ADMIN_ACCOUNT_SIZE);
+        assert_eq!(admin.authority, authority);
+        assert_eq!(admin.verifier, verifier);
+        assert!(admin.is_write_enabled);
+        assert_eq!(admin.pending_authority, Pubkey::default());
+        assert_eq!(admin.pending_verifier, Pubkey::default());
+        assert_eq!(admin.max_replica_set_size, DEFAULT_MAX_REPLICA_SET_SIZE);
+        assert_eq!(admin.proposer_threshold, DEFAULT_PROPOSER_THRESHOLD);
+    }
+
+    #[test]
+    fn migrates_legacy_user() {
+        let authority = Pubkey::new_unique();
+        let data = legacy_account(
+            User::discriminator(),
+            &LegacyUser {
+                eth_address: [7; 20],
+                authority,
+                replica_set: [1, 2, 3],
+            },
+        );
+        assert_eq!(data.len(), LEGACY_USER_ACCOUNT_SIZE);
+        let user: User = migrate(&data, user_account_size(3));
+        assert_eq!(user.eth_address, [7; 20]);
+        assert_eq!(user.authority, authority);
+        assert_eq!(user.replica_set, vec![1, 2, 3]);
+        assert!(!user.is_verified);
+        assert_eq!(user.authority_epoch, 0);
+        assert_eq!(user.authority_updated_at, 0);
+        assert_eq!(user.deactivated_at, 0);
+    }
+
+    #[test]
+    fn migrates_legacy_content_node() {
+        let authority = Pubkey::new_unique();
+        let data = legacy_account(
+            ContentNode::discriminator(),
+            &LegacyContentNode {
+                owner_eth_address: [3; 20],
+                authority,
+            },
+        );
+        let content_node: ContentNode = migrate(&data, CONTENT_NODE_ACCOUNT_SIZE);
+        assert_eq!(content_node.owner_eth_address, [3; 20]);
+        assert_eq!(content_node.authority, authority);
+        assert!(content_node.endpoint.is_empty());
+        assert!(content_node.metadata.is_empty());
+    }
+
+    #[test]
+    fn migrates_legacy_user_authority_delegate() {
+        let (delegate_authority, user_storage_account, payer, admin) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let data = legacy_account(
+            UserAuthorityDelegate::discriminator(),
+            &LegacyUserAuthorityDelegate {
+                delegate_authority,
+                user_storage_account,
+            },
+        );
+        assert!(migrate_account_data(&data, &payer, None).is_err());
+        let migrated = migrate_account_data(&data, &payer, Some(&admin))
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated.len(), USER_AUTHORITY_DELEGATE_ACCOUNT_SIZE);
+        let delegate = UserAuthorityDelegate::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(delegate.version, ACCOUNT_VERSION);
+        assert_eq!(delegate.delegate_authority, delegate_authority);
+        assert_eq!(delegate.user_storage_account, user_storage_account);
+        assert_eq!(delegate.admin, admin);
+        assert_eq!(delegate.permissions, DELEGATE_PERMISSION_ALL);
+        assert_eq!(delegate.expires_at, None);
+        assert_eq!(delegate.payer, payer);
+        assert_eq!(delegate.authority_epoch, 0);
+    }
+
+    #[test]
+    fn migrates_legacy_authority_delegation_status() {
+        let admin = Pubkey::new_unique();
+        let data = legacy_account(
+            AuthorityDelegationStatus::discriminator(),
+            &LegacyAuthorityDelegationStatus { is_revoked: true },
+        );
+        assert!(migrate_account_data(&data, &Pubkey::default(), None).is_err());
+
+        let migrated = migrate_account_data(&data, &Pubkey::default(), Some(&admin))
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated.len(), AUTHORITY_DELEGATION_STATUS_ACCOUNT_SIZE);
+        assert!(migrate_account_data(&migrated, &Pubkey::default(), None)
+            .unwrap()
+            .is_none());
+        let status = AuthorityDelegationStatus::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(status.version, ACCOUNT_VERSION);
+        assert_eq!(status.admin, admin);
+        assert!(status.is_revoked);
+        assert_eq!(status.revoked_by, Pubkey::default());
+        assert_eq!(status.revoked_at, 0);
+    }
+
+    #[test]
+    fn leaves_current_accounts() {
+        let mut data = Follow::discriminator().to_vec();
+        Follow {
+            version: ACCOUNT_VERSION,
+            follower: Pubkey::new_unique(),
+            followee: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+        }
+        .serialize(&mut data)
+        .unwrap();
+        assert!(migrate_account_data(&data, &Pubkey::default(), None)
+            .unwrap()
+            .is_none());
+
+        let mut data = PlaylistContents::discriminator().to_vec();
+        data.extend([ACCOUNT_VERSION; 100]);
+        assert!(migrate_account_data(&data, &Pubkey::default(), None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_layouts() {
+        let mut data = AudiusAdmin::discriminator().to_vec();
+        data.extend([0; 10]);
+        assert!(migrate_account_data(&data, &Pubkey::default(), None).is_err());
+
+        // Current layout with an unknown version
+        let mut data = ContentNode::discriminator().to_vec();
+        data.extend([ACCOUNT_VERSION + 1; CONTENT_NODE_ACCOUNT_SIZE - 8]);
+        assert!(migrate_account_data(&data, &Pubkey::default(), None).is_err());
+
+        // Versioned content node shorter than the current layout
+        let mut data = ContentNode::discriminator().to_vec();
+        data.extend([ACCOUNT_VERSION; 53]);
+        assert!(migrate_account_data(&data, &Pubkey::default(), None).is_err());
+
+        // User that is neither legacy nor versioned
+        let mut data = User::discriminator().to_vec();
+        data.extend([0; 79]);
+        assert!(migrate_account_data(&data, &Pubkey::default(), None).is_err());
+
+        // Account types introduced with versioning have no legacy layout
+        let data = legacy_account(
+            Follow::discriminator(),
+            &LegacyUserAuthorityDelegate {
+                delegate_authority: Pubkey::new_unique(),
+                user_storage_account: Pubkey::new_unique(),
+            },
+        );
+        assert!(migrate_account_data(&data, &Pubkey::default(), None).is_err());
+
+        assert!(migrate_account_data(&[1; 40], &Pubkey::default(), None).is_err());
+        assert!(migrate_account_data(&[], &Pubkey::default(), None).is_err());
+    }
+}
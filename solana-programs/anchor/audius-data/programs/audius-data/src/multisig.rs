@@ -0,0 +1,133 @@
+//! Admin multisig, an M-of-N signer set that may hold the admin authority
+//! Like SPL token multisig owners, the multisig account is passed in place of a signature and its signers
+//! sign the transaction as additional accounts, so privileged instructions keep a single named signer.
+use crate::{constants::MAX_ADMIN_MULTISIG_SIGNERS, error::ErrorCode, AdminMultisig, AudiusAdmin};
+use anchor_lang::prelude::*;
+use std::{collections::BTreeSet, iter};
+
+/// Validate that the admin authority signed the transaction
+/// `co_signers` are the remaining accounts holding any additional signers, see validate_authority_signers
+pub fn validate_admin_signers(
+    program_id: &Pubkey,
+    admin: &AudiusAdmin,
+    authority: &AccountInfo,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    validate_authority_signers(program_id, &admin.authority, authority, co_signers)
+}
+
+/// Validate that `expected_authority` signed the transaction, either as `authority` or among `co_signers`
+/// When `expected_authority` is an AdminMultisig account, the multisig account must be passed in `co_signers`
+/// and at least its threshold of distinct signers must sign, as `authority` or among `co_signers`
+pub fn validate_authority_signers(
+    program_id: &Pubkey,
+    expected_authority: &Pubkey,
+    authority: &AccountInfo,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    let signers = signer_keys(authority, co_signers);
+    if signers.contains(expected_authority) {
+        return Ok(());
+    }
+
+    let multisig_account = co_signers
+        .iter()
+        .find(|account| account.key == expected_authority)
+        .ok_or(ErrorCode::Unauthorized)?;
+    if multisig_account.owner != program_id {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    let multisig = AdminMultisig::try_deserialize(&mut &multisig_account.try_borrow_data()?[..])?;
+    validate_multisig_signers(&multisig, &signers)
+}
+
+/// Validate that at least the multisig threshold of distinct multisig signers are among `signers`
+pub fn validate_multisig_signers(
+    multisig: &AdminMultisig,
+    signers: &BTreeSet<Pubkey>,
+) -> Result<()> {
+    let signed = multisig
+        .signers
+        .iter()
+        .filter(|signer| signers.contains(signer))
+        .count();
+    if signed < multisig.threshold as usize {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    Ok(())
+}
+
+/// Keys of the accounts that signed the transaction among `authority` and `co_signers`
+pub fn signer_keys(authority: &AccountInfo, co_signers: &[AccountInfo]) -> BTreeSet<Pubkey> {
+    iter::once((authority.key, authority.is_signer))
+        .chain(
+            co_signers
+                .iter()
+                .map(|account| (account.key, account.is_signer)),
+        )
+        .filter(|(_, is_signer)| *is_signer)
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+/// Validate a multisig signer set: unique non-default signers, no more than MAX_ADMIN_MULTISIG_SIGNERS,
+/// and a threshold between 1 and the number of signers
+pub fn validate_multisig_config(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    let unique_signers: BTreeSet<&Pubkey> = signers.iter().collect();
+    if signers.len() > MAX_ADMIN_MULTISIG_SIGNERS
+        || unique_signers.len() != signers.len()
+        || unique_signers.contains(&Pubkey::default())
+        || threshold == 0
+        || threshold as usize > signers.len()
+    {
+        return Err(ErrorCode::InvalidMultisig.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multisig(signers: &[Pubkey], threshold: u8) -> AdminMultisig {
+        AdminMultisig {
+            version: crate::constants::ACCOUNT_VERSION,
+            admin: Pubkey::new_unique(),
+            signers: signers.to_vec(),
+            threshold,
+        }
+    }
+
+    #[test]
+    fn requires_threshold_of_distinct_signers() {
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let multisig = multisig(&signers, 2);
+        let signed = |keys: &[Pubkey]| keys.iter().copied().collect::<BTreeSet<Pubkey>>();
+
+        assert!(validate_multisig_signers(&multisig, &signed(&signers[..2])).is_ok());
+        assert!(validate_multisig_signers(&multisig, &signed(&signers)).is_ok());
+        assert!(validate_multisig_signers(&multisig, &signed(&signers[..1])).is_err());
+        // Signatures from outside the signer set do not count
+        assert!(
+            validate_multisig_signers(&multisig, &signed(&[signers[0], Pubkey::new_unique()]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn validates_multisig_config() {
+        let signers: Vec<Pubkey> = (0..MAX_ADMIN_MULTISIG_SIGNERS + 1)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert!(validate_multisig_config(&signers[..3], 1).is_ok());
+        assert!(validate_multisig_config(&signers[..3], 3).is_ok());
+        assert!(validate_multisig_config(&signers[..MAX_ADMIN_MULTISIG_SIGNERS], 5).is_ok());
+
+        assert!(validate_multisig_config(&signers[..3], 0).is_err());
+        assert!(validate_multisig_config(&signers[..3], 4).is_err());
+        assert!(validate_multisig_config(&[], 0).is_err());
+        assert!(validate_multisig_config(&signers, 5).is_err());
+        assert!(validate_multisig_config(&[signers[0], signers[0]], 1).is_err());
+        assert!(validate_multisig_config(&[signers[0], Pubkey::default()], 1).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Ordered track lists of playlists, stored in PlaylistContents accounts
+//! Removes and moves name the track expected at each index, so edits built against a stale copy of
+//! the list are rejected instead of changing the wrong entry.
+use crate::{
+    constants::{
+        MAX_PLAYLIST_TRACKS, PLAYLIST_CONTENTS_CAPACITY_INCREMENT, PLAYLIST_CONTENTS_SEED,
+    },
+    error::ErrorCode,
+    utils::{close_program_account, is_program_account_initialized, recorded_payer_account},
+    PlaylistContents, PlaylistContentsAction,
+};
+use anchor_lang::prelude::*;
+
+/// Apply `action` to the track ids of a playlist
+pub fn apply_playlist_contents_action(
+    track_ids: &mut Vec<u64>,
+    action: &PlaylistContentsAction,
+) -> Result<()> {
+    match *action {
+        PlaylistContentsAction::Append { track_id } => {
+            if track_ids.len() >= MAX_PLAYLIST_TRACKS {
+                return Err(ErrorCode::PlaylistFull.into());
+            }
+            track_ids.push(track_id);
+        }
+        PlaylistContentsAction::Remove { index, track_id } => {
+            validate_playlist_index(track_ids, index, track_id)?;
+            track_ids.remove(index as usize);
+        }
+        PlaylistContentsAction::Move { from, to, track_id } => {
+            validate_playlist_index(track_ids, from, track_id)?;
+            if to as usize >= track_ids.len() {
+                return Err(ErrorCode::InvalidPlaylistIndex.into());
+            }
+            let track_id = track_ids.remove(from as usize);
+            track_ids.insert(to as usize, track_id);
+        }
+    }
+    Ok(())
+}
+
+/// Validate that `index` is in range and holds `track_id`
+fn validate_playlist_index(track_ids: &[u64], index: u32, track_id: u64) -> Result<()> {
+    match track_ids.get(index as usize) {
+        Some(&id) if id == track_id => Ok(()),
+        _ => Err(ErrorCode::InvalidPlaylistIndex.into()),
+    }
+}
+
+/// Number of tracks a PlaylistContents account holding `len` tracks is allocated for,
+/// `len` rounded up to a whole number of PLAYLIST_CONTENTS_CAPACITY_INCREMENT
+pub fn playlist_contents_capacity(len: usize) -> usize {
+    let full_increments = len.saturating_sub(1) / PLAYLIST_CONTENTS_CAPACITY_INCREMENT;
+    ((full_increments + 1) * PLAYLIST_CONTENTS_CAPACITY_INCREMENT).min(MAX_PLAYLIST_TRACKS)
+}
+
+/// Close the PlaylistContents of a deleted playlist, refunding its rent to the payer that allocated it
+/// `accounts` are the PlaylistContents PDA of the playlist followed by its recorded payer, which may be
+/// omitted when the contents were never allocated
+pub fn close_playlist_contents<'info>(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    playlist_id: u64,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let playlist_contents = accounts
+        .first()
+        .ok_or(ErrorCode::ProgramDerivedAddressNotFound)?;
+    let (derived_playlist_contents, _) = Pubkey::find_program_address(
+        &[
+            &base.to_bytes()[..32],
+            PLAYLIST_CONTENTS_SEED,
+            &playlist_id.to_le_bytes(),
+        ],
+        program_id,
+    );
+    if derived_playlist_contents != playlist_contents.key() {
+        return Err(ErrorCode::ProgramDerivedAddressNotFound.into());
+    }
+    if !is_program_account_initialized(program_id, playlist_contents) {
+        return Ok(());
+    }
+
+    let contents =
+        PlaylistContents::try_deserialize(&mut &playlist_contents.try_borrow_data()?[..])?;
+    close_program_account(
+        playlist_contents,
+        recorded_payer_account(accounts.get(1), &contents.payer)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(track_ids: &mut Vec<u64>, action: PlaylistContentsAction) -> Result<()> {
+        apply_playlist_contents_action(track_ids, &action)
+    }
+
+    #[test]
+    fn appends_tracks_in_order() {
+        let mut track_ids = vec![];
+        for track_id in [3, 1, 3] {
+            apply(&mut track_ids, PlaylistContentsAction::Append { track_id }).unwrap();
+        }
+        assert_eq!(track_ids, vec![3, 1, 3]);
+    }
+
+    #[test]
+    fn rejects_append_to_full_playlist() {
+        let mut track_ids = vec![0; MAX_PLAYLIST_TRACKS];
+        let append = PlaylistContentsAction::Append { track_id: 1 };
+        assert!(apply(&mut track_ids, append).is_err());
+        assert_eq!(track_ids.len(), MAX_PLAYLIST_TRACKS);
+    }
+
+    #[test]
+    fn removes_track_at_index() {
+        let mut track_ids = vec![1, 2, 3];
+        let remove = PlaylistContentsAction::Remove {
+            index: 1,
+            track_id: 2,
+        };
+        apply(&mut track_ids, remove).unwrap();
+        assert_eq!(track_ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn moves_track_to_index() {
+        let mut track_ids = vec![1, 2, 3, 4];
+        let forward = PlaylistContentsAction::Move {
+            from: 0,
+            to: 2,
+            track_id: 1,
+        };
+        apply(&mut track_ids, forward).unwrap();
+        assert_eq!(track_ids, vec![2, 3, 1, 4]);
+        let back = PlaylistContentsAction::Move {
+            from: 3,
+            to: 0,
+            track_id: 4,
+        };
+        apply(&mut track_ids, back).unwrap();
+        assert_eq!(track_ids, vec![4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn rejects_stale_or_out_of_range_indexes() {
+        let mut track_ids = vec![1, 2, 3];
+        let actions = [
+            PlaylistContentsAction::Remove {
+                index: 3,
+                track_id: 3,
+            },
+            PlaylistContentsAction::Remove {
+                index: 0,
+                track_id: 2,
+            },
+            PlaylistContentsAction::Move {
+                from: 0,
+                to: 3,
+                track_id: 1,
+            },
+            PlaylistContentsAction::Move {
+                from: 1,
+                to: 0,
+                track_id: 3,
+            },
+        ];
+        for action in actions {
+            assert!(apply(&mut track_ids, action).is_err());
+        }
+        assert_eq!(track_ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn rounds_capacity_up_to_increments() {
+        let increment = PLAYLIST_CONTENTS_CAPACITY_INCREMENT;
+        assert_eq!(playlist_contents_capacity(0), increment);
+        assert_eq!(playlist_contents_capacity(1), increment);
+        assert_eq!(playlist_contents_capacity(increment), increment);
+        assert_eq!(playlist_contents_capacity(increment + 1), 2 * increment);
+        assert_eq!(
+            playlist_contents_capacity(MAX_PLAYLIST_TRACKS),
+            MAX_PLAYLIST_TRACKS
+        );
+    }
+}
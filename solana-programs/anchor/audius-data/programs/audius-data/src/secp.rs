@@ -0,0 +1,186 @@
+//! Introspection of the secp256k1 program instruction carrying a user's Ethereum signature
+//! The secp256k1 program only verifies the signature found at the offsets of its instruction, so the
+//! offsets are validated before the eth address and message are read from their fixed positions.
+use crate::{constants::*, error::ErrorCode};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{secp256k1_program, sysvar},
+};
+
+/// Secp256k1 signature offsets data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SecpSignatureOffsets {
+    /// Offset of 64+1 bytes
+    pub signature_offset: u16,
+    /// Index of signature instruction in buffer
+    pub signature_instruction_index: u8,
+    /// Offset to eth_address of 20 bytes
+    pub eth_address_offset: u16,
+    /// Index of eth address instruction in buffer
+    pub eth_address_instruction_index: u8,
+    /// Offset to start of message data
+    pub message_data_offset: u16,
+    /// Size of message data
+    pub message_data_size: u16,
+    /// Index on message instruction in buffer
+    pub message_instruction_index: u8,
+}
+
+/// Eth address and message verified by a secp256k1 program instruction
+#[derive(Debug, PartialEq)]
+pub struct SecpSignature {
+    pub eth_address: [u8; 20],
+    pub message: Vec<u8>,
+}
+
+/// Load the secp256k1 program instruction at `instruction_index` of the transaction
+/// and return the eth address and message of the single signature it verified
+pub fn verify_secp_instruction(
+    instructions_sysvar: &AccountInfo,
+    instruction_index: u8,
+) -> Result<SecpSignature> {
+    let instruction = sysvar::instructions::load_instruction_at_checked(
+        instruction_index as usize,
+        instructions_sysvar,
+    )?;
+    if instruction.program_id != secp256k1_program::id() {
+        return Err(ErrorCode::SignatureVerification.into());
+    }
+    parse_secp_instruction(&instruction.data, instruction_index)
+}
+
+/// Parse the data of the secp256k1 program instruction at `instruction_index`
+/// The instruction must hold a single signature whose offsets all point into the instruction itself,
+/// at the positions the eth address, signature and message are laid out by the clients,
+/// with the message running to the end of the instruction data.
+pub fn parse_secp_instruction(data: &[u8], instruction_index: u8) -> Result<SecpSignature> {
+    // Ensure there is just a single offsets struct included
+    if data.len() < MESSAGE_OFFSET || data[0] != 1 {
+        return Err(ErrorCode::SignatureVerification.into());
+    }
+    let offsets = SecpSignatureOffsets::try_from_slice(&data[1..ETH_ADDRESS_OFFSET])
+        .map_err(|_| ErrorCode::SignatureVerification)?;
+
+    // Ensure indices match the secp instruction, so the verified data is the data read below
+    let indices_match = offsets.signature_instruction_index == instruction_index
+        && offsets.eth_address_instruction_index == instruction_index
+        && offsets.message_instruction_index == instruction_index;
+    if !indices_match {
+        return Err(ErrorCode::SignatureVerification.into());
+    }
+
+    // Ensure offsets match the fixed layout
+    let offsets_match = offsets.eth_address_offset as usize == ETH_ADDRESS_OFFSET
+        && offsets.signature_offset as usize == SIGNATURE_OFFSET
+        && offsets.message_data_offset as usize == MESSAGE_OFFSET
+        && MESSAGE_OFFSET + offsets.message_data_size as usize == data.len();
+    if !offsets_match {
+        return Err(ErrorCode::SignatureVerification.into());
+    }
+
+    let mut eth_address = [0u8; 20];
+    eth_address.copy_from_slice(&data[ETH_ADDRESS_OFFSET..SIGNATURE_OFFSET]);
+    Ok(SecpSignature {
+        eth_address,
+        message: data[MESSAGE_OFFSET..].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: u8 = SECP_INSTRUCTION_INDEX;
+    const ETH_ADDRESS: [u8; 20] = [7; 20];
+    const MESSAGE: [u8; 32] = [9; 32];
+
+    fn offsets() -> SecpSignatureOffsets {
+        SecpSignatureOffsets {
+            signature_offset: SIGNATURE_OFFSET as u16,
+            signature_instruction_index: INDEX,
+            eth_address_offset: ETH_ADDRESS_OFFSET as u16,
+            eth_address_instruction_index: INDEX,
+            message_data_offset: MESSAGE_OFFSET as u16,
+            message_data_size: MESSAGE.len() as u16,
+            message_instruction_index: INDEX,
+        }
+    }
+
+    /// Secp instruction data laid out as Secp256k1Program.createInstructionWithEthAddress
+    fn instruction_data(signature_count: u8, offsets: SecpSignatureOffsets) -> Vec<u8> {
+        let mut data = vec![signature_count];
+        data.extend(offsets.try_to_vec().unwrap());
+        data.extend(ETH_ADDRESS);
+        data.extend([1; 65]);
+        data.extend(MESSAGE);
+        data
+    }
+
+    fn assert_rejected(data: &[u8]) {
+        assert!(parse_secp_instruction(data, INDEX).is_err());
+    }
+
+    #[test]
+    fn parses_single_signature() {
+        assert_eq!(
+            offsets().try_to_vec().unwrap().len(),
+            SIGNATURE_OFFSETS_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            parse_secp_instruction(&instruction_data(1, offsets()), INDEX).unwrap(),
+            SecpSignature {
+                eth_address: ETH_ADDRESS,
+                message: MESSAGE.to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_signature_counts_other_than_one() {
+        assert_rejected(&instruction_data(0, offsets()));
+        assert_rejected(&instruction_data(2, offsets()));
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        assert_rejected(&[]);
+        assert_rejected(&instruction_data(1, offsets())[..MESSAGE_OFFSET - 1]);
+        // Message shorter than its declared size
+        assert_rejected(&instruction_data(1, offsets())[..MESSAGE_OFFSET + 16]);
+    }
+
+    #[test]
+    fn rejects_offsets_into_other_instructions() {
+        let mut signature = offsets();
+        signature.signature_instruction_index = INDEX + 1;
+        let mut eth_address = offsets();
+        eth_address.eth_address_instruction_index = INDEX + 1;
+        let mut message = offsets();
+        message.message_instruction_index = INDEX + 1;
+        for offsets in [signature, eth_address, message] {
+            assert_rejected(&instruction_data(1, offsets));
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_offsets() {
+        let mut signature = offsets();
+        signature.signature_offset += 1;
+        let mut eth_address = offsets();
+        eth_address.eth_address_offset += 1;
+        let mut message = offsets();
+        message.message_data_offset += 1;
+        let mut message_size = offsets();
+        message_size.message_data_size -= 1;
+        for offsets in [signature, eth_address, message, message_size] {
+            assert_rejected(&instruction_data(1, offsets));
+        }
+    }
+
+    #[test]
+    fn rejects_trailing_data_after_message() {
+        let mut data = instruction_data(1, offsets());
+        data.push(0);
+        assert_rejected(&data);
+    }
+}
@@ -1,16 +1,30 @@
-use crate::{ErrorCode, User, UserAuthorityDelegate, AuthorityDelegationStatus, constants::AUTHORITY_DELEGATION_STATUS_SEED};
+use crate::{ErrorCode, User, UserAuthorityDelegate, AuthorityDelegationStatus, AudiusAdmin, ContentNode, Follow, EntitySocialAction, EntitySocialActionValues, EntityTypes, UserAction, constants::{ACCOUNT_VERSION, AUTHORITY_DELEGATION_STATUS_SEED, CONTENT_NODE_SEED_PREFIX, DELEGATE_PERMISSION_ALL, MAX_CONTENT_NODE_ENDPOINT_LENGTH, FOLLOW_ACCOUNT_SIZE, FOLLOW_SEED, SOCIAL_ACTION_ACCOUNT_SIZE, SOCIAL_ACTION_SEED}, multisig::validate_admin_signers};
 
-use anchor_lang::{prelude::*, solana_program::system_program};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::{invoke, invoke_signed},
+        pubkey::MAX_SEED_LEN,
+        system_instruction, system_program,
+    },
+};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Validate the authority account that signed the transaction
-/// is the user's authority or the user's delegate authority
+/// is the user's authority or an unexpired user delegate authority holding the required permission
+/// Returns the permissions and expiry held by the signer, all permissions and no expiry for the user's authority
 pub fn validate_user_authority<'info>(
     program_id: &Pubkey,
     user: &Account<'info, User>,
     user_authority_delegate: &AccountInfo<'info>,
     authority: &Signer,
     authority_delegation_status: &AccountInfo<'info>,
-) -> Result<()> {
+    required_permission: u16,
+) -> Result<(u16, Option<i64>)> {
+    // Deactivated users can not be changed by their authority or any delegate
+    if user.deactivated_at != 0 {
+        return Err(ErrorCode::UserDeactivated.into());
+    }
     if user.authority != authority.key() {
         // Authority must be a delegate
         // Reject if user_authority_delegate or authority_delegation_status is not provided
@@ -59,10 +73,428 @@ pub fn validate_user_authority<'info>(
             &mut &authority_delegation_status.try_borrow_data()?[..],
         )?;
         
+        // Reject if the delegation status is bound to another admin, which could not revoke it for this user
+        if authority_delegation_status_account.admin != user_authority_delegate_account.admin {
+            return Err(ErrorCode::InvalidUserAuthorityDelegation.into());
+        }
+
         // Reject if app delegate is revoked
         if authority_delegation_status_account.is_revoked {
             return Err(ErrorCode::RevokedAuthority.into());
         }
+
+        // Reject if the delegate was added before the user last recovered their account
+        if user_authority_delegate_account.authority_epoch != user.authority_epoch {
+            return Err(ErrorCode::DelegateInvalidated.into());
+        }
+
+        // Reject if the delegate has expired
+        if is_delegate_expired(&user_authority_delegate_account)? {
+            return Err(ErrorCode::DelegateExpired.into());
+        }
+
+        // Reject if the delegate was not granted the permission for this action
+        if user_authority_delegate_account.permissions & required_permission != required_permission {
+            return Err(ErrorCode::MissingPermission.into());
+        }
+        return Ok((
+            user_authority_delegate_account.permissions,
+            user_authority_delegate_account.expires_at,
+        ));
+    }
+    Ok((DELEGATE_PERMISSION_ALL, None))
+}
+
+/// Validate that the signer may revoke or reinstate an authority's delegation
+/// The admin authority may always do so, the delegated authority itself only when `delegate_may_sign`
+pub fn validate_delegation_status_authority(
+    program_id: &Pubkey,
+    admin: &AudiusAdmin,
+    delegate_authority: &AccountInfo,
+    authority: &AccountInfo,
+    co_signers: &[AccountInfo],
+    delegate_may_sign: bool,
+) -> Result<()> {
+    if delegate_may_sign && authority.key() == delegate_authority.key() {
+        return Ok(());
+    }
+    validate_admin_signers(program_id, admin, authority, co_signers)
+}
+
+/// Validate that the signer may deactivate or close a user
+/// Only the user's authority or the admin authority may do so
+pub fn validate_user_or_admin_authority(
+    program_id: &Pubkey,
+    admin: &AudiusAdmin,
+    user: &User,
+    authority: &AccountInfo,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    if authority.key() == user.authority {
+        return Ok(());
+    }
+    validate_admin_signers(program_id, admin, authority, co_signers)
+}
+
+/// Returns true if the delegate has an expiry that is not after the current Clock sysvar unix timestamp
+pub fn is_delegate_expired(user_authority_delegate: &UserAuthorityDelegate) -> Result<bool> {
+    match user_authority_delegate.expires_at {
+        Some(expires_at) => Ok(Clock::get()?.unix_timestamp >= expires_at),
+        None => Ok(false),
+    }
+}
+
+/// Validate a replica set against the admin maximum and the content node accounts provided for it
+/// `content_nodes` must hold the content node PDA for each sp_id in the replica set, in the same order
+pub fn validate_replica_set<'info>(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    admin: &AudiusAdmin,
+    replica_set: &[u16],
+    content_nodes: &[AccountInfo<'info>],
+) -> Result<Vec<Account<'info, ContentNode>>> {
+    if replica_set.is_empty()
+        || replica_set.len() > admin.max_replica_set_size as usize
+        || replica_set.len() != content_nodes.len()
+    {
+        return Err(ErrorCode::InvalidReplicaSet.into());
+    }
+
+    // Reject if any content node is repeated
+    let mut sp_ids = BTreeSet::new();
+    if !replica_set.iter().all(|sp_id| sp_ids.insert(sp_id)) {
+        return Err(ErrorCode::InvalidReplicaSet.into());
+    }
+
+    replica_set
+        .iter()
+        .zip(content_nodes)
+        .map(|(sp_id, content_node)| {
+            let (derived_content_node, _) = Pubkey::find_program_address(
+                &[&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &sp_id.to_le_bytes()],
+                program_id,
+            );
+            if derived_content_node != content_node.key() {
+                return Err(ErrorCode::ProgramDerivedAddressNotFound.into());
+            }
+            // Reject content nodes that were never created or have been deleted
+            if !is_program_account_initialized(program_id, content_node) {
+                return Err(ErrorCode::ContentNodeNotFound.into());
+            }
+            Account::try_from(content_node)
+        })
+        .collect()
+}
+
+/// Validate the content node proposers for a content node change against the admin proposer threshold
+/// `proposer_accounts` must hold the content node PDA followed by its signing authority for each sp_id
+/// in `proposer_sp_ids`, in the same order
+pub fn validate_proposers<'info>(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    admin: &AudiusAdmin,
+    proposer_sp_ids: &[u16],
+    proposer_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if proposer_sp_ids.len() < admin.proposer_threshold as usize
+        || proposer_accounts.len() != 2 * proposer_sp_ids.len()
+    {
+        return Err(ErrorCode::InsufficientProposers.into());
+    }
+
+    // Ensure that no proposer's owner eth address is repeated
+    let mut eth_addresses = BTreeMap::new();
+    for (sp_id, accounts) in proposer_sp_ids.iter().zip(proposer_accounts.chunks(2)) {
+        let (proposer, authority) = (&accounts[0], &accounts[1]);
+        let (derived_proposer, _) = Pubkey::find_program_address(
+            &[&base.to_bytes()[..32], CONTENT_NODE_SEED_PREFIX, &sp_id.to_le_bytes()],
+            program_id,
+        );
+        if derived_proposer != proposer.key() {
+            return Err(ErrorCode::ProgramDerivedAddressNotFound.into());
+        }
+        if !is_program_account_initialized(program_id, proposer) {
+            return Err(ErrorCode::ContentNodeNotFound.into());
+        }
+        let proposer: Account<ContentNode> = Account::try_from(proposer)?;
+
+        // Reject duplicate owner eth addresses and proposers not signed by their authority
+        if eth_addresses.insert(proposer.owner_eth_address, true).is_some()
+            || !authority.is_signer
+            || proposer.authority != authority.key()
+        {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+    }
+    Ok(())
+}
+
+/// Validate the endpoint published by a content node, an http or https URL with a host and no whitespace
+pub fn validate_content_node_endpoint(endpoint: &str) -> Result<()> {
+    let host = endpoint
+        .strip_prefix("https://")
+        .or_else(|| endpoint.strip_prefix("http://"));
+    match host {
+        Some(host)
+            if endpoint.len() <= MAX_CONTENT_NODE_ENDPOINT_LENGTH
+                && !host.is_empty()
+                && !host.starts_with('/')
+                && endpoint.bytes().all(|byte| byte.is_ascii_graphic()) =>
+        {
+            Ok(())
+        }
+        _ => Err(ErrorCode::InvalidEndpoint.into()),
+    }
+}
+
+/// Follow or unfollow a user by allocating or closing the Follow PDA derived from the follower and followee
+/// Rent is paid by the payer, which is recorded in the follow and must be passed as `recorded_payer` to unfollow
+#[allow(clippy::too_many_arguments)]
+pub fn apply_follow<'info>(
+    program_id: &Pubkey,
+    follower: &Pubkey,
+    followee: &Pubkey,
+    user_action: &UserAction,
+    follow: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    recorded_payer: Option<&AccountInfo<'info>>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (derived_follow, follow_bump) = Pubkey::find_program_address(
+        &[FOLLOW_SEED, follower.as_ref(), followee.as_ref()],
+        program_id,
+    );
+    if derived_follow != follow.key() {
+        return Err(ErrorCode::ProgramDerivedAddressNotFound.into());
+    }
+
+    let is_following = is_program_account_initialized(program_id, follow);
+    match user_action {
+        UserAction::FollowUser => {
+            if is_following {
+                return Err(ErrorCode::FollowAlreadyExists.into());
+            }
+            create_program_account(
+                program_id,
+                follow,
+                payer,
+                system_program,
+                &[FOLLOW_SEED, follower.as_ref(), followee.as_ref(), &[follow_bump]],
+                FOLLOW_ACCOUNT_SIZE,
+                &Follow {
+                    version: ACCOUNT_VERSION,
+                    follower: *follower,
+                    followee: *followee,
+                    payer: payer.key(),
+                },
+            )
+        }
+        UserAction::UnfollowUser => {
+            if !is_following {
+                return Err(ErrorCode::FollowNotFound.into());
+            }
+            let follow_account = Follow::try_deserialize(&mut &follow.try_borrow_data()?[..])?;
+            close_program_account(
+                follow,
+                recorded_payer_account(recorded_payer, &follow_account.payer)?,
+            )
+        }
+    }
+}
+
+/// Add or delete a save or repost by allocating or closing the EntitySocialAction PDA derived from the user,
+/// entity type, social action kind and entity id
+/// Rent is paid by the payer, which is recorded in the social action and must be passed as `recorded_payer` to
+/// delete it
+#[allow(clippy::too_many_arguments)]
+pub fn apply_entity_social_action<'info>(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    entity_social_action: &EntitySocialActionValues,
+    entity_type: &EntityTypes,
+    id: &str,
+    social_action: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    recorded_payer: Option<&AccountInfo<'info>>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // The entity id is used directly as a PDA seed
+    if id.len() > MAX_SEED_LEN {
+        return Err(ErrorCode::InvalidId.into());
+    }
+
+    let social_action_kind = entity_social_action.kind();
+    let entity_type_seed = [entity_type.clone() as u8];
+    let social_action_kind_seed = [social_action_kind.clone() as u8];
+    let (derived_social_action, social_action_bump) = Pubkey::find_program_address(
+        &[
+            SOCIAL_ACTION_SEED,
+            user.as_ref(),
+            &entity_type_seed,
+            &social_action_kind_seed,
+            id.as_bytes(),
+        ],
+        program_id,
+    );
+    if derived_social_action != social_action.key() {
+        return Err(ErrorCode::ProgramDerivedAddressNotFound.into());
+    }
+
+    let is_applied = is_program_account_initialized(program_id, social_action);
+    match entity_social_action {
+        EntitySocialActionValues::AddSave | EntitySocialActionValues::AddRepost => {
+            if is_applied {
+                return Err(ErrorCode::SocialActionAlreadyExists.into());
+            }
+            create_program_account(
+                program_id,
+                social_action,
+                payer,
+                system_program,
+                &[
+                    SOCIAL_ACTION_SEED,
+                    user.as_ref(),
+                    &entity_type_seed,
+                    &social_action_kind_seed,
+                    id.as_bytes(),
+                    &[social_action_bump],
+                ],
+                SOCIAL_ACTION_ACCOUNT_SIZE,
+                &EntitySocialAction {
+                    version: ACCOUNT_VERSION,
+                    user: *user,
+                    entity_type: entity_type.clone(),
+                    social_action_kind,
+                    payer: payer.key(),
+                },
+            )
+        }
+        EntitySocialActionValues::DeleteSave | EntitySocialActionValues::DeleteRepost => {
+            if !is_applied {
+                return Err(ErrorCode::SocialActionNotFound.into());
+            }
+            let social_action_account =
+                EntitySocialAction::try_deserialize(&mut &social_action.try_borrow_data()?[..])?;
+            close_program_account(
+                social_action,
+                recorded_payer_account(recorded_payer, &social_action_account.payer)?,
+            )
+        }
+    }
+}
+
+/// Returns the account of the payer recorded in a program account, which receives its rent when it is closed
+pub fn recorded_payer_account<'a, 'info>(
+    account: Option<&'a AccountInfo<'info>>,
+    payer: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    match account {
+        Some(account) if account.key() == *payer => Ok(account),
+        _ => Err(ErrorCode::Unauthorized.into()),
+    }
+}
+
+/// Returns true if the given account has already been allocated by this program and not closed
+pub fn is_program_account_initialized(program_id: &Pubkey, account: &AccountInfo) -> bool {
+    account.owner == program_id && account.lamports() > 0 && !account.data_is_empty()
+}
+
+/// Allocate a PDA owned by this program and write its initial state
+/// Performs the same steps as the anchor `init` constraint, allowing accounts to be created
+/// conditionally from within an instruction
+pub fn create_program_account<'info, T: AccountSerialize>(
+    program_id: &Pubkey,
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    space: usize,
+    state: &T,
+) -> Result<()> {
+    let required_lamports = Rent::get()?
+        .minimum_balance(space)
+        .max(1)
+        .saturating_sub(account.lamports());
+
+    if account.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                account.key,
+                required_lamports,
+                space as u64,
+                program_id,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+    } else {
+        // The target address was funded ahead of time, so allocate and assign it in place
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, account.key, required_lamports),
+                &[payer.clone(), account.clone(), system_program.clone()],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(account.key, space as u64),
+            &[account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(account.key, program_id),
+            &[account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    state.try_serialize(&mut &mut data[..])
+}
+
+/// Close an account owned by this program, refunding its lamports to the destination account
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let refund_lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(refund_lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    // Clear the discriminator so the account can not be deserialized for the rest of the transaction
+    account.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+/// Resize an account owned by this program
+/// The payer funds any additional rent and receives any excess lamports, new bytes are zero initialized
+pub fn resize_program_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let minimum_balance = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    if minimum_balance > current_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer.key,
+                account.key,
+                minimum_balance - current_lamports,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    } else if current_lamports > minimum_balance {
+        **payer.try_borrow_mut_lamports()? = payer
+            .lamports()
+            .checked_add(current_lamports - minimum_balance)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **account.try_borrow_mut_lamports()? = minimum_balance;
     }
+    account.realloc(space, true)?;
     Ok(())
 }
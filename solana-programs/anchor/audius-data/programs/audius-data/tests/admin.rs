@@ -0,0 +1,644 @@
+mod utils;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use audius_data::{
+    client,
+    constants::*,
+    error::ErrorCode,
+    migration::{LegacyAuthorityDelegationStatus, LegacyUser},
+    AdminMultisig, AudiusAdmin, AuthorityDelegationStatus, User,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use utils::*;
+
+#[tokio::test]
+/// init_admin writes the authority, verifier and default settings
+async fn success_init_admin() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        verifier,
+        ..
+    } = setup().await;
+
+    let account: AudiusAdmin = get_account(&mut context, &admin.pubkey()).await.unwrap();
+    assert_eq!(account.version, ACCOUNT_VERSION);
+    assert_eq!(account.authority, authority.pubkey());
+    assert_eq!(account.verifier, verifier.pubkey());
+    assert!(account.is_write_enabled);
+    assert_eq!(account.max_replica_set_size, DEFAULT_MAX_REPLICA_SET_SIZE);
+    assert_eq!(account.proposer_threshold, DEFAULT_PROPOSER_THRESHOLD);
+}
+
+#[tokio::test]
+/// The admin authority updates write access, the max replica set size and the proposer threshold
+async fn success_update_admin_settings() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+
+    process(
+        &mut context,
+        &[
+            client::update_admin(&program_id, &admin.pubkey(), &authority.pubkey(), false),
+            client::update_max_replica_set_size(
+                &program_id,
+                &admin.pubkey(),
+                &authority.pubkey(),
+                5,
+            ),
+            client::update_proposer_threshold(&program_id, &admin.pubkey(), &authority.pubkey(), 2),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    let account: AudiusAdmin = get_account(&mut context, &admin.pubkey()).await.unwrap();
+    assert!(!account.is_write_enabled);
+    assert_eq!(account.max_replica_set_size, 5);
+    assert_eq!(account.proposer_threshold, 2);
+}
+
+#[tokio::test]
+/// Admin settings can not be changed by another signer or set to zero
+async fn failure_update_admin_settings() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+    let impostor = Keypair::new();
+
+    let result = process(
+        &mut context,
+        &[client::update_admin(
+            &program_id,
+            &admin.pubkey(),
+            &impostor.pubkey(),
+            false,
+        )],
+        &[&impostor],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let result = process(
+        &mut context,
+        &[client::update_max_replica_set_size(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            0,
+        )],
+        &[&authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::InvalidReplicaSet);
+
+    let result = process(
+        &mut context,
+        &[client::update_proposer_threshold(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            0,
+        )],
+        &[&authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::InvalidProposerThreshold);
+}
+
+#[tokio::test]
+/// A proposed admin authority takes over once it accepts, and a cancelled proposal can not be accepted
+async fn success_rotate_admin_authority() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+    let cancelled_authority = Keypair::new();
+    let new_authority = Keypair::new();
+
+    process(
+        &mut context,
+        &[
+            client::propose_admin_authority(
+                &program_id,
+                &admin.pubkey(),
+                &authority.pubkey(),
+                cancelled_authority.pubkey(),
+            ),
+            client::cancel_admin_authority(&program_id, &admin.pubkey(), &authority.pubkey()),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+    let result = process(
+        &mut context,
+        &[client::accept_admin_authority(
+            &program_id,
+            &admin.pubkey(),
+            &cancelled_authority.pubkey(),
+        )],
+        &[&cancelled_authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    process(
+        &mut context,
+        &[client::propose_admin_authority(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            new_authority.pubkey(),
+        )],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+    let impostor = Keypair::new();
+    let result = process(
+        &mut context,
+        &[client::accept_admin_authority(
+            &program_id,
+            &admin.pubkey(),
+            &impostor.pubkey(),
+        )],
+        &[&impostor],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+    process(
+        &mut context,
+        &[client::accept_admin_authority(
+            &program_id,
+            &admin.pubkey(),
+            &new_authority.pubkey(),
+        )],
+        &[&new_authority],
+    )
+    .await
+    .unwrap();
+
+    let account: AudiusAdmin = get_account(&mut context, &admin.pubkey()).await.unwrap();
+    assert_eq!(account.authority, new_authority.pubkey());
+    assert_eq!(account.pending_authority, Default::default());
+
+    // The previous authority no longer controls the admin
+    let result = process(
+        &mut context,
+        &[client::update_admin(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            false,
+        )],
+        &[&authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+/// A proposed verifier takes over once it accepts, and a cancelled proposal can not be accepted
+async fn success_rotate_admin_verifier() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+    let cancelled_verifier = Keypair::new();
+    let new_verifier = Keypair::new();
+
+    process(
+        &mut context,
+        &[
+            client::propose_admin_verifier(
+                &program_id,
+                &admin.pubkey(),
+                &authority.pubkey(),
+                cancelled_verifier.pubkey(),
+            ),
+            client::cancel_admin_verifier(&program_id, &admin.pubkey(), &authority.pubkey()),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+    let result = process(
+        &mut context,
+        &[client::accept_admin_verifier(
+            &program_id,
+            &admin.pubkey(),
+            &cancelled_verifier.pubkey(),
+        )],
+        &[&cancelled_verifier],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    process(
+        &mut context,
+        &[client::propose_admin_verifier(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            new_verifier.pubkey(),
+        )],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+    let impostor = Keypair::new();
+    let result = process(
+        &mut context,
+        &[client::accept_admin_verifier(
+            &program_id,
+            &admin.pubkey(),
+            &impostor.pubkey(),
+        )],
+        &[&impostor],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+    process(
+        &mut context,
+        &[client::accept_admin_verifier(
+            &program_id,
+            &admin.pubkey(),
+            &new_verifier.pubkey(),
+        )],
+        &[&new_verifier],
+    )
+    .await
+    .unwrap();
+
+    let account: AudiusAdmin = get_account(&mut context, &admin.pubkey()).await.unwrap();
+    assert_eq!(account.verifier, new_verifier.pubkey());
+    assert_eq!(account.pending_verifier, Default::default());
+}
+
+#[tokio::test]
+/// An admin multisig becomes the admin authority and then acts with its threshold of signers
+async fn success_admin_multisig() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+    let signers: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    let signer_keys: Vec<_> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let admin_multisig = client::find_admin_multisig_address(&program_id, &admin.pubkey()).0;
+    let payer = context.payer.pubkey();
+
+    process(
+        &mut context,
+        &[
+            client::init_admin_multisig(
+                &program_id,
+                &admin.pubkey(),
+                &authority.pubkey(),
+                &payer,
+                signer_keys.clone(),
+                2,
+            ),
+            client::propose_admin_authority(
+                &program_id,
+                &admin.pubkey(),
+                &authority.pubkey(),
+                admin_multisig,
+            ),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    // A single signer is below the threshold
+    let mut accept = client::accept_admin_authority(&program_id, &admin.pubkey(), &signer_keys[0]);
+    accept
+        .accounts
+        .extend(client::admin_multisig_accounts(&admin_multisig, &[]));
+    let result = process(&mut context, &[accept.clone()], &[&signers[0]]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    accept.accounts.pop();
+    accept.accounts.extend(client::admin_multisig_accounts(
+        &admin_multisig,
+        &[signer_keys[1]],
+    ));
+    process(&mut context, &[accept], &[&signers[0], &signers[1]])
+        .await
+        .unwrap();
+    let account: AudiusAdmin = get_account(&mut context, &admin.pubkey()).await.unwrap();
+    assert_eq!(account.authority, admin_multisig);
+
+    let mut update_admin =
+        client::update_admin(&program_id, &admin.pubkey(), &signer_keys[1], false);
+    update_admin
+        .accounts
+        .extend(client::admin_multisig_accounts(
+            &admin_multisig,
+            &[signer_keys[2]],
+        ));
+    process(&mut context, &[update_admin], &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+    let account: AudiusAdmin = get_account(&mut context, &admin.pubkey()).await.unwrap();
+    assert!(!account.is_write_enabled);
+
+    // The former authority can no longer act for the admin
+    let result = process(
+        &mut context,
+        &[client::update_admin(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            true,
+        )],
+        &[&authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+/// The multisig signer set is replaced by its threshold of signers, and invalid configs are rejected
+async fn success_update_admin_multisig() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+    let signers: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    let signer_keys: Vec<_> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let new_signer = Keypair::new();
+    let admin_multisig = client::find_admin_multisig_address(&program_id, &admin.pubkey()).0;
+    let payer = context.payer.pubkey();
+
+    process(
+        &mut context,
+        &[client::init_admin_multisig(
+            &program_id,
+            &admin.pubkey(),
+            &authority.pubkey(),
+            &payer,
+            signer_keys.clone(),
+            2,
+        )],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    let new_signers = vec![signer_keys[0], new_signer.pubkey()];
+    let result = process(
+        &mut context,
+        &[client::update_admin_multisig(
+            &program_id,
+            &admin.pubkey(),
+            &signer_keys[0],
+            &[],
+            new_signers.clone(),
+            2,
+        )],
+        &[&signers[0]],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let result = process(
+        &mut context,
+        &[client::update_admin_multisig(
+            &program_id,
+            &admin.pubkey(),
+            &signer_keys[0],
+            &[signer_keys[1]],
+            new_signers.clone(),
+            3,
+        )],
+        &[&signers[0], &signers[1]],
+    )
+    .await;
+    assert_error(result, ErrorCode::InvalidMultisig);
+
+    process(
+        &mut context,
+        &[client::update_admin_multisig(
+            &program_id,
+            &admin.pubkey(),
+            &signer_keys[0],
+            &[signer_keys[1]],
+            new_signers.clone(),
+            2,
+        )],
+        &[&signers[0], &signers[1]],
+    )
+    .await
+    .unwrap();
+
+    let account: AdminMultisig = get_account(&mut context, &admin_multisig).await.unwrap();
+    assert_eq!(account.admin, admin.pubkey());
+    assert_eq!(account.signers, new_signers);
+    assert_eq!(account.threshold, 2);
+}
+
+#[tokio::test]
+/// Accounts already in the current layout are left untouched by migrate_account
+async fn success_migrate_current_account() {
+    let TestAdmin {
+        mut context, admin, ..
+    } = setup().await;
+    let payer = context.payer.pubkey();
+    let before = context
+        .banks_client
+        .get_account(admin.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    process(
+        &mut context,
+        &[client::migrate_account(
+            &audius_data::id(),
+            &admin.pubkey(),
+            &payer,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let after = context
+        .banks_client
+        .get_account(admin.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(before, after);
+}
+
+#[tokio::test]
+/// migrate_account rejects accounts that are not owned by the program
+async fn failure_migrate_foreign_account() {
+    let TestAdmin { mut context, .. } = setup().await;
+    let payer = context.payer.pubkey();
+
+    let result = process(
+        &mut context,
+        &[client::migrate_account(&audius_data::id(), &payer, &payer)],
+        &[],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+/// A user allocated before versioning is grown to the current layout, the payer funding the rent
+async fn success_migrate_legacy_user() {
+    let TestAdmin { mut context, .. } = setup().await;
+    let payer = context.payer.pubkey();
+    let address = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut data = User::discriminator().to_vec();
+    data.extend(
+        LegacyUser {
+            eth_address: [7; 20],
+            authority,
+            replica_set: [1, 2, 3],
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &address,
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(LEGACY_USER_ACCOUNT_SIZE),
+            data,
+            owner: audius_data::id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    process(
+        &mut context,
+        &[client::migrate_account(
+            &audius_data::id(),
+            &address,
+            &payer,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), user_account_size(3));
+    assert_eq!(account.lamports, rent.minimum_balance(user_account_size(3)));
+    let user: User = get_account(&mut context, &address).await.unwrap();
+    assert_eq!(user.version, ACCOUNT_VERSION);
+    assert_eq!(user.authority, authority);
+    assert_eq!(user.replica_set, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+/// A delegation status allocated before versioning is bound to the admin whose authority migrates it
+async fn success_migrate_legacy_authority_delegation_status() {
+    let TestAdmin {
+        mut context,
+        admin,
+        authority,
+        ..
+    } = setup().await;
+    let program_id = audius_data::id();
+    let payer = context.payer.pubkey();
+    let address = Pubkey::new_unique();
+    let mut data = AuthorityDelegationStatus::discriminator().to_vec();
+    data.extend(
+        LegacyAuthorityDelegationStatus { is_revoked: false }
+            .try_to_vec()
+            .unwrap(),
+    );
+    // Funded for the current layout, so that the migration needs no additional rent
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &address,
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(AUTHORITY_DELEGATION_STATUS_ACCOUNT_SIZE),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let result = process(
+        &mut context,
+        &[client::migrate_account(&program_id, &address, &payer)],
+        &[],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // The payer must be the authority of the admin the status is bound to
+    let result = process(
+        &mut context,
+        &[client::migrate_authority_delegation_status(
+            &program_id,
+            &address,
+            &admin.pubkey(),
+            &payer,
+        )],
+        &[],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    process(
+        &mut context,
+        &[client::migrate_authority_delegation_status(
+            &program_id,
+            &address,
+            &admin.pubkey(),
+            &authority.pubkey(),
+        )],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    let status: AuthorityDelegationStatus = get_account(&mut context, &address).await.unwrap();
+    assert_eq!(status.version, ACCOUNT_VERSION);
+    assert_eq!(status.admin, admin.pubkey());
+    assert!(!status.is_revoked);
+}
@@ -0,0 +1,463 @@
+mod utils;
+use audius_data::{client, constants::*, error::ErrorCode, ContentNode, User};
+use solana_program_test::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::SystemError,
+};
+use utils::*;
+
+/// Proposers for every content node created by setup
+fn proposers(test: &TestAdmin) -> Vec<(u16, Pubkey)> {
+    REPLICA_SET
+        .iter()
+        .zip(&test.content_node_authorities)
+        .map(|(sp_id, authority)| (*sp_id, authority.pubkey()))
+        .collect()
+}
+
+fn content_node_address(test: &TestAdmin, sp_id: u16) -> Pubkey {
+    let base = client::find_base_address(&audius_data::id(), &test.admin.pubkey()).0;
+    client::find_content_node_address(&audius_data::id(), &base, sp_id).0
+}
+
+#[tokio::test]
+/// The admin authority creates content nodes at the PDA of their sp_id
+async fn success_create_content_node() {
+    let mut test = setup().await;
+
+    for (sp_id, authority) in REPLICA_SET.iter().zip(&test.content_node_authorities) {
+        let address = content_node_address(&test, *sp_id);
+        let content_node: ContentNode = get_account(&mut test.context, &address).await.unwrap();
+        assert_eq!(content_node.version, ACCOUNT_VERSION);
+        assert_eq!(content_node.authority, authority.pubkey());
+        assert_eq!(content_node.owner_eth_address, [*sp_id as u8; 20]);
+    }
+}
+
+#[tokio::test]
+/// Only the admin authority may create content nodes, and only for an unused sp_id
+async fn failure_create_content_node() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let impostor = Keypair::new();
+
+    let create = client::create_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &impostor.pubkey(),
+        &payer,
+        4,
+        Keypair::new().pubkey(),
+        [4; 20],
+    );
+    let result = process(&mut test.context, &[create], &[&impostor]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // An sp_id that already has a content node
+    let create = client::create_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &test.authority.pubkey(),
+        &payer,
+        REPLICA_SET[0],
+        Keypair::new().pubkey(),
+        [4; 20],
+    );
+    let result = process(&mut test.context, &[create], &[&test.authority]).await;
+    assert_error(result, SystemError::AccountAlreadyInUse as u32);
+}
+
+#[tokio::test]
+/// The proposer threshold of content nodes creates and then updates a content node
+async fn success_public_create_or_update_content_node() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let proposers = proposers(&test);
+    let signers: Vec<&Keypair> = test.content_node_authorities.iter().collect();
+    let address = content_node_address(&test, 4);
+
+    let authority = Keypair::new();
+    let create = client::public_create_or_update_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &proposers,
+        4,
+        authority.pubkey(),
+        [4; 20],
+    );
+    process(&mut test.context, &[create], &signers)
+        .await
+        .unwrap();
+    let content_node: ContentNode = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(content_node.authority, authority.pubkey());
+    assert_eq!(content_node.owner_eth_address, [4; 20]);
+
+    let new_authority = Keypair::new();
+    let update = client::public_create_or_update_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &proposers,
+        4,
+        new_authority.pubkey(),
+        [5; 20],
+    );
+    process(&mut test.context, &[update], &signers)
+        .await
+        .unwrap();
+    let content_node: ContentNode = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(content_node.authority, new_authority.pubkey());
+    assert_eq!(content_node.owner_eth_address, [5; 20]);
+}
+
+#[tokio::test]
+/// Proposals need the threshold of existing content nodes, each signed by its own authority
+async fn failure_public_create_or_update_content_node() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let proposers = proposers(&test);
+    let admin = test.admin.pubkey();
+    let authorities = &test.content_node_authorities;
+    let create = |proposers: &[(u16, Pubkey)], eth_address: [u8; 20]| {
+        client::public_create_or_update_content_node(
+            &program_id,
+            &admin,
+            &payer,
+            proposers,
+            4,
+            Keypair::new().pubkey(),
+            eth_address,
+        )
+    };
+
+    // Below the proposer threshold
+    let ix = create(&proposers[..2], [4; 20]);
+    let result = process(
+        &mut test.context,
+        &[ix],
+        &[&authorities[0], &authorities[1]],
+    )
+    .await;
+    assert_error(result, ErrorCode::InsufficientProposers);
+
+    // A proposer signed by another content node's authority
+    let mut forged = proposers.clone();
+    forged[2].1 = authorities[1].pubkey();
+    let ix = create(&forged, [4; 20]);
+    let result = process(
+        &mut test.context,
+        &[ix],
+        &[&authorities[0], &authorities[1]],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // A proposer that does not exist
+    let mut missing = proposers.clone();
+    missing[2] = (9, authorities[2].pubkey());
+    let ix = create(&missing, [4; 20]);
+    let signers: Vec<&Keypair> = authorities.iter().collect();
+    let result = process(&mut test.context, &[ix], &signers).await;
+    assert_error(result, ErrorCode::ContentNodeNotFound);
+
+    // A proposer account that is not the PDA of its sp_id
+    let mut ix = create(&proposers, [5; 20]);
+    ix.accounts[4].pubkey = content_node_address(&test, 2);
+    let result = process(&mut test.context, &[ix], &signers).await;
+    assert_error(result, ErrorCode::ProgramDerivedAddressNotFound);
+}
+
+#[tokio::test]
+/// The proposer threshold of content nodes deletes a content node, refunding its rent to its authority
+async fn success_public_delete_content_node() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let proposers = proposers(&test);
+    let signers: Vec<&Keypair> = test.content_node_authorities.iter().collect();
+    let address = content_node_address(&test, 4);
+    let authority = Keypair::new().pubkey();
+
+    let create = client::public_create_or_update_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &proposers,
+        4,
+        authority,
+        [4; 20],
+    );
+    process(&mut test.context, &[create], &signers)
+        .await
+        .unwrap();
+    let rent = test
+        .context
+        .banks_client
+        .get_balance(address)
+        .await
+        .unwrap();
+
+    let delete = client::public_delete_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &authority,
+        &proposers[..2],
+        4,
+    );
+    let result = process(&mut test.context, &[delete], &signers[..2]).await;
+    assert_error(result, ErrorCode::InsufficientProposers);
+
+    // Refunded to an account other than the content node's authority
+    let delete = client::public_delete_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &payer,
+        &proposers,
+        4,
+    );
+    let result = process(&mut test.context, &[delete], &signers).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let delete = client::public_delete_content_node(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &authority,
+        &proposers,
+        4,
+    );
+    process(&mut test.context, &[delete], &signers)
+        .await
+        .unwrap();
+    assert!(get_account::<ContentNode>(&mut test.context, &address)
+        .await
+        .is_none());
+    assert_eq!(
+        test.context
+            .banks_client
+            .get_balance(authority)
+            .await
+            .unwrap(),
+        rent
+    );
+}
+
+#[tokio::test]
+/// Content node authorities publish endpoints, read with the replica set of a user
+async fn success_update_content_node_profile() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+
+    for (sp_id, authority) in REPLICA_SET.iter().zip(&test.content_node_authorities) {
+        let update = client::update_content_node_profile(
+            &program_id,
+            &admin,
+            &authority.pubkey(),
+            *sp_id,
+            format!("https://cn{}.audius.co", sp_id),
+            METADATA_CID.to_string(),
+        );
+        process(&mut test.context, &[update], &[authority])
+            .await
+            .unwrap();
+    }
+
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+    let user: User = get_account(&mut test.context, &address).await.unwrap();
+    let base = client::find_base_address(&program_id, &admin).0;
+    let mut endpoints = vec![];
+    for content_node in client::find_replica_set_addresses(&program_id, &base, &user.replica_set) {
+        let content_node: ContentNode =
+            get_account(&mut test.context, &content_node).await.unwrap();
+        assert_eq!(content_node.metadata, METADATA_CID);
+        endpoints.push(content_node.endpoint);
+    }
+    assert_eq!(
+        endpoints,
+        vec![
+            "https://cn1.audius.co",
+            "https://cn2.audius.co",
+            "https://cn3.audius.co"
+        ]
+    );
+
+    // Endpoints up to the maximum length fit the content node account
+    let endpoint = format!(
+        "http://{}",
+        "a".repeat(MAX_CONTENT_NODE_ENDPOINT_LENGTH - "http://".len())
+    );
+    let update = client::update_content_node_profile(
+        &program_id,
+        &admin,
+        &test.content_node_authorities[0].pubkey(),
+        1,
+        endpoint.clone(),
+        METADATA_CID.to_string(),
+    );
+    process(
+        &mut test.context,
+        &[update],
+        &[&test.content_node_authorities[0]],
+    )
+    .await
+    .unwrap();
+    let address = content_node_address(&test, 1);
+    let content_node: ContentNode = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(content_node.endpoint, endpoint);
+}
+
+#[tokio::test]
+/// Only a content node's own authority publishes its profile, with a valid endpoint and metadata
+async fn failure_update_content_node_profile() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+    let update = |authority: &Keypair, endpoint: &str, metadata: &str| {
+        client::update_content_node_profile(
+            &program_id,
+            &admin,
+            &authority.pubkey(),
+            1,
+            endpoint.to_string(),
+            metadata.to_string(),
+        )
+    };
+
+    // Signed by the authority of another content node
+    let ix = update(
+        &test.content_node_authorities[1],
+        "https://cn1.audius.co",
+        METADATA_CID,
+    );
+    let result = process(
+        &mut test.context,
+        &[ix],
+        &[&test.content_node_authorities[1]],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Signed by the admin authority
+    let ix = update(&test.authority, "https://cn1.audius.co", METADATA_CID);
+    let result = process(&mut test.context, &[ix], &[&test.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let authority = &test.content_node_authorities[0];
+    let too_long = format!(
+        "https://{}",
+        "a".repeat(MAX_CONTENT_NODE_ENDPOINT_LENGTH - "https://".len() + 1)
+    );
+    for endpoint in [
+        "",
+        "cn1.audius.co",
+        "ftp://cn1.audius.co",
+        "https://",
+        "https:///path",
+        "https://cn1 .audius.co",
+        &too_long,
+    ] {
+        let ix = update(authority, endpoint, METADATA_CID);
+        let result = process(&mut test.context, &[ix], &[authority]).await;
+        assert_error(result, ErrorCode::InvalidEndpoint);
+    }
+
+    let ix = update(authority, "https://cn1.audius.co", "not a cid");
+    let result = process(&mut test.context, &[ix], &[authority]).await;
+    assert_error(result, ErrorCode::InvalidMetadata);
+}
+
+#[tokio::test]
+/// A content node's authority rotates itself, and the previous authority can no longer update it
+async fn success_update_content_node_authority() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+    let address = content_node_address(&test, 1);
+    let new_authority = Keypair::new();
+
+    let impostor = Keypair::new();
+    let rotate = client::update_content_node_authority(
+        &program_id,
+        &admin,
+        &impostor.pubkey(),
+        1,
+        impostor.pubkey(),
+    );
+    let result = process(&mut test.context, &[rotate], &[&impostor]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let previous_authority = &test.content_node_authorities[0];
+    let rotate = client::update_content_node_authority(
+        &program_id,
+        &admin,
+        &previous_authority.pubkey(),
+        1,
+        new_authority.pubkey(),
+    );
+    process(&mut test.context, &[rotate], &[previous_authority])
+        .await
+        .unwrap();
+    let content_node: ContentNode = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(content_node.authority, new_authority.pubkey());
+    assert_eq!(content_node.owner_eth_address, [1; 20]);
+
+    let update = |authority: &Keypair| {
+        client::update_content_node_profile(
+            &program_id,
+            &admin,
+            &authority.pubkey(),
+            1,
+            "https://cn1.audius.co".to_string(),
+            METADATA_CID.to_string(),
+        )
+    };
+    let result = process(
+        &mut test.context,
+        &[update(previous_authority)],
+        &[previous_authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+    process(
+        &mut test.context,
+        &[update(&new_authority)],
+        &[&new_authority],
+    )
+    .await
+    .unwrap();
+
+    // Proposers replacing the authority keep the published profile
+    let proposers = proposers(&test);
+    let signers: Vec<&Keypair> = test.content_node_authorities.iter().collect();
+    let mut proposers_with_rotation = proposers.clone();
+    proposers_with_rotation[0].1 = new_authority.pubkey();
+    let mut signers_with_rotation = signers[1..].to_vec();
+    signers_with_rotation.push(&new_authority);
+    let replaced_authority = Keypair::new();
+    let replace = client::public_create_or_update_content_node(
+        &program_id,
+        &admin,
+        &test.payer(),
+        &proposers_with_rotation,
+        1,
+        replaced_authority.pubkey(),
+        [1; 20],
+    );
+    process(&mut test.context, &[replace], &signers_with_rotation)
+        .await
+        .unwrap();
+    let content_node: ContentNode = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(content_node.authority, replaced_authority.pubkey());
+    assert_eq!(content_node.endpoint, "https://cn1.audius.co");
+    assert_eq!(content_node.metadata, METADATA_CID);
+}
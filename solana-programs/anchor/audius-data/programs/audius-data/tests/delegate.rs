@@ -0,0 +1,489 @@
+mod utils;
+use anchor_lang::InstructionData;
+use audius_data::{
+    client, constants::*, error::ErrorCode, instruction, AuthorityDelegationStatus,
+    UserAuthorityDelegate,
+};
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+use utils::*;
+
+#[tokio::test]
+/// A delegate acts for the user until the user's authority removes it
+async fn success_add_and_remove_delegate() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_UPDATE_USER, None)
+        .await;
+    let delegate_address =
+        client::find_user_authority_delegate_address(&program_id, &address, &delegate.pubkey()).0;
+
+    let account: UserAuthorityDelegate = get_account(&mut test.context, &delegate_address)
+        .await
+        .unwrap();
+    assert_eq!(account.version, ACCOUNT_VERSION);
+    assert_eq!(account.delegate_authority, delegate.pubkey());
+    assert_eq!(account.user_storage_account, address);
+    assert_eq!(account.permissions, DELEGATE_PERMISSION_UPDATE_USER);
+    assert_eq!(account.expires_at, None);
+    assert_eq!(account.payer, test.payer());
+
+    let delegate_context = test.user_context(&user, &delegate, true);
+    let update_user = client::update_user(&program_id, &delegate_context, METADATA_CID.to_string());
+    process(
+        &mut test.context,
+        std::slice::from_ref(&update_user),
+        &[&delegate],
+    )
+    .await
+    .unwrap();
+
+    let context = test.user_context(&user, &user.authority, false);
+    let remove = client::remove_user_authority_delegate(&program_id, &context, delegate.pubkey());
+    process(&mut test.context, &[remove], &[&user.authority])
+        .await
+        .unwrap();
+    assert!(
+        get_account::<UserAuthorityDelegate>(&mut test.context, &delegate_address)
+            .await
+            .is_none()
+    );
+
+    refresh_blockhash(&mut test.context).await;
+    let result = process(&mut test.context, &[update_user], &[&delegate]).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound,
+    );
+}
+
+#[tokio::test]
+/// Delegates only perform the actions they were granted, and only grant permissions they hold
+async fn failure_delegate_missing_permission() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let delegate = test
+        .add_delegate(
+            &user,
+            DELEGATE_PERMISSION_FOLLOW | DELEGATE_PERMISSION_MANAGE_DELEGATES,
+            None,
+        )
+        .await;
+    let context = test.user_context(&user, &delegate, true);
+
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&delegate]).await;
+    assert_error(result, ErrorCode::MissingPermission);
+
+    let add = client::add_user_authority_delegate(
+        &program_id,
+        &context,
+        Keypair::new().pubkey(),
+        DELEGATE_PERMISSION_UPDATE_USER,
+        None,
+    );
+    let result = process(&mut test.context, &[add], &[&delegate]).await;
+    assert_error(result, ErrorCode::MissingPermission);
+
+    // A delegate with the permission may add delegates with a subset of its permissions
+    let add = client::add_user_authority_delegate(
+        &program_id,
+        &context,
+        Keypair::new().pubkey(),
+        DELEGATE_PERMISSION_FOLLOW,
+        None,
+    );
+    process(&mut test.context, &[add], &[&delegate])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+/// A revoked authority can not act as a delegate for any user until it is reinstated
+async fn success_revoke_and_reinstate_delegation() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+    let user = test.claimed_user("alice", 1).await;
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_UPDATE_USER, None)
+        .await;
+    let status_address =
+        client::find_authority_delegation_status_address(&program_id, &delegate.pubkey()).0;
+    let context = test.user_context(&user, &delegate, true);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert_eq!(status.admin, admin);
+
+    let impostor = Keypair::new();
+    let revoke = client::revoke_authority_delegation(
+        &program_id,
+        &admin,
+        &delegate.pubkey(),
+        &impostor.pubkey(),
+    );
+    let result = process(&mut test.context, &[revoke], &[&impostor]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let revoke = client::revoke_authority_delegation(
+        &program_id,
+        &admin,
+        &delegate.pubkey(),
+        &test.authority.pubkey(),
+    );
+    process(&mut test.context, &[revoke], &[&test.authority])
+        .await
+        .unwrap();
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert!(status.is_revoked);
+    assert_eq!(status.revoked_by, test.authority.pubkey());
+
+    let result = process(
+        &mut test.context,
+        std::slice::from_ref(&update_user),
+        &[&delegate],
+    )
+    .await;
+    assert_error(result, ErrorCode::RevokedAuthority);
+
+    // Revoking again itself does not replace the admin's revocation
+    let self_revoke = client::revoke_authority_delegation(
+        &program_id,
+        &admin,
+        &delegate.pubkey(),
+        &delegate.pubkey(),
+    );
+    process(
+        &mut test.context,
+        std::slice::from_ref(&self_revoke),
+        &[&delegate],
+    )
+    .await
+    .unwrap();
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert_eq!(status.revoked_by, test.authority.pubkey());
+
+    // Only the admin may reinstate a delegation it revoked
+    let self_reinstate = client::reinstate_authority_delegation(
+        &program_id,
+        &admin,
+        &delegate.pubkey(),
+        &delegate.pubkey(),
+    );
+    let result = process(
+        &mut test.context,
+        std::slice::from_ref(&self_reinstate),
+        &[&delegate],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let reinstate = client::reinstate_authority_delegation(
+        &program_id,
+        &admin,
+        &delegate.pubkey(),
+        &test.authority.pubkey(),
+    );
+    process(&mut test.context, &[reinstate], &[&test.authority])
+        .await
+        .unwrap();
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert!(!status.is_revoked);
+
+    refresh_blockhash(&mut test.context).await;
+    process(
+        &mut test.context,
+        std::slice::from_ref(&update_user),
+        &[&delegate],
+    )
+    .await
+    .unwrap();
+
+    // A delegation revoked by the delegate itself may be reinstated by it
+    process(&mut test.context, &[self_revoke], &[&delegate])
+        .await
+        .unwrap();
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert!(status.is_revoked);
+    assert_eq!(status.revoked_by, delegate.pubkey());
+
+    process(&mut test.context, &[self_reinstate], &[&delegate])
+        .await
+        .unwrap();
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert!(!status.is_revoked);
+}
+
+#[tokio::test]
+/// A delegation status can only be updated through the admin it was initialized with
+async fn failure_foreign_admin_delegation_status() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_UPDATE_USER, None)
+        .await;
+
+    let (foreign_admin, foreign_authority) = (Keypair::new(), Keypair::new());
+    let payer = test.payer();
+    process(
+        &mut test.context,
+        &[client::init_admin(
+            &program_id,
+            &foreign_admin.pubkey(),
+            &payer,
+            foreign_authority.pubkey(),
+            foreign_authority.pubkey(),
+        )],
+        &[&foreign_admin],
+    )
+    .await
+    .unwrap();
+
+    let revoke = client::revoke_authority_delegation(
+        &program_id,
+        &foreign_admin.pubkey(),
+        &delegate.pubkey(),
+        &foreign_authority.pubkey(),
+    );
+    let result = process(&mut test.context, &[revoke], &[&foreign_authority]).await;
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+
+    let revoke = client::revoke_authority_delegation(
+        &program_id,
+        &test.admin.pubkey(),
+        &delegate.pubkey(),
+        &test.authority.pubkey(),
+    );
+    process(&mut test.context, &[revoke], &[&test.authority])
+        .await
+        .unwrap();
+
+    let reinstate = client::reinstate_authority_delegation(
+        &program_id,
+        &foreign_admin.pubkey(),
+        &delegate.pubkey(),
+        &foreign_authority.pubkey(),
+    );
+    let result = process(&mut test.context, &[reinstate], &[&foreign_authority]).await;
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+
+    let status_address =
+        client::find_authority_delegation_status_address(&program_id, &delegate.pubkey()).0;
+    let status: AuthorityDelegationStatus = get_account(&mut test.context, &status_address)
+        .await
+        .unwrap();
+    assert!(status.is_revoked);
+    assert_eq!(status.revoked_by, test.authority.pubkey());
+}
+
+#[tokio::test]
+/// A delegate whose delegation status is bound to another admin cannot act for the user
+async fn failure_delegate_bound_to_self_made_admin() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let (delegate, fake_admin) = (Keypair::new(), Keypair::new());
+    let payer = test.payer();
+
+    // The delegate initializes its delegation status under an admin it controls
+    process(
+        &mut test.context,
+        &[
+            client::init_admin(
+                &program_id,
+                &fake_admin.pubkey(),
+                &payer,
+                delegate.pubkey(),
+                delegate.pubkey(),
+            ),
+            client::init_authority_delegation_status(
+                &program_id,
+                &fake_admin.pubkey(),
+                &delegate.pubkey(),
+                &payer,
+                "delegate".to_string(),
+            ),
+        ],
+        &[&fake_admin, &delegate],
+    )
+    .await
+    .unwrap();
+
+    // The delegate account cannot be recorded under the fake admin either
+    let context = test.user_context(&user, &user.authority, false);
+    let mut add = client::add_user_authority_delegate(
+        &program_id,
+        &context,
+        delegate.pubkey(),
+        DELEGATE_PERMISSION_UPDATE_USER,
+        None,
+    );
+    add.accounts[0].pubkey = fake_admin.pubkey();
+    let result = process(&mut test.context, &[add], &[&user.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let add = client::add_user_authority_delegate(
+        &program_id,
+        &context,
+        delegate.pubkey(),
+        DELEGATE_PERMISSION_UPDATE_USER,
+        None,
+    );
+    process(&mut test.context, &[add], &[&user.authority])
+        .await
+        .unwrap();
+
+    // The real admin cannot revoke the status, so the delegate may not act at all
+    let revoke = client::revoke_authority_delegation(
+        &program_id,
+        &test.admin.pubkey(),
+        &delegate.pubkey(),
+        &test.authority.pubkey(),
+    );
+    let result = process(&mut test.context, &[revoke], &[&test.authority]).await;
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+
+    let context = test.user_context(&user, &delegate, true);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&delegate]).await;
+    assert_error(result, ErrorCode::InvalidUserAuthorityDelegation);
+}
+
+#[tokio::test]
+/// Delegate and delegation status accounts must be the PDAs of the signer and user
+async fn failure_delegate_pda_mismatch() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let alice = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let alice_delegate = test
+        .add_delegate(&alice, DELEGATE_PERMISSION_ALL, None)
+        .await;
+    let bob_delegate = test.add_delegate(&bob, DELEGATE_PERMISSION_ALL, None).await;
+    let bob_address = test.user_address(&bob.handle_seed);
+
+    // Bob's delegate acting for Alice with its delegate account for Bob
+    let context = test.user_context(&alice, &bob_delegate, true);
+    let mut update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    update_user.accounts[2].pubkey = client::find_user_authority_delegate_address(
+        &program_id,
+        &bob_address,
+        &bob_delegate.pubkey(),
+    )
+    .0;
+    let result = process(&mut test.context, &[update_user], &[&bob_delegate]).await;
+    assert_error(result, ErrorCode::ProgramDerivedAddressNotFound);
+
+    // Alice's delegate with the delegation status of another authority
+    let context = test.user_context(&alice, &alice_delegate, true);
+    let mut update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    update_user.accounts[3].pubkey =
+        client::find_authority_delegation_status_address(&program_id, &bob_delegate.pubkey()).0;
+    let result = process(&mut test.context, &[update_user], &[&alice_delegate]).await;
+    assert_error(result, ErrorCode::ProgramDerivedAddressNotFound);
+
+    // Alice's delegate without its delegate accounts
+    let context = test.user_context(&alice, &alice_delegate, false);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&alice_delegate]).await;
+    assert_error(result, ErrorCode::MissingDelegateAccount);
+
+    // A user PDA that does not match the handle bump
+    let context = test.user_context(&alice, &alice.authority, false);
+    let (_, handle) = context.user(&program_id);
+    let new_delegate = Keypair::new().pubkey();
+    let mut add = client::add_user_authority_delegate(
+        &program_id,
+        &context,
+        new_delegate,
+        DELEGATE_PERMISSION_ALL,
+        None,
+    );
+    add.data = instruction::AddUserAuthorityDelegate {
+        base: context.base(&program_id),
+        _handle_seed: handle.seed,
+        _user_bump: handle.bump.wrapping_sub(1),
+        user_authority_delegate: new_delegate,
+        permissions: DELEGATE_PERMISSION_ALL,
+        expires_at: None,
+    }
+    .data();
+    let result = process(&mut test.context, &[add], &[&alice.authority]).await;
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+/// Expired delegates can no longer act for the user, and are then closed by anyone
+async fn success_close_expired_delegate() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+    let now = unix_timestamp(&mut test.context).await;
+
+    let context = test.user_context(&user, &user.authority, false);
+    let add = client::add_user_authority_delegate(
+        &program_id,
+        &context,
+        Keypair::new().pubkey(),
+        DELEGATE_PERMISSION_ALL,
+        Some(now),
+    );
+    let result = process(&mut test.context, &[add], &[&user.authority]).await;
+    assert_error(result, ErrorCode::InvalidDelegateExpiry);
+
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_ALL, Some(now + 100))
+        .await;
+    let delegate_address =
+        client::find_user_authority_delegate_address(&program_id, &address, &delegate.pubkey()).0;
+    let close =
+        client::close_expired_user_authority_delegate(&program_id, &delegate_address, &payer);
+    let result = process(&mut test.context, std::slice::from_ref(&close), &[]).await;
+    assert_error(result, ErrorCode::DelegateNotExpired);
+
+    set_unix_timestamp(&mut test.context, now + 100).await;
+    let delegate_context = test.user_context(&user, &delegate, true);
+    let update_user = client::update_user(&program_id, &delegate_context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&delegate]).await;
+    assert_error(result, ErrorCode::DelegateExpired);
+
+    let rent = test
+        .context
+        .banks_client
+        .get_balance(delegate_address)
+        .await
+        .unwrap();
+    let balance = test.context.banks_client.get_balance(payer).await.unwrap();
+    refresh_blockhash(&mut test.context).await;
+    process(&mut test.context, &[close], &[]).await.unwrap();
+    assert!(
+        get_account::<UserAuthorityDelegate>(&mut test.context, &delegate_address)
+            .await
+            .is_none()
+    );
+    let fee = 5000;
+    assert_eq!(
+        test.context.banks_client.get_balance(payer).await.unwrap(),
+        balance + rent - fee
+    );
+}
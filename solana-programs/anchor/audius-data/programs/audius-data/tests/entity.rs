@@ -0,0 +1,807 @@
+mod utils;
+use audius_data::{
+    client::{self, UserContext},
+    constants::*,
+    error::ErrorCode,
+    Entity, EntitySocialAction, EntitySocialActionValues, EntityTypes, Follow, ManagementActions,
+    PlaylistContents, PlaylistContentsAction, SocialAction, SocialActionKinds, UserAction,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+/// Compute units an instruction may consume without requesting a larger compute budget
+const INSTRUCTION_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+fn entity_address(context: &UserContext, entity_type: &EntityTypes, id: u64) -> Pubkey {
+    let program_id = audius_data::id();
+    client::find_entity_address(&program_id, &context.base(&program_id), entity_type, id).0
+}
+
+fn playlist_contents_address(context: &UserContext, playlist_id: u64) -> Pubkey {
+    let program_id = audius_data::id();
+    client::find_playlist_contents_address(&program_id, &context.base(&program_id), playlist_id).0
+}
+
+fn social_action_address(
+    context: &UserContext,
+    entity_type: &EntityTypes,
+    kind: &SocialActionKinds,
+    id: &str,
+) -> Pubkey {
+    let program_id = audius_data::id();
+    let user = context.user(&program_id).0;
+    client::find_social_action_address(&program_id, &user, entity_type, kind, id).0
+}
+
+/// Submit a batch signed by `signer`, asserting that it fits in a packet and in the default compute budget
+/// Compute units are only metered when the tests run against the SBF build of the program, with cargo test-sbf
+async fn process_within_limits(test: &mut TestAdmin, batch: Instruction, signer: &Keypair) {
+    let tx = Transaction::new_signed_with_payer(
+        &[batch],
+        Some(&test.context.payer.pubkey()),
+        &[&test.context.payer, signer],
+        test.context.last_blockhash,
+    );
+    let tx_size = 1 + tx.signatures.len() * 64 + tx.message_data().len();
+    assert!(tx_size <= PACKET_DATA_SIZE);
+    let result = test
+        .context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    let compute_units = result.metadata.unwrap().compute_units_consumed;
+    assert!(compute_units <= INSTRUCTION_COMPUTE_UNIT_LIMIT);
+}
+
+#[tokio::test]
+/// The owner creates, updates and deletes a track, which no other user can change, refunding its payer
+async fn success_manage_entity() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let alice = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let alice_context = test.user_context(&alice, &alice.authority, false);
+    let bob_context = test.user_context(&bob, &bob.authority, false);
+
+    // The track is paid for by a sponsor other than the transaction fee payer
+    let sponsor = Keypair::new();
+    let mut sponsor_context = alice_context.clone();
+    sponsor_context.payer = sponsor.pubkey();
+    let address = entity_address(&alice_context, &EntityTypes::Track, 1);
+    let manage = |context: &UserContext, action: ManagementActions, metadata: &str| {
+        client::manage_entity(
+            &program_id,
+            context,
+            EntityTypes::Track,
+            action,
+            1,
+            metadata.to_string(),
+        )
+    };
+
+    let create = manage(&sponsor_context, ManagementActions::Create, METADATA_CID);
+    let payer = test.payer();
+    process(
+        &mut test.context,
+        &[
+            system_instruction::transfer(&payer, &sponsor.pubkey(), LAMPORTS_PER_SOL),
+            create,
+        ],
+        &[&sponsor, &alice.authority],
+    )
+    .await
+    .unwrap();
+    let entity: Entity = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(entity.version, ACCOUNT_VERSION);
+    assert_eq!(entity.owner, alice_context.user(&program_id).0);
+    assert_eq!(entity.payer, sponsor.pubkey());
+
+    let create = manage(&bob_context, ManagementActions::Create, METADATA_CID);
+    let result = process(&mut test.context, &[create], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::EntityAlreadyExists);
+
+    let update = manage(&bob_context, ManagementActions::Update, METADATA_CID);
+    let result = process(&mut test.context, &[update], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let update = manage(&alice_context, ManagementActions::Update, "not a cid");
+    let result = process(&mut test.context, &[update], &[&alice.authority]).await;
+    assert_error(result, ErrorCode::InvalidMetadata);
+
+    let update = manage(&alice_context, ManagementActions::Update, METADATA_CID);
+    process(&mut test.context, &[update], &[&alice.authority])
+        .await
+        .unwrap();
+
+    let delete = manage(&bob_context, ManagementActions::Delete, "");
+    let result = process(&mut test.context, &[delete], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // The rent is only refunded to the sponsor that paid it
+    let mut delete = manage(&alice_context, ManagementActions::Delete, "");
+    let result = process(
+        &mut test.context,
+        std::slice::from_ref(&delete),
+        &[&alice.authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let sponsor_balance = balance(&mut test.context, &sponsor.pubkey()).await;
+    let entity_balance = balance(&mut test.context, &address).await;
+    delete.accounts.last_mut().unwrap().pubkey = sponsor.pubkey();
+    process(&mut test.context, &[delete], &[&alice.authority])
+        .await
+        .unwrap();
+    assert!(get_account::<Entity>(&mut test.context, &address)
+        .await
+        .is_none());
+    assert_eq!(
+        balance(&mut test.context, &sponsor.pubkey()).await,
+        sponsor_balance + entity_balance
+    );
+
+    // Deletes may carry metadata, which differs from the delete already submitted
+    let delete = manage(&alice_context, ManagementActions::Delete, METADATA_CID);
+    let result = process(&mut test.context, &[delete], &[&alice.authority]).await;
+    assert_error(result, ErrorCode::EntityNotFound);
+}
+
+#[tokio::test]
+/// Delegates manage entities for the user only with the manage entity permission
+async fn success_manage_entity_delegate() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let entity_delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_MANAGE_ENTITY, None)
+        .await;
+    let follow_delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_FOLLOW, None)
+        .await;
+
+    let context = test.user_context(&user, &follow_delegate, true);
+    let create = client::manage_entity(
+        &program_id,
+        &context,
+        EntityTypes::Playlist,
+        ManagementActions::Create,
+        1,
+        METADATA_CID.to_string(),
+    );
+    let result = process(&mut test.context, &[create], &[&follow_delegate]).await;
+    assert_error(result, ErrorCode::MissingPermission);
+
+    let context = test.user_context(&user, &entity_delegate, true);
+    let create = client::manage_entity(
+        &program_id,
+        &context,
+        EntityTypes::Playlist,
+        ManagementActions::Create,
+        1,
+        METADATA_CID.to_string(),
+    );
+    process(&mut test.context, &[create], &[&entity_delegate])
+        .await
+        .unwrap();
+    let entity: Entity = get_account(
+        &mut test.context,
+        &entity_address(&context, &EntityTypes::Playlist, 1),
+    )
+    .await
+    .unwrap();
+    assert_eq!(entity.owner, test.user_address(&user.handle_seed));
+}
+
+#[tokio::test]
+/// The playlist owner appends, moves and removes tracks, growing the contents account as needed
+async fn success_manage_playlist_contents() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let context = test.user_context(&user, &user.authority, false);
+    let address = playlist_contents_address(&context, 1);
+
+    let append = client::manage_playlist_contents(
+        &program_id,
+        &context,
+        1,
+        PlaylistContentsAction::Append { track_id: 1 },
+    );
+    let result = process(&mut test.context, &[append], &[&user.authority]).await;
+    assert_error(result, ErrorCode::EntityNotFound);
+
+    let create = client::manage_entity(
+        &program_id,
+        &context,
+        EntityTypes::Playlist,
+        ManagementActions::Create,
+        1,
+        METADATA_CID.to_string(),
+    );
+    process(&mut test.context, &[create], &[&user.authority])
+        .await
+        .unwrap();
+
+    // The first append repeats the append rejected above
+    refresh_blockhash(&mut test.context).await;
+    let track_count = PLAYLIST_CONTENTS_CAPACITY_INCREMENT as u64 + 1;
+    for track_id in 1..=track_count {
+        let append = client::manage_playlist_contents(
+            &program_id,
+            &context,
+            1,
+            PlaylistContentsAction::Append { track_id },
+        );
+        process(&mut test.context, &[append], &[&user.authority])
+            .await
+            .unwrap();
+    }
+    let contents: PlaylistContents = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(contents.owner, test.user_address(&user.handle_seed));
+    assert_eq!(contents.playlist_id, 1);
+    assert_eq!(contents.track_ids, (1..=track_count).collect::<Vec<_>>());
+    let account = test
+        .context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        account.data.len(),
+        playlist_contents_account_size(2 * PLAYLIST_CONTENTS_CAPACITY_INCREMENT)
+    );
+
+    let edits = [
+        PlaylistContentsAction::Move {
+            from: 0,
+            to: 2,
+            track_id: 1,
+        },
+        PlaylistContentsAction::Remove {
+            index: 0,
+            track_id: 2,
+        },
+    ];
+    for edit in edits {
+        let ix = client::manage_playlist_contents(&program_id, &context, 1, edit);
+        process(&mut test.context, &[ix], &[&user.authority])
+            .await
+            .unwrap();
+    }
+    let contents: PlaylistContents = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(&contents.track_ids[..3], &[3, 1, 4]);
+    assert_eq!(contents.track_ids.len() as u64, track_count - 1);
+
+    // The removal names a track that is not at the index
+    let remove = client::manage_playlist_contents(
+        &program_id,
+        &context,
+        1,
+        PlaylistContentsAction::Remove {
+            index: 0,
+            track_id: 1,
+        },
+    );
+    let result = process(&mut test.context, &[remove], &[&user.authority]).await;
+    assert_error(result, ErrorCode::InvalidPlaylistIndex);
+}
+
+#[tokio::test]
+/// Deleting a playlist closes its contents, so a playlist re-created with the id starts empty
+async fn success_delete_and_recreate_playlist() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let context = test.user_context(&user, &user.authority, false);
+    let address = playlist_contents_address(&context, 1);
+    let create = client::manage_entity(
+        &program_id,
+        &context,
+        EntityTypes::Playlist,
+        ManagementActions::Create,
+        1,
+        METADATA_CID.to_string(),
+    );
+    let append = |track_id| {
+        client::manage_playlist_contents(
+            &program_id,
+            &context,
+            1,
+            PlaylistContentsAction::Append { track_id },
+        )
+    };
+    process(
+        &mut test.context,
+        &[create.clone(), append(1)],
+        &[&user.authority],
+    )
+    .await
+    .unwrap();
+    let contents: PlaylistContents = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(contents.payer, test.payer());
+
+    // The contents are refunded to the payer that allocated them
+    let delete = client::manage_entity(
+        &program_id,
+        &context,
+        EntityTypes::Playlist,
+        ManagementActions::Delete,
+        1,
+        String::new(),
+    );
+    let mut wrong_refund = delete.clone();
+    wrong_refund.accounts.last_mut().unwrap().pubkey = Pubkey::new_unique();
+    let result = process(&mut test.context, &[wrong_refund], &[&user.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // The contents must be passed with the playlist
+    let mut missing_contents = delete.clone();
+    missing_contents
+        .accounts
+        .truncate(missing_contents.accounts.len() - 2);
+    let result = process(&mut test.context, &[missing_contents], &[&user.authority]).await;
+    assert_error(result, ErrorCode::ProgramDerivedAddressNotFound);
+
+    process(&mut test.context, &[delete], &[&user.authority])
+        .await
+        .unwrap();
+    assert!(test
+        .context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_none());
+
+    refresh_blockhash(&mut test.context).await;
+    process(&mut test.context, &[create, append(2)], &[&user.authority])
+        .await
+        .unwrap();
+    let contents: PlaylistContents = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(contents.track_ids, vec![2]);
+}
+
+#[tokio::test]
+/// Only the owner of a playlist may change its contents
+async fn failure_manage_playlist_contents_not_owner() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let alice = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let alice_context = test.user_context(&alice, &alice.authority, false);
+    let bob_context = test.user_context(&bob, &bob.authority, false);
+
+    let create = client::manage_entity(
+        &program_id,
+        &alice_context,
+        EntityTypes::Playlist,
+        ManagementActions::Create,
+        1,
+        METADATA_CID.to_string(),
+    );
+    process(&mut test.context, &[create], &[&alice.authority])
+        .await
+        .unwrap();
+
+    let append = client::manage_playlist_contents(
+        &program_id,
+        &bob_context,
+        1,
+        PlaylistContentsAction::Append { track_id: 1 },
+    );
+    let result = process(&mut test.context, &[append], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Bob's authority signing for Alice
+    let mut context = alice_context.clone();
+    context.authority = bob.authority.pubkey();
+    let append = client::manage_playlist_contents(
+        &program_id,
+        &context,
+        1,
+        PlaylistContentsAction::Append { track_id: 1 },
+    );
+    let result = process(&mut test.context, &[append], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::MissingDelegateAccount);
+}
+
+#[tokio::test]
+/// Saves and reposts are added once and deleted once, refunding the payer of the social action
+async fn success_write_entity_social_action() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let context = test.user_context(&user, &user.authority, false);
+
+    // Social actions are paid for by a sponsor other than the transaction fee payer
+    let sponsor = Keypair::new();
+    let mut sponsor_context = context.clone();
+    sponsor_context.payer = sponsor.pubkey();
+    let payer = test.payer();
+    process(
+        &mut test.context,
+        &[system_instruction::transfer(
+            &payer,
+            &sponsor.pubkey(),
+            LAMPORTS_PER_SOL,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let write = |action: EntitySocialActionValues| {
+        let context = if action.is_delete() {
+            &context
+        } else {
+            &sponsor_context
+        };
+        client::write_entity_social_action(
+            &program_id,
+            context,
+            action,
+            EntityTypes::Track,
+            "1".to_string(),
+        )
+    };
+
+    for (add, delete, kind) in [
+        (
+            EntitySocialActionValues::AddSave,
+            EntitySocialActionValues::DeleteSave,
+            SocialActionKinds::Save,
+        ),
+        (
+            EntitySocialActionValues::AddRepost,
+            EntitySocialActionValues::DeleteRepost,
+            SocialActionKinds::Repost,
+        ),
+    ] {
+        let address = social_action_address(&context, &EntityTypes::Track, &kind, "1");
+
+        process(
+            &mut test.context,
+            &[write(add.clone())],
+            &[&sponsor, &user.authority],
+        )
+        .await
+        .unwrap();
+        let social_action: EntitySocialAction =
+            get_account(&mut test.context, &address).await.unwrap();
+        assert_eq!(social_action.user, test.user_address(&user.handle_seed));
+        assert!(social_action.entity_type == EntityTypes::Track);
+        assert!(social_action.social_action_kind == kind);
+        assert_eq!(social_action.payer, sponsor.pubkey());
+
+        refresh_blockhash(&mut test.context).await;
+        let result = process(
+            &mut test.context,
+            &[write(add)],
+            &[&sponsor, &user.authority],
+        )
+        .await;
+        assert_error(result, ErrorCode::SocialActionAlreadyExists);
+
+        // The rent is only refunded to the sponsor that paid it
+        let result = process(
+            &mut test.context,
+            &[write(delete.clone())],
+            &[&user.authority],
+        )
+        .await;
+        assert_error(result, ErrorCode::Unauthorized);
+
+        let sponsor_balance = balance(&mut test.context, &sponsor.pubkey()).await;
+        let social_action_balance = balance(&mut test.context, &address).await;
+        let mut delete_ix = write(delete.clone());
+        delete_ix.accounts.last_mut().unwrap().pubkey = sponsor.pubkey();
+        process(&mut test.context, &[delete_ix], &[&user.authority])
+            .await
+            .unwrap();
+        assert!(
+            get_account::<EntitySocialAction>(&mut test.context, &address)
+                .await
+                .is_none()
+        );
+        assert_eq!(
+            balance(&mut test.context, &sponsor.pubkey()).await,
+            sponsor_balance + social_action_balance
+        );
+
+        refresh_blockhash(&mut test.context).await;
+        let result = process(&mut test.context, &[write(delete)], &[&user.authority]).await;
+        assert_error(result, ErrorCode::SocialActionNotFound);
+    }
+
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_FOLLOW, None)
+        .await;
+    let delegate_context = test.user_context(&user, &delegate, true);
+    let save = client::write_entity_social_action(
+        &program_id,
+        &delegate_context,
+        EntitySocialActionValues::AddSave,
+        EntityTypes::Track,
+        "1".to_string(),
+    );
+    let result = process(&mut test.context, &[save], &[&delegate]).await;
+    assert_error(result, ErrorCode::MissingPermission);
+}
+
+#[tokio::test]
+/// A user follows and unfollows another user through the Follow PDA of the pair, refunding its payer
+async fn success_follow_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let alice = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let carol = test.claimed_user("carol", 3).await;
+    let alice_address = test.user_address(&alice.handle_seed);
+    let bob_address = test.user_address(&bob.handle_seed);
+    let follow_address = client::find_follow_address(&program_id, &alice_address, &bob_address).0;
+    let context = test.user_context(&alice, &alice.authority, false);
+
+    // The follow is paid for by a sponsor other than the transaction fee payer
+    let sponsor = Keypair::new();
+    let mut sponsor_context = context.clone();
+    sponsor_context.payer = sponsor.pubkey();
+    let follow = client::follow_user(
+        &program_id,
+        &sponsor_context,
+        UserAction::FollowUser,
+        &bob.handle_seed,
+    );
+    let payer = test.payer();
+    process(
+        &mut test.context,
+        &[
+            system_instruction::transfer(&payer, &sponsor.pubkey(), LAMPORTS_PER_SOL),
+            follow.clone(),
+        ],
+        &[&sponsor, &alice.authority],
+    )
+    .await
+    .unwrap();
+    let account: Follow = get_account(&mut test.context, &follow_address)
+        .await
+        .unwrap();
+    assert_eq!(account.follower, alice_address);
+    assert_eq!(account.followee, bob_address);
+    assert_eq!(account.payer, sponsor.pubkey());
+
+    let result = process(&mut test.context, &[follow], &[&sponsor, &alice.authority]).await;
+    assert_error(result, ErrorCode::FollowAlreadyExists);
+
+    // The Follow PDA of another pair of users
+    let mut follow = client::follow_user(
+        &program_id,
+        &context,
+        UserAction::FollowUser,
+        &carol.handle_seed,
+    );
+    follow.accounts[6].pubkey = follow_address;
+    let result = process(&mut test.context, &[follow], &[&alice.authority]).await;
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+
+    // The rent of the follow is only refunded to the sponsor that paid it
+    let mut unfollow = client::follow_user(
+        &program_id,
+        &context,
+        UserAction::UnfollowUser,
+        &bob.handle_seed,
+    );
+    let result = process(
+        &mut test.context,
+        std::slice::from_ref(&unfollow),
+        &[&alice.authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let sponsor_balance = balance(&mut test.context, &sponsor.pubkey()).await;
+    let follow_balance = balance(&mut test.context, &follow_address).await;
+    unfollow.accounts.last_mut().unwrap().pubkey = sponsor.pubkey();
+    process(
+        &mut test.context,
+        std::slice::from_ref(&unfollow),
+        &[&alice.authority],
+    )
+    .await
+    .unwrap();
+    assert!(get_account::<Follow>(&mut test.context, &follow_address)
+        .await
+        .is_none());
+    assert_eq!(
+        balance(&mut test.context, &sponsor.pubkey()).await,
+        sponsor_balance + follow_balance
+    );
+
+    refresh_blockhash(&mut test.context).await;
+    let result = process(&mut test.context, &[unfollow], &[&alice.authority]).await;
+    assert_error(result, ErrorCode::FollowNotFound);
+}
+
+#[tokio::test]
+/// A batch applies follows, saves and reposts, and a delegate needs every permission it uses
+async fn success_write_social_actions() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let alice = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let alice_address = test.user_address(&alice.handle_seed);
+    let bob_address = test.user_address(&bob.handle_seed);
+    let context = test.user_context(&alice, &alice.authority, false);
+    let base = context.base(&program_id);
+    let follow = SocialAction::Follow {
+        user_action: UserAction::FollowUser,
+        followee_handle: client::user_handle(&program_id, &base, &bob.handle_seed).1,
+    };
+    let save = SocialAction::EntitySocialAction {
+        entity_social_action: EntitySocialActionValues::AddSave,
+        entity_type: EntityTypes::Track,
+        id: "1".to_string(),
+    };
+    let repost = SocialAction::EntitySocialAction {
+        entity_social_action: EntitySocialActionValues::AddRepost,
+        entity_type: EntityTypes::Playlist,
+        id: "2".to_string(),
+    };
+
+    let delegate = test
+        .add_delegate(&alice, DELEGATE_PERMISSION_FOLLOW, None)
+        .await;
+    let delegate_context = test.user_context(&alice, &delegate, true);
+    let batch = client::write_social_actions(
+        &program_id,
+        &delegate_context,
+        vec![follow.clone(), save.clone()],
+    );
+    let result = process(&mut test.context, &[batch], &[&delegate]).await;
+    assert_error(result, ErrorCode::MissingPermission);
+
+    let batch = client::write_social_actions(&program_id, &context, vec![]);
+    let result = process(&mut test.context, &[batch], &[&alice.authority]).await;
+    assert_error(result, ErrorCode::InvalidSocialActionBatch);
+
+    // An account that belongs to no action
+    let mut batch = client::write_social_actions(&program_id, &context, vec![save.clone()]);
+    batch
+        .accounts
+        .push(solana_sdk::instruction::AccountMeta::new(
+            bob_address,
+            false,
+        ));
+    let result = process(&mut test.context, &[batch], &[&alice.authority]).await;
+    assert_error(result, ErrorCode::InvalidSocialActionBatch);
+
+    let batch = client::write_social_actions(&program_id, &context, vec![follow, save, repost]);
+    process(&mut test.context, &[batch], &[&alice.authority])
+        .await
+        .unwrap();
+
+    let follow_address = client::find_follow_address(&program_id, &alice_address, &bob_address).0;
+    assert!(get_account::<Follow>(&mut test.context, &follow_address)
+        .await
+        .is_some());
+    let save_address =
+        social_action_address(&context, &EntityTypes::Track, &SocialActionKinds::Save, "1");
+    assert!(
+        get_account::<EntitySocialAction>(&mut test.context, &save_address)
+            .await
+            .is_some()
+    );
+    let repost_address = social_action_address(
+        &context,
+        &EntityTypes::Playlist,
+        &SocialActionKinds::Repost,
+        "2",
+    );
+    assert!(
+        get_account::<EntitySocialAction>(&mut test.context, &repost_address)
+            .await
+            .is_some()
+    );
+
+    let unfollow = SocialAction::Follow {
+        user_action: UserAction::UnfollowUser,
+        followee_handle: client::user_handle(&program_id, &base, &bob.handle_seed).1,
+    };
+    let delete_save = SocialAction::EntitySocialAction {
+        entity_social_action: EntitySocialActionValues::DeleteSave,
+        entity_type: EntityTypes::Track,
+        id: "1".to_string(),
+    };
+    let batch = client::write_social_actions(&program_id, &context, vec![unfollow, delete_save]);
+    process(&mut test.context, &[batch], &[&alice.authority])
+        .await
+        .unwrap();
+    assert!(get_account::<Follow>(&mut test.context, &follow_address)
+        .await
+        .is_none());
+    assert!(
+        get_account::<EntitySocialAction>(&mut test.context, &save_address)
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+/// Full batches of follows and unfollows signed by a delegate fit in a transaction and its compute budget,
+/// one more action is rejected
+async fn success_write_max_social_actions() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let alice = test.claimed_user("alice", 1).await;
+    let alice_address = test.user_address(&alice.handle_seed);
+    let delegate = test
+        .add_delegate(&alice, DELEGATE_PERMISSION_ALL, None)
+        .await;
+    let context = test.user_context(&alice, &delegate, true);
+    let base = context.base(&program_id);
+
+    let mut follows = vec![];
+    let mut followees = vec![];
+    for i in 0..MAX_SOCIAL_ACTION_BATCH_SIZE {
+        let followee = test
+            .claimed_user(&format!("followee{}", i), i as u8 + 2)
+            .await;
+        follows.push(SocialAction::Follow {
+            user_action: UserAction::FollowUser,
+            followee_handle: client::user_handle(&program_id, &base, &followee.handle_seed).1,
+        });
+        followees.push(test.user_address(&followee.handle_seed));
+    }
+    let batch = client::write_social_actions(&program_id, &context, follows.clone());
+    process_within_limits(&mut test, batch, &delegate).await;
+    for followee in &followees {
+        let follow_address = client::find_follow_address(&program_id, &alice_address, followee).0;
+        assert!(get_account::<Follow>(&mut test.context, &follow_address)
+            .await
+            .is_some());
+    }
+
+    // Unfollows also pass the payer of each follow
+    let unfollows = follows
+        .into_iter()
+        .map(|follow| match follow {
+            SocialAction::Follow {
+                followee_handle, ..
+            } => SocialAction::Follow {
+                user_action: UserAction::UnfollowUser,
+                followee_handle,
+            },
+            other => other,
+        })
+        .collect();
+    let batch = client::write_social_actions(&program_id, &context, unfollows);
+    process_within_limits(&mut test, batch, &delegate).await;
+    for followee in &followees {
+        let follow_address = client::find_follow_address(&program_id, &alice_address, followee).0;
+        assert!(get_account::<Follow>(&mut test.context, &follow_address)
+            .await
+            .is_none());
+    }
+
+    let saves = (0..=MAX_SOCIAL_ACTION_BATCH_SIZE)
+        .map(|id| SocialAction::EntitySocialAction {
+            entity_social_action: EntitySocialActionValues::AddSave,
+            entity_type: EntityTypes::Track,
+            id: id.to_string(),
+        })
+        .collect();
+    let batch = client::write_social_actions(&program_id, &context, saves);
+    let result = process(&mut test.context, &[batch], &[&delegate]).await;
+    assert_error(result, ErrorCode::InvalidSocialActionBatch);
+}
@@ -0,0 +1,762 @@
+mod utils;
+use audius_data::{
+    claim::ClaimKind, client, constants::*, error::ErrorCode, User, UserRedirect, UserTombstone,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::{self, SystemError},
+};
+use utils::*;
+
+#[tokio::test]
+/// The admin authority initializes an unclaimed user with its replica set
+async fn success_init_user() {
+    let mut test = setup().await;
+    let eth_key = eth_key(1);
+    let handle_seed = test.init_user("alice", &eth_key).await;
+    let address = test.user_address(&handle_seed);
+
+    let user: User = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(user.version, ACCOUNT_VERSION);
+    assert_eq!(user.eth_address, eth_address(&eth_key));
+    assert_eq!(user.authority, Pubkey::default());
+    assert_eq!(user.replica_set, REPLICA_SET.to_vec());
+    assert!(!user.is_verified);
+    assert_eq!(user.authority_epoch, 0);
+}
+
+#[tokio::test]
+/// Users are only initialized by the admin authority, with a valid replica set and metadata
+async fn failure_init_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let admin = test.admin.pubkey();
+    let authority = test.authority.pubkey();
+    let init_user = |authority: &Pubkey, replica_set: &[u16], metadata: &str| {
+        client::init_user(
+            &program_id,
+            &admin,
+            authority,
+            &payer,
+            &client::handle_seed("alice"),
+            [1; 20],
+            replica_set.to_vec(),
+            metadata.to_string(),
+        )
+    };
+
+    let impostor = Keypair::new();
+    let ix = init_user(&impostor.pubkey(), &REPLICA_SET, METADATA_CID);
+    let result = process(&mut test.context, &[ix], &[&impostor]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let ix = init_user(&authority, &[1, 2, 9], METADATA_CID);
+    let result = process(&mut test.context, &[ix], &[&test.authority]).await;
+    assert_error(result, ErrorCode::ContentNodeNotFound);
+
+    let ix = init_user(&authority, &[1, 1, 2], METADATA_CID);
+    let result = process(&mut test.context, &[ix], &[&test.authority]).await;
+    assert_error(result, ErrorCode::InvalidReplicaSet);
+
+    let ix = init_user(&authority, &REPLICA_SET, "not a cid");
+    let result = process(&mut test.context, &[ix], &[&test.authority]).await;
+    assert_error(result, ErrorCode::InvalidMetadata);
+}
+
+#[tokio::test]
+/// A user is claimed once with a claim signed by its Ethereum key
+async fn success_init_user_sol() {
+    let mut test = setup().await;
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+
+    let account: User = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(account.authority, user.authority.pubkey());
+    assert!(account.authority_updated_at > 0);
+
+    // A claimed user can not be claimed again, even with a valid signature
+    let authority = Keypair::new().pubkey();
+    let expires_at = test.claim_expiry().await;
+    let claim = test.claim_instruction(
+        &user.eth_key,
+        ClaimKind::Claim,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let init_user_sol = client::init_user_sol(
+        &audius_data::id(),
+        &test.admin.pubkey(),
+        &address,
+        authority,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[claim, init_user_sol], &[]).await;
+    assert_error(result, ErrorCode::UserAlreadyClaimed);
+}
+
+#[tokio::test]
+/// Claims must be signed by the user's Ethereum key, for the claimed authority, and unexpired
+async fn failure_init_user_sol() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let eth_key = eth_key(1);
+    let handle_seed = test.init_user("alice", &eth_key).await;
+    let address = test.user_address(&handle_seed);
+    let authority = Keypair::new().pubkey();
+    let expires_at = test.claim_expiry().await;
+    let init_user_sol = client::init_user_sol(
+        &program_id,
+        &test.admin.pubkey(),
+        &address,
+        authority,
+        expires_at,
+    );
+
+    // No secp256k1 instruction
+    let result = process(&mut test.context, std::slice::from_ref(&init_user_sol), &[]).await;
+    assert_error(result, ErrorCode::SignatureVerification);
+
+    // Signed by another Ethereum key
+    let claim = test.claim_instruction(
+        &utils::eth_key(2),
+        ClaimKind::Claim,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[claim, init_user_sol.clone()], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Signed for another authority
+    let claim = test.claim_instruction(
+        &eth_key,
+        ClaimKind::Claim,
+        &address,
+        &Keypair::new().pubkey(),
+        0,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[claim, init_user_sol.clone()], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Signed as a recovery
+    let claim = test.claim_instruction(
+        &eth_key,
+        ClaimKind::Recovery,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[claim, init_user_sol.clone()], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Signed for another expiry than the one submitted
+    let claim = test.claim_instruction(
+        &eth_key,
+        ClaimKind::Claim,
+        &address,
+        &authority,
+        0,
+        expires_at + 1,
+    );
+    let result = process(&mut test.context, &[claim, init_user_sol.clone()], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Submitted after its expiry
+    let claim = test.claim_instruction(
+        &eth_key,
+        ClaimKind::Claim,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let now = unix_timestamp(&mut test.context).await;
+    set_unix_timestamp(&mut test.context, now + CLAIM_LIFETIME + 1).await;
+    let result = process(&mut test.context, &[claim, init_user_sol], &[]).await;
+    assert_error(result, ErrorCode::ClaimExpired);
+}
+
+#[tokio::test]
+/// Recovery rotates the authority, starts a new epoch and invalidates earlier delegates
+async fn success_recover_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_ALL, None)
+        .await;
+    let new_authority = Keypair::new();
+    let expires_at = test.claim_expiry().await;
+    let recover_user = client::recover_user(
+        &program_id,
+        &test.admin.pubkey(),
+        &address,
+        new_authority.pubkey(),
+        expires_at,
+    );
+
+    // A claim can not be replayed as a recovery
+    let claim = test.claim_instruction(
+        &user.eth_key,
+        ClaimKind::Claim,
+        &address,
+        &new_authority.pubkey(),
+        0,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[claim, recover_user.clone()], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let recovery = test.claim_instruction(
+        &user.eth_key,
+        ClaimKind::Recovery,
+        &address,
+        &new_authority.pubkey(),
+        0,
+        expires_at,
+    );
+    process(&mut test.context, &[recovery, recover_user], &[])
+        .await
+        .unwrap();
+
+    let account: User = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(account.authority, new_authority.pubkey());
+    assert_eq!(account.authority_epoch, 1);
+
+    // The recovery was signed for the previous epoch
+    let replay_authority = Keypair::new().pubkey();
+    let replay = test.claim_instruction(
+        &user.eth_key,
+        ClaimKind::Recovery,
+        &address,
+        &replay_authority,
+        0,
+        expires_at,
+    );
+    let recover_user = client::recover_user(
+        &program_id,
+        &test.admin.pubkey(),
+        &address,
+        replay_authority,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[replay, recover_user], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Delegates added before the recovery can no longer act for the user
+    let context = test.user_context(&user, &delegate, true);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&delegate]).await;
+    assert_error(result, ErrorCode::DelegateInvalidated);
+
+    // The previous authority can no longer act for the user
+    let context = test.user_context(&user, &user.authority, false);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&user.authority]).await;
+    assert_error(result, ErrorCode::MissingDelegateAccount);
+}
+
+#[tokio::test]
+/// Unclaimed users are claimed with init_user_sol rather than recovered
+async fn failure_recover_unclaimed_user() {
+    let mut test = setup().await;
+    let eth_key = eth_key(1);
+    let handle_seed = test.init_user("alice", &eth_key).await;
+    let address = test.user_address(&handle_seed);
+    let authority = Keypair::new().pubkey();
+    let expires_at = test.claim_expiry().await;
+
+    let recovery = test.claim_instruction(
+        &eth_key,
+        ClaimKind::Recovery,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let recover_user = client::recover_user(
+        &audius_data::id(),
+        &test.admin.pubkey(),
+        &address,
+        authority,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[recovery, recover_user], &[]).await;
+    assert_error(result, ErrorCode::UserNotClaimed);
+}
+
+#[tokio::test]
+/// Once admin writes are disabled, users create themselves with a signed claim
+async fn success_create_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let eth_key = eth_key(1);
+    let handle_seed = client::handle_seed("alice");
+    let address = test.user_address(&handle_seed);
+    let authority = Keypair::new().pubkey();
+    let expires_at = test.claim_expiry().await;
+    let create_user = client::create_user(
+        &program_id,
+        &test.admin.pubkey(),
+        &payer,
+        &handle_seed,
+        eth_address(&eth_key),
+        REPLICA_SET.to_vec(),
+        METADATA_CID.to_string(),
+        1,
+        authority,
+        expires_at,
+    );
+
+    // Rejected while admin writes are enabled
+    let claim = test.claim_instruction(
+        &eth_key,
+        ClaimKind::Claim,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let result = process(
+        &mut test.context,
+        &[claim.clone(), create_user.clone()],
+        &[],
+    )
+    .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let update_admin = client::update_admin(
+        &program_id,
+        &test.admin.pubkey(),
+        &test.authority.pubkey(),
+        false,
+    );
+    process(&mut test.context, &[update_admin], &[&test.authority])
+        .await
+        .unwrap();
+
+    // Rejected when signed by another Ethereum key
+    let forged = test.claim_instruction(
+        &utils::eth_key(2),
+        ClaimKind::Claim,
+        &address,
+        &authority,
+        0,
+        expires_at,
+    );
+    let result = process(&mut test.context, &[forged, create_user.clone()], &[]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Accepted until its expiry, long after it was signed
+    let now = unix_timestamp(&mut test.context).await;
+    set_unix_timestamp(&mut test.context, now + CLAIM_LIFETIME).await;
+    refresh_blockhash(&mut test.context).await;
+    process(&mut test.context, &[claim, create_user], &[])
+        .await
+        .unwrap();
+    let account: User = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(account.version, ACCOUNT_VERSION);
+    assert_eq!(account.eth_address, eth_address(&eth_key));
+    assert_eq!(account.authority, authority);
+    assert_eq!(account.replica_set, REPLICA_SET.to_vec());
+}
+
+#[tokio::test]
+/// Only the user's authority or a delegate may log metadata, which must be a valid CID
+async fn success_update_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let user = test.claimed_user("alice", 1).await;
+
+    let context = test.user_context(&user, &user.authority, false);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    process(&mut test.context, &[update_user], &[&user.authority])
+        .await
+        .unwrap();
+
+    let update_user = client::update_user(&program_id, &context, "not a cid".to_string());
+    let result = process(&mut test.context, &[update_user], &[&user.authority]).await;
+    assert_error(result, ErrorCode::InvalidMetadata);
+
+    let impostor = Keypair::new();
+    let context = test.user_context(&user, &impostor, false);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&impostor]).await;
+    assert_error(result, ErrorCode::MissingDelegateAccount);
+}
+
+#[tokio::test]
+/// The admin verifier sets and clears verification, for the user PDA of the handle only
+async fn success_update_is_verified() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+    let alice = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let alice_address = test.user_address(&alice.handle_seed);
+
+    let verify = client::update_is_verified(
+        &program_id,
+        &admin,
+        &alice.handle_seed,
+        &test.verifier.pubkey(),
+        true,
+    );
+    process(&mut test.context, &[verify], &[&test.verifier])
+        .await
+        .unwrap();
+    let account: User = get_account(&mut test.context, &alice_address)
+        .await
+        .unwrap();
+    assert!(account.is_verified);
+
+    let unverify = client::update_is_verified(
+        &program_id,
+        &admin,
+        &alice.handle_seed,
+        &test.verifier.pubkey(),
+        false,
+    );
+    process(&mut test.context, &[unverify], &[&test.verifier])
+        .await
+        .unwrap();
+    let account: User = get_account(&mut test.context, &alice_address)
+        .await
+        .unwrap();
+    assert!(!account.is_verified);
+
+    let verify = client::update_is_verified(
+        &program_id,
+        &admin,
+        &alice.handle_seed,
+        &test.authority.pubkey(),
+        true,
+    );
+    let result = process(&mut test.context, &[verify], &[&test.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    // Another user's PDA does not match the handle
+    let mut verify = client::update_is_verified(
+        &program_id,
+        &admin,
+        &alice.handle_seed,
+        &test.verifier.pubkey(),
+        true,
+    );
+    verify.accounts[1].pubkey = test.user_address(&bob.handle_seed);
+    let result = process(&mut test.context, &[verify], &[&test.verifier]).await;
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+/// The user's authority or a content node of the current replica set replaces the replica set,
+/// a smaller replica set leaves the user account and its rent unchanged
+async fn success_update_user_replica_set() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let admin = test.admin.pubkey();
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+    let user_balance = balance(&mut test.context, &address).await;
+
+    let update = client::update_user_replica_set(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+        &payer,
+        vec![2, 3],
+    );
+    process(&mut test.context, &[update], &[&user.authority])
+        .await
+        .unwrap();
+    let account: User = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(account.replica_set, vec![2, 3]);
+    let account = test
+        .context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), user_account_size(REPLICA_SET.len()));
+    assert_eq!(account.lamports, user_balance);
+
+    // Content node 1 is no longer in the user's replica set
+    let cn_authority = &test.content_node_authorities[0];
+    let update = client::update_user_replica_set(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &cn_authority.pubkey(),
+        &payer,
+        vec![1, 2],
+    );
+    let result = process(&mut test.context, &[update], &[cn_authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let cn_authority = &test.content_node_authorities[1];
+    let update = client::update_user_replica_set(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &cn_authority.pubkey(),
+        &payer,
+        vec![2, 3, 1],
+    );
+    process(&mut test.context, &[update], &[cn_authority])
+        .await
+        .unwrap();
+    let account: User = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(account.replica_set, vec![2, 3, 1]);
+
+    let update = client::update_user_replica_set(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+        &payer,
+        vec![1, 2, 3, 4],
+    );
+    let result = process(&mut test.context, &[update], &[&user.authority]).await;
+    assert_error(result, ErrorCode::InvalidReplicaSet);
+}
+
+#[tokio::test]
+/// The user's authority moves the user to a free handle, leaving a redirect at the old handle
+async fn success_change_user_handle() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let payer = test.payer();
+    let admin = test.admin.pubkey();
+    let user = test.claimed_user("alice", 1).await;
+    let bob = test.claimed_user("bob", 2).await;
+    let address = test.user_address(&user.handle_seed);
+    let new_handle_seed = client::handle_seed("alice2");
+    let new_address = test.user_address(&new_handle_seed);
+    let before: User = get_account(&mut test.context, &address).await.unwrap();
+
+    let change = client::change_user_handle(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+        &payer,
+        bob.handle_seed,
+    );
+    let result = process(&mut test.context, &[change], &[&user.authority]).await;
+    assert_error(result, ErrorCode::HandleAlreadyTaken);
+
+    let change = client::change_user_handle(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &bob.authority.pubkey(),
+        &payer,
+        new_handle_seed,
+    );
+    let result = process(&mut test.context, &[change], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let change = client::change_user_handle(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+        &payer,
+        new_handle_seed,
+    );
+    process(&mut test.context, &[change], &[&user.authority])
+        .await
+        .unwrap();
+
+    let redirect: UserRedirect = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(redirect.user, new_address);
+    let after: User = get_account(&mut test.context, &new_address).await.unwrap();
+    assert_eq!(after.authority, before.authority);
+    assert_eq!(after.eth_address, before.eth_address);
+    assert_eq!(after.replica_set, before.replica_set);
+
+    // The previous handle stays reserved by the redirect
+    let change = client::change_user_handle(
+        &program_id,
+        &admin,
+        &bob.handle_seed,
+        &bob.authority.pubkey(),
+        &payer,
+        user.handle_seed,
+    );
+    let result = process(&mut test.context, &[change], &[&bob.authority]).await;
+    assert_error(result, ErrorCode::HandleAlreadyTaken);
+
+    // The user now acts through the new handle only
+    let mut context = test.user_context(&user, &user.authority, false);
+    let update = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update], &[&user.authority]).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch,
+    );
+    context.handle_seed = new_handle_seed;
+    let update = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    process(&mut test.context, &[update], &[&user.authority])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+/// Deactivated users can no longer be changed, and are then closed by the admin authority
+async fn success_deactivate_and_close_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+    let user = test.claimed_user("alice", 1).await;
+    let address = test.user_address(&user.handle_seed);
+
+    let close = client::close_user(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &test.authority.pubkey(),
+    );
+    let result = process(
+        &mut test.context,
+        std::slice::from_ref(&close),
+        &[&test.authority],
+    )
+    .await;
+    assert_error(result, ErrorCode::UserNotDeactivated);
+
+    let impostor = Keypair::new();
+    let deactivate =
+        client::deactivate_user(&program_id, &admin, &user.handle_seed, &impostor.pubkey());
+    let result = process(&mut test.context, &[deactivate], &[&impostor]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let deactivate = client::deactivate_user(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+    );
+    process(&mut test.context, &[deactivate], &[&user.authority])
+        .await
+        .unwrap();
+    let account: User = get_account(&mut test.context, &address).await.unwrap();
+    assert!(account.deactivated_at > 0);
+
+    let context = test.user_context(&user, &user.authority, false);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&user.authority]).await;
+    assert_error(result, ErrorCode::UserDeactivated);
+
+    // The close already submitted in this blockhash failed
+    refresh_blockhash(&mut test.context).await;
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    let user_rent = test
+        .context
+        .banks_client
+        .get_balance(address)
+        .await
+        .unwrap();
+    // The authority keeps a rent exempt balance after receiving the refund
+    let funding = rent.minimum_balance(0);
+    let payer = test.payer();
+    process(
+        &mut test.context,
+        &[
+            system_instruction::transfer(&payer, &test.authority.pubkey(), funding),
+            close,
+        ],
+        &[&test.authority],
+    )
+    .await
+    .unwrap();
+    let tombstone: UserTombstone = get_account(&mut test.context, &address).await.unwrap();
+    assert_eq!(tombstone.version, ACCOUNT_VERSION);
+    assert!(tombstone.closed_at > 0);
+    assert_eq!(
+        test.context
+            .banks_client
+            .get_balance(test.authority.pubkey())
+            .await
+            .unwrap(),
+        funding + user_rent - rent.minimum_balance(USER_TOMBSTONE_ACCOUNT_SIZE)
+    );
+}
+
+#[tokio::test]
+/// The handle of a closed user can not be taken again, so the closed user's delegates never act again
+async fn failure_recreate_closed_user() {
+    let mut test = setup().await;
+    let program_id = audius_data::id();
+    let admin = test.admin.pubkey();
+    let payer = test.payer();
+    let user = test.claimed_user("alice", 1).await;
+    let delegate = test
+        .add_delegate(&user, DELEGATE_PERMISSION_UPDATE_USER, None)
+        .await;
+
+    let deactivate = client::deactivate_user(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+    );
+    let close = client::close_user(
+        &program_id,
+        &admin,
+        &user.handle_seed,
+        &user.authority.pubkey(),
+    );
+    let funding = test
+        .context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+    process(
+        &mut test.context,
+        &[
+            system_instruction::transfer(&payer, &user.authority.pubkey(), funding),
+            deactivate,
+            close,
+        ],
+        &[&user.authority],
+    )
+    .await
+    .unwrap();
+
+    let init_user = client::init_user(
+        &program_id,
+        &admin,
+        &test.authority.pubkey(),
+        &payer,
+        &user.handle_seed,
+        eth_address(&eth_key(2)),
+        REPLICA_SET.to_vec(),
+        METADATA_CID.to_string(),
+    );
+    let result = process(&mut test.context, &[init_user], &[&test.authority]).await;
+    assert_error(result, SystemError::AccountAlreadyInUse as u32);
+
+    let context = test.user_context(&user, &delegate, true);
+    let update_user = client::update_user(&program_id, &context, METADATA_CID.to_string());
+    let result = process(&mut test.context, &[update_user], &[&delegate]).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch,
+    );
+}
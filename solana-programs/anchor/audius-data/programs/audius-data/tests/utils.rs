@@ -0,0 +1,332 @@
+#![allow(dead_code)]
+
+use anchor_lang::AccountDeserialize;
+use audius_data::{
+    claim::{ClaimKind, ClaimMessage},
+    client::{self, UserContext},
+};
+use libsecp256k1::{PublicKey, SecretKey};
+use solana_program_test::*;
+use solana_sdk::{
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    secp256k1_instruction::{construct_eth_pubkey, new_secp256k1_instruction},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+/// Metadata CID accepted by validate_metadata_cid
+pub const METADATA_CID: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+/// sp_ids of the content nodes created by setup, the default replica set of test users
+pub const REPLICA_SET: [u16; 3] = [1, 2, 3];
+
+/// Claims signed by tests stay valid for a day of test clock time
+pub const CLAIM_LIFETIME: i64 = 24 * 60 * 60;
+
+pub fn program_test() -> ProgramTest {
+    ProgramTest::new(
+        "audius_data",
+        audius_data::id(),
+        processor!(audius_data::entry),
+    )
+}
+
+/// Audius admin with a content node for each sp_id in REPLICA_SET
+pub struct TestAdmin {
+    pub context: ProgramTestContext,
+    pub admin: Keypair,
+    pub authority: Keypair,
+    pub verifier: Keypair,
+    // Authorities of the content nodes in REPLICA_SET order
+    pub content_node_authorities: Vec<Keypair>,
+}
+
+/// Claimed user of a TestAdmin
+pub struct TestUser {
+    pub handle_seed: [u8; 32],
+    pub eth_key: SecretKey,
+    pub authority: Keypair,
+}
+
+impl TestAdmin {
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Address of the user PDA for a handle seed
+    pub fn user_address(&self, handle_seed: &[u8; 32]) -> Pubkey {
+        let base = client::find_base_address(&audius_data::id(), &self.admin.pubkey()).0;
+        client::find_user_address(&audius_data::id(), &base, handle_seed).0
+    }
+
+    /// Context of instructions signed by `authority` on behalf of a user
+    pub fn user_context(
+        &self,
+        user: &TestUser,
+        authority: &Keypair,
+        is_delegate: bool,
+    ) -> UserContext {
+        UserContext {
+            admin: self.admin.pubkey(),
+            handle_seed: user.handle_seed,
+            authority: authority.pubkey(),
+            is_delegate,
+            payer: self.payer(),
+        }
+    }
+
+    /// Initialize a user through the admin authority
+    pub async fn init_user(&mut self, handle: &str, eth_key: &SecretKey) -> [u8; 32] {
+        let handle_seed = client::handle_seed(handle);
+        let init_user = client::init_user(
+            &audius_data::id(),
+            &self.admin.pubkey(),
+            &self.authority.pubkey(),
+            &self.payer(),
+            &handle_seed,
+            eth_address(eth_key),
+            REPLICA_SET.to_vec(),
+            METADATA_CID.to_string(),
+        );
+        process(&mut self.context, &[init_user], &[&self.authority])
+            .await
+            .unwrap();
+        handle_seed
+    }
+
+    /// Initialize a user through the admin authority and claim it for a new authority
+    pub async fn claimed_user(&mut self, handle: &str, eth_key_byte: u8) -> TestUser {
+        let eth_key = eth_key(eth_key_byte);
+        let handle_seed = self.init_user(handle, &eth_key).await;
+        let user = self.user_address(&handle_seed);
+        let authority = Keypair::new();
+        let expires_at = self.claim_expiry().await;
+        let claim = self.claim_instruction(
+            &eth_key,
+            ClaimKind::Claim,
+            &user,
+            &authority.pubkey(),
+            0,
+            expires_at,
+        );
+        process(
+            &mut self.context,
+            &[
+                claim,
+                client::init_user_sol(
+                    &audius_data::id(),
+                    &self.admin.pubkey(),
+                    &user,
+                    authority.pubkey(),
+                    expires_at,
+                ),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+        TestUser {
+            handle_seed,
+            eth_key,
+            authority,
+        }
+    }
+
+    /// Expiry of a claim signed now, CLAIM_LIFETIME from the current clock
+    pub async fn claim_expiry(&mut self) -> i64 {
+        unix_timestamp(&mut self.context).await + CLAIM_LIFETIME
+    }
+
+    /// Secp256k1 instruction carrying a claim signed by `eth_key` that expires at `expires_at`
+    pub fn claim_instruction(
+        &self,
+        eth_key: &SecretKey,
+        kind: ClaimKind,
+        user: &Pubkey,
+        authority: &Pubkey,
+        authority_epoch: u32,
+        expires_at: i64,
+    ) -> Instruction {
+        let claim = ClaimMessage {
+            program_id: audius_data::id(),
+            admin: self.admin.pubkey(),
+            user: *user,
+            authority: *authority,
+            authority_epoch,
+            expires_at,
+        };
+        new_secp256k1_instruction(eth_key, &claim.to_bytes(kind))
+    }
+
+    /// Add a delegate with its AuthorityDelegationStatus, signed by the user's authority
+    pub async fn add_delegate(
+        &mut self,
+        user: &TestUser,
+        permissions: u16,
+        expires_at: Option<i64>,
+    ) -> Keypair {
+        let delegate = Keypair::new();
+        let context = self.user_context(user, &user.authority, false);
+        process(
+            &mut self.context,
+            &[
+                client::init_authority_delegation_status(
+                    &audius_data::id(),
+                    &self.admin.pubkey(),
+                    &delegate.pubkey(),
+                    &context.payer,
+                    "delegate".to_string(),
+                ),
+                client::add_user_authority_delegate(
+                    &audius_data::id(),
+                    &context,
+                    delegate.pubkey(),
+                    permissions,
+                    expires_at,
+                ),
+            ],
+            &[&delegate, &user.authority],
+        )
+        .await
+        .unwrap();
+        delegate
+    }
+}
+
+/// Start the program and initialize an admin with the content nodes of REPLICA_SET
+pub async fn setup() -> TestAdmin {
+    let mut context = program_test().start_with_context().await;
+    let admin = Keypair::new();
+    let authority = Keypair::new();
+    let verifier = Keypair::new();
+    let payer = context.payer.pubkey();
+
+    process(
+        &mut context,
+        &[client::init_admin(
+            &audius_data::id(),
+            &admin.pubkey(),
+            &payer,
+            authority.pubkey(),
+            verifier.pubkey(),
+        )],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let content_node_authorities: Vec<Keypair> =
+        REPLICA_SET.iter().map(|_| Keypair::new()).collect();
+    let instructions: Vec<Instruction> = REPLICA_SET
+        .iter()
+        .zip(&content_node_authorities)
+        .map(|(sp_id, content_node_authority)| {
+            client::create_content_node(
+                &audius_data::id(),
+                &admin.pubkey(),
+                &authority.pubkey(),
+                &payer,
+                *sp_id,
+                content_node_authority.pubkey(),
+                [*sp_id as u8; 20],
+            )
+        })
+        .collect();
+    process(&mut context, &instructions, &[&authority])
+        .await
+        .unwrap();
+
+    TestAdmin {
+        context,
+        admin,
+        authority,
+        verifier,
+        content_node_authorities,
+    }
+}
+
+/// Sign and process a transaction paid for by the context payer
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .map_err(|err| err.unwrap())
+}
+
+/// Move to a new blockhash, required to resubmit a transaction identical to one already processed
+pub async fn refresh_blockhash(context: &mut ProgramTestContext) {
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+}
+
+/// Assert that a transaction failed with a program or anchor error code
+pub fn assert_error(result: Result<(), TransactionError>, error: impl Into<u32>) {
+    let code = error.into();
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(actual))) => {
+            assert_eq!(actual, code)
+        }
+        other => panic!("expected custom error {}, got {:?}", code, other),
+    }
+}
+
+/// Fetch and deserialize a program account, None if the account does not exist
+pub async fn get_account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+) -> Option<T> {
+    context
+        .banks_client
+        .get_account(*address)
+        .await
+        .expect("account not found")
+        .map(|account| T::try_deserialize(&mut &account.data[..]).unwrap())
+}
+
+/// Fetch the lamports held by an account, 0 if it does not exist
+pub async fn balance(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*address).await.unwrap()
+}
+
+pub async fn unix_timestamp(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+/// Move the test clock to `unix_timestamp`
+pub async fn set_unix_timestamp(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+/// Ethereum key of a test user
+pub fn eth_key(byte: u8) -> SecretKey {
+    SecretKey::parse(&[byte; 32]).unwrap()
+}
+
+pub fn eth_address(eth_key: &SecretKey) -> [u8; 20] {
+    construct_eth_pubkey(&PublicKey::from_secret_key(eth_key))
+}
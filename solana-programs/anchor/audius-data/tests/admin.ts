@@ -0,0 +1,321 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import chai, { expect } from "chai";
+import chaiAsPromised from "chai-as-promised";
+import {
+  initAdmin,
+  updateAdmin,
+  proposeAdminAuthority,
+  acceptAdminAuthority,
+  cancelAdminAuthority,
+  proposeAdminVerifier,
+  acceptAdminVerifier,
+  cancelAdminVerifier,
+  initAdminMultisig,
+  updateAdminMultisig,
+} from "../lib/lib";
+import { AudiusData } from "../target/types/audius_data";
+
+const { PublicKey } = anchor.web3;
+const DefaultPubkey = new PublicKey("11111111111111111111111111111111");
+
+chai.use(chaiAsPromised);
+
+describe("admin", function () {
+  const provider = anchor.Provider.local("http://localhost:8899", {
+    preflightCommitment: "confirmed",
+    commitment: "confirmed",
+  });
+
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.Provider.env());
+
+  const program = anchor.workspace.AudiusData as Program<AudiusData>;
+
+  let adminKeypair = anchor.web3.Keypair.generate();
+  const adminStorageKeypair = anchor.web3.Keypair.generate();
+  const verifierKeypair = anchor.web3.Keypair.generate();
+
+  it("admin - Initializing admin account!", async function () {
+    await initAdmin({
+      provider,
+      program,
+      adminKeypair,
+      adminStorageKeypair,
+      verifierKeypair,
+    });
+
+    const adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.pendingAuthority.toString()).to.equal(
+      DefaultPubkey.toString()
+    );
+    expect(adminAccount.pendingVerifier.toString()).to.equal(
+      DefaultPubkey.toString()
+    );
+  });
+
+  it("Rotates the admin authority", async function () {
+    const newAdminKeypair = anchor.web3.Keypair.generate();
+
+    await proposeAdminAuthority({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+      pendingPublicKey: newAdminKeypair.publicKey,
+    });
+
+    let adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.authority.toString()).to.equal(
+      adminKeypair.publicKey.toString()
+    );
+    expect(adminAccount.pendingAuthority.toString()).to.equal(
+      newAdminKeypair.publicKey.toString()
+    );
+
+    // Only the pending authority may accept
+    await expect(
+      acceptAdminAuthority({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        pendingKeypair: anchor.web3.Keypair.generate(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await acceptAdminAuthority({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      pendingKeypair: newAdminKeypair,
+    });
+
+    adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.authority.toString()).to.equal(
+      newAdminKeypair.publicKey.toString()
+    );
+    expect(adminAccount.pendingAuthority.toString()).to.equal(
+      DefaultPubkey.toString()
+    );
+
+    // The previous authority can no longer update the admin account
+    await expect(
+      updateAdmin({
+        program,
+        isWriteEnabled: false,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        adminAuthorityKeypair: adminKeypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    adminKeypair = newAdminKeypair;
+  });
+
+  it("Cancels a proposed admin authority", async function () {
+    const newAdminKeypair = anchor.web3.Keypair.generate();
+
+    await proposeAdminAuthority({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+      pendingPublicKey: newAdminKeypair.publicKey,
+    });
+    await cancelAdminAuthority({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+
+    await expect(
+      acceptAdminAuthority({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        pendingKeypair: newAdminKeypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+  });
+
+  it("Rotates the verifier", async function () {
+    const newVerifierKeypair = anchor.web3.Keypair.generate();
+
+    // Only the admin authority may propose a verifier
+    await expect(
+      proposeAdminVerifier({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        adminAuthorityKeypair: verifierKeypair,
+        pendingPublicKey: newVerifierKeypair.publicKey,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await proposeAdminVerifier({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+      pendingPublicKey: newVerifierKeypair.publicKey,
+    });
+    await acceptAdminVerifier({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      pendingKeypair: newVerifierKeypair,
+    });
+
+    const adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.verifier.toString()).to.equal(
+      newVerifierKeypair.publicKey.toString()
+    );
+    expect(adminAccount.pendingVerifier.toString()).to.equal(
+      DefaultPubkey.toString()
+    );
+  });
+
+  it("Cancels a proposed verifier", async function () {
+    const newVerifierKeypair = anchor.web3.Keypair.generate();
+
+    await proposeAdminVerifier({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+      pendingPublicKey: newVerifierKeypair.publicKey,
+    });
+    await cancelAdminVerifier({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+
+    await expect(
+      acceptAdminVerifier({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        pendingKeypair: newVerifierKeypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+  });
+
+  it("Hands the admin authority to a 2-of-3 multisig", async function () {
+    const [signer1, signer2, signer3] = [0, 1, 2].map(() =>
+      anchor.web3.Keypair.generate()
+    );
+    const { adminMultisig } = await initAdminMultisig({
+      provider,
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+      signers: [signer1, signer2, signer3].map((signer) => signer.publicKey),
+      threshold: 2,
+    });
+    await proposeAdminAuthority({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+      pendingPublicKey: adminMultisig,
+    });
+
+    // A single multisig signer can not accept the authority
+    await expect(
+      acceptAdminAuthority({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        pendingKeypair: signer1,
+        multisigSigners: { adminMultisig, signers: [] },
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await acceptAdminAuthority({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      pendingKeypair: signer1,
+      multisigSigners: { adminMultisig, signers: [signer3] },
+    });
+    const adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.authority.toString()).to.equal(
+      adminMultisig.toString()
+    );
+
+    // Privileged instructions need the threshold of multisig signatures
+    for (const adminAuthorityKeypair of [adminKeypair, signer2]) {
+      await expect(
+        updateAdmin({
+          program,
+          isWriteEnabled: true,
+          adminStorageAccount: adminStorageKeypair.publicKey,
+          adminAuthorityKeypair,
+          multisigSigners: { adminMultisig, signers: [] },
+        })
+      )
+        .to.eventually.be.rejected.and.property("msg")
+        .to.include("You are not authorized to perform this action.");
+    }
+    await updateAdmin({
+      program,
+      isWriteEnabled: true,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: signer2,
+      multisigSigners: { adminMultisig, signers: [signer3] },
+    });
+
+    // Changing the signer set also needs the threshold of current signers
+    const signer4 = anchor.web3.Keypair.generate();
+    const newSigners = [signer2, signer3, signer4].map(
+      (signer) => signer.publicKey
+    );
+    await expect(
+      updateAdminMultisig({
+        program,
+        adminMultisig,
+        signerKeypairs: [signer1],
+        signers: newSigners,
+        threshold: 2,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+    await updateAdminMultisig({
+      program,
+      adminMultisig,
+      signerKeypairs: [signer1, signer2],
+      signers: newSigners,
+      threshold: 2,
+    });
+
+    // Removed signers no longer count towards the threshold
+    await expect(
+      updateAdmin({
+        program,
+        isWriteEnabled: true,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        adminAuthorityKeypair: signer1,
+        multisigSigners: { adminMultisig, signers: [signer2] },
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+    await updateAdmin({
+      program,
+      isWriteEnabled: true,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: signer4,
+      multisigSigners: { adminMultisig, signers: [signer2] },
+    });
+  });
+});
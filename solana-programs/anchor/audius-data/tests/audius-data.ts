@@ -7,13 +7,30 @@ import {
   updateUser,
   updateAdmin,
   updateIsVerified,
+  migrateAccount,
+  changeUserHandle,
   getKeypairFromSecretKey,
+  findEntityAddress,
+  EntityTypesEnumValues,
+  DelegatePermissions,
+  closeExpiredUserAuthorityDelegate,
+  revokeAuthorityDelegation,
+  reinstateAuthorityDelegation,
+  initUserSolPubkey,
+  recoverUser,
+  deactivateUser,
+  closeUser,
 } from "../lib/lib";
 import {
   getTransactionWithData,
   findDerivedPair,
   randomCID,
   randomId,
+  signBytes,
+  SystemSysVarProgramKey,
+  getClaimMessage,
+  claimExpiry,
+  RECOVERY_MESSAGE_PREFIX,
 } from "../lib/utils";
 import { AudiusData } from "../target/types/audius_data";
 import {
@@ -21,6 +38,7 @@ import {
   confirmLogInTransaction,
   initTestConstants,
   pollAccountBalance,
+  waitForEvent,
   testCreateUser,
   testInitUser,
   testInitUserSolPubkey,
@@ -28,8 +46,10 @@ import {
   testUpdateTrack,
   testCreateUserDelegate,
   createSolanaContentNode,
+  createSolanaUser,
 } from "./test-helpers";
-const { PublicKey, SystemProgram } = anchor.web3;
+const { PublicKey, SystemProgram, Secp256k1Program, Transaction } =
+  anchor.web3;
 
 chai.use(chaiAsPromised);
 
@@ -54,14 +74,11 @@ describe("audius-data", function () {
         contentNodes["2"].spId.toNumber(),
         contentNodes["3"].spId.toNumber(),
       ],
-      replicaSetBumps: [
-        contentNodes["1"].seedBump.bump,
-        contentNodes["2"].seedBump.bump,
-        contentNodes["3"].seedBump.bump,
+      contentNodes: [
+        contentNodes["1"].pda,
+        contentNodes["2"].pda,
+        contentNodes["3"].pda,
       ],
-      cn1: contentNodes["1"].pda,
-      cn2: contentNodes["2"].pda,
-      cn3: contentNodes["3"].pda,
     };
   };
 
@@ -194,17 +211,13 @@ describe("audius-data", function () {
       keypairFromSecretKey.secretKey.toString()
     );
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = keypairFromSecretKey.publicKey.toBytes();
-
     await testInitUserSolPubkey({
       provider,
       program,
-      message,
       ethPrivateKey: ethAccount.privateKey,
       newUserPublicKey: keypairFromSecretKey.publicKey,
       newUserAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
     });
   });
 
@@ -239,7 +252,7 @@ describe("audius-data", function () {
     const newUserKeypair = anchor.web3.Keypair.generate();
 
     // Generate signed SECP instruction
-    // Message as the incoming public key
+    // Message as a public key instead of a claim message
     const message = anchor.web3.Keypair.generate().publicKey.toBytes();
 
     await expect(
@@ -250,11 +263,12 @@ describe("audius-data", function () {
         ethPrivateKey: ethAccount.privateKey,
         newUserPublicKey: newUserKeypair.publicKey,
         newUserAcctPDA,
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
       })
     ).to.be.rejectedWith(Error);
   });
 
-  it("Initializing + claiming + updating user!", async function () {
+  it("claiming user with a replayed or expired claim should fail", async function () {
     const { ethAccount, handleBytesArray, metadata } = initTestConstants();
 
     const {
@@ -281,20 +295,217 @@ describe("audius-data", function () {
       ...getURSMParams(),
     });
 
-    // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
+    const claim = {
+      programId: program.programId,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      userStorageAccount: newUserAcctPDA,
+      userSolPubkey: newUserKeypair.publicKey,
+      expiresAt: claimExpiry(),
+    };
+    const claimParams = {
+      provider,
+      program,
+      ethPrivateKey: ethAccount.privateKey,
+      newUserPublicKey: newUserKeypair.publicKey,
+      newUserAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+    };
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
+    // Claims signed for another program, admin or user are rejected
+    for (const replayedClaim of [
+      { ...claim, programId: anchor.web3.Keypair.generate().publicKey },
+      {
+        ...claim,
+        adminStorageAccount: anchor.web3.Keypair.generate().publicKey,
+      },
+      {
+        ...claim,
+        userStorageAccount: anchor.web3.Keypair.generate().publicKey,
+      },
+    ]) {
+      await expect(
+        testInitUserSolPubkey({
+          ...claimParams,
+          expiresAt: claim.expiresAt,
+          message: getClaimMessage(replayedClaim),
+        })
+      )
+        .to.eventually.be.rejected.and.property("logs")
+        .to.satisfy((logs: string[]) =>
+          logs.some((log) =>
+            log.includes("You are not authorized to perform this action.")
+          )
+        );
+    }
+
+    // Claims signed for another expiry than the one submitted are rejected
+    await expect(
+      testInitUserSolPubkey({
+        ...claimParams,
+        expiresAt: claim.expiresAt,
+        message: getClaimMessage({ ...claim, expiresAt: claimExpiry(60) }),
+      })
+    )
+      .to.eventually.be.rejected.and.property("logs")
+      .to.satisfy((logs: string[]) =>
+        logs.some((log) =>
+          log.includes("You are not authorized to perform this action.")
+        )
+      );
+
+    const expiredClaim = { ...claim, expiresAt: claimExpiry(-60) };
+    await expect(
+      testInitUserSolPubkey({
+        ...claimParams,
+        expiresAt: expiredClaim.expiresAt,
+        message: getClaimMessage(expiredClaim),
+      })
+    )
+      .to.eventually.be.rejected.and.property("logs")
+      .to.satisfy((logs: string[]) =>
+        logs.some((log) => log.includes("This signed claim has expired."))
+      );
 
     await testInitUserSolPubkey({
+      ...claimParams,
+      expiresAt: claim.expiresAt,
+      message: getClaimMessage(claim),
+    });
+  });
+
+  it("claiming user with crafted secp offsets should fail", async function () {
+    const { ethAccount, handleBytesArray, metadata } = initTestConstants();
+
+    const {
+      baseAuthorityAccount,
+      bumpSeed,
+      derivedAddress: newUserAcctPDA,
+    } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from(handleBytesArray)
+    );
+
+    await testInitUser({
       provider,
       program,
+      baseAuthorityAccount,
+      ethAddress: ethAccount.address,
+      handleBytesArray,
+      bumpSeed,
+      metadata,
+      userStorageAccount: newUserAcctPDA,
+      adminStorageKeypair,
+      adminKeypair,
+      ...getURSMParams(),
+    });
+
+    const newUserKeypair = anchor.web3.Keypair.generate();
+    const expiresAt = claimExpiry();
+    const message = getClaimMessage({
+      programId: program.programId,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      userStorageAccount: newUserAcctPDA,
+      userSolPubkey: newUserKeypair.publicKey,
+      expiresAt,
+    });
+    const { signature, recoveryId } = signBytes(
       message,
+      ethAccount.privateKey
+    );
+    const ethAddress = Buffer.from(
+      anchor.utils.bytes.hex.decode(ethAccount.address)
+    );
+
+    // Secp instruction data verified by the secp256k1 program, laid out with `padding` bytes
+    // between the offsets structs and the eth address, signature and message
+    const craftSecpInstruction = (signatureCount: number, padding: number) => {
+      const dataStart = 1 + 11 * signatureCount + padding;
+      const offsets = Buffer.alloc(11);
+      offsets.writeUInt16LE(dataStart + 20, 0);
+      offsets.writeUInt8(0, 2);
+      offsets.writeUInt16LE(dataStart, 3);
+      offsets.writeUInt8(0, 5);
+      offsets.writeUInt16LE(dataStart + 85, 6);
+      offsets.writeUInt16LE(message.length, 8);
+      offsets.writeUInt8(0, 10);
+      return new anchor.web3.TransactionInstruction({
+        keys: [],
+        programId: Secp256k1Program.programId,
+        data: Buffer.concat([
+          Buffer.from([signatureCount]),
+          ...Array(signatureCount).fill(offsets),
+          Buffer.alloc(padding),
+          ethAddress,
+          signature,
+          Buffer.from([recoveryId]),
+          Buffer.from(message),
+        ]),
+      });
+    };
+
+    // Both layouts pass the secp256k1 program, but not the fixed layout read by the program
+    for (const secpInstruction of [
+      craftSecpInstruction(1, 4),
+      craftSecpInstruction(2, 0),
+    ]) {
+      const tx = new Transaction();
+      tx.add(secpInstruction);
+      tx.add(
+        program.instruction.initUserSol(newUserKeypair.publicKey, expiresAt, {
+          accounts: {
+            user: newUserAcctPDA,
+            audiusAdmin: adminStorageKeypair.publicKey,
+            sysvarProgram: SystemSysVarProgramKey,
+          },
+        })
+      );
+      await expect(provider.send(tx))
+        .to.eventually.be.rejected.and.property("logs")
+        .to.satisfy((logs: string[]) =>
+          logs.some((log) => log.includes("Signature verification failed."))
+        );
+    }
+  });
+
+  it("Initializing + claiming + updating user!", async function () {
+    const { ethAccount, handleBytesArray, metadata } = initTestConstants();
+
+    const {
+      baseAuthorityAccount,
+      bumpSeed,
+      derivedAddress: newUserAcctPDA,
+    } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from(handleBytesArray)
+    );
+
+    await testInitUser({
+      provider,
+      program,
+      baseAuthorityAccount,
+      ethAddress: ethAccount.address,
+      handleBytesArray,
+      bumpSeed,
+      metadata,
+      userStorageAccount: newUserAcctPDA,
+      adminStorageKeypair,
+      adminKeypair,
+      ...getURSMParams(),
+    });
+
+    // New sol key that will be used to permission user updates
+    const newUserKeypair = anchor.web3.Keypair.generate();
+
+    await testInitUserSolPubkey({
+      provider,
+      program,
       ethPrivateKey: ethAccount.privateKey,
       newUserPublicKey: newUserKeypair.publicKey,
       newUserAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
     });
 
     const updatedCID = randomCID();
@@ -317,6 +528,23 @@ describe("audius-data", function () {
     expect(accountPubKeys[0]).to.equal(newUserAcctPDA.toString());
     expect(accountPubKeys[1]).to.equal(newUserKeypair.publicKey.toString());
     expect(accountPubKeys[2]).to.equal(SystemProgram.programId.toString());
+
+    // Metadata that is not a CID is rejected
+    const invalidCIDs = ["", "not a cid", `${updatedCID.slice(0, -1)}0`];
+    for (const metadata of invalidCIDs) {
+      await expect(
+        updateUser({
+          program,
+          metadata,
+          userStorageAccount: newUserAcctPDA,
+          userAuthorityKeypair: newUserKeypair,
+          userAuthorityDelegate: SystemProgram.programId,
+          authorityDelegationStatusAccount: SystemProgram.programId,
+        })
+      )
+        .to.eventually.be.rejected.and.property("msg")
+        .to.include("The metadata is not a valid CID or is too long.");
+    }
   });
 
   it("Initializing + claiming user, creating + updating track", async function () {
@@ -349,17 +577,13 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testInitUserSolPubkey({
       provider,
       program,
-      message,
       ethPrivateKey: ethAccount.privateKey,
       newUserPublicKey: newUserKeypair.publicKey,
       newUserAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
     });
 
     const trackMetadata = randomCID();
@@ -445,15 +669,10 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await expect(
       testCreateUser({
         provider,
         program,
-        message,
         ethAccount,
         baseAuthorityAccount,
         handleBytesArray,
@@ -541,14 +760,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       ethAccount,
       baseAuthorityAccount,
       handleBytesArray,
@@ -565,7 +779,6 @@ describe("audius-data", function () {
       testCreateUser({
         provider,
         program,
-        message,
         ethAccount,
         baseAuthorityAccount,
         handleBytesArray,
@@ -690,21 +903,39 @@ describe("audius-data", function () {
         userDelegate.authorityDelegationStatusPDA,
     });
 
-    // revoke authority delegation
-    const revokeAuthorityDelegationArgs = {
-      accounts: {
-        delegateAuthority: userDelegate.userAuthorityDelegateKeypair.publicKey,
-        authorityDelegationStatusPda: userDelegate.authorityDelegationStatusPDA,
-        payer: provider.wallet.publicKey,
-        systemProgram: SystemProgram.programId,
-      },
-      signers: [userDelegate.userAuthorityDelegateKeypair],
+    const statusArgs = {
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      delegateAuthority: userDelegate.userAuthorityDelegateKeypair.publicKey,
+      authorityDelegationStatusAccount:
+        userDelegate.authorityDelegationStatusPDA,
+      authorityDelegationStatusBump: userDelegate.authorityDelegationStatusBump,
     };
 
-    await program.rpc.revokeAuthorityDelegation(
-      userDelegate.authorityDelegationStatusBump,
-      revokeAuthorityDelegationArgs
+    // Only the delegate authority or the admin authority may revoke
+    await expect(
+      revokeAuthorityDelegation({
+        ...statusArgs,
+        authorityKeypair: anchor.web3.Keypair.generate(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    // revoke authority delegation
+    await revokeAuthorityDelegation({
+      ...statusArgs,
+      authorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+    });
+
+    let status = await program.account.authorityDelegationStatus.fetch(
+      userDelegate.authorityDelegationStatusPDA
+    );
+    expect(status.isRevoked).to.equal(true);
+    expect(status.revokedBy.toString()).to.equal(
+      userDelegate.userAuthorityDelegateKeypair.publicKey.toString()
     );
+    expect(status.revokedAt.toNumber()).to.be.greaterThan(0);
 
     // Confirm revoked delegation cannot update user
     await expect(
@@ -720,6 +951,58 @@ describe("audius-data", function () {
     )
       .to.eventually.be.rejected.and.property("msg")
       .to.include(`This authority's delegation status is revoked.`);
+
+    // Only the delegate authority or the admin authority may reinstate
+    await expect(
+      reinstateAuthorityDelegation({
+        ...statusArgs,
+        authorityKeypair: anchor.web3.Keypair.generate(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await reinstateAuthorityDelegation({
+      ...statusArgs,
+      authorityKeypair: adminKeypair,
+    });
+    status = await program.account.authorityDelegationStatus.fetch(
+      userDelegate.authorityDelegationStatusPDA
+    );
+    expect(status.isRevoked).to.equal(false);
+
+    await updateUser({
+      program,
+      metadata: randomCID(),
+      userStorageAccount: userDelegate.userAccountPDA,
+      userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+      userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+      authorityDelegationStatusAccount:
+        userDelegate.authorityDelegationStatusPDA,
+    });
+
+    // The admin authority may also revoke, and is recorded as the revoker
+    await revokeAuthorityDelegation({
+      ...statusArgs,
+      authorityKeypair: adminKeypair,
+    });
+    status = await program.account.authorityDelegationStatus.fetch(
+      userDelegate.authorityDelegationStatusPDA
+    );
+    expect(status.isRevoked).to.equal(true);
+    expect(status.revokedBy.toString()).to.equal(
+      adminKeypair.publicKey.toString()
+    );
+
+    // A delegation revoked by the admin authority may only be reinstated by it
+    await expect(
+      reinstateAuthorityDelegation({
+        ...statusArgs,
+        authorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
   });
 
   it("delegate adds/removes another delegate", async function () {
@@ -770,6 +1053,8 @@ describe("audius-data", function () {
       firstUserDelegate.userHandleBytesArray,
       firstUserDelegate.userBumpSeed,
       secondUserDelegate.userAuthorityDelegateKeypair.publicKey, // seed for userAuthorityDelegatePda
+      DelegatePermissions.all,
+      null, // expiresAt
       addUserAuthorityDelegateArgs
     );
 
@@ -798,65 +1083,501 @@ describe("audius-data", function () {
     );
   });
 
-  it("creating initialized user should fail", async function () {
-    const { ethAccount, handleBytesArray, metadata, userId } =
-      initTestConstants();
-
-    const {
-      baseAuthorityAccount,
-      bumpSeed,
-      derivedAddress: newUserAcctPDA,
-    } = await findDerivedPair(
-      program.programId,
-      adminStorageKeypair.publicKey,
-      Buffer.from(handleBytesArray)
+  it("emits events with the validated instruction inputs", async function () {
+    const delegateAddedEvent = waitForEvent(
+      program,
+      "UserAuthorityDelegateAdded"
     );
-
-    await testInitUser({
+    const userDelegate = await testCreateUserDelegate({
+      adminKeypair,
+      adminStorageKeypair,
+      program,
       provider,
+      permissions: DelegatePermissions.updateUser,
+    });
+    const delegateAdded = await delegateAddedEvent;
+    expect(delegateAdded.user.toString()).to.equal(
+      userDelegate.userAccountPDA.toString()
+    );
+    expect(delegateAdded.delegateAuthority.toString()).to.equal(
+      userDelegate.userAuthorityDelegateKeypair.publicKey.toString()
+    );
+    expect(delegateAdded.permissions).to.equal(DelegatePermissions.updateUser);
+    expect(delegateAdded.expiresAt).to.be.null;
+
+    const metadata = randomCID();
+    const userUpdatedEvent = waitForEvent(program, "UserUpdated");
+    await updateUser({
       program,
-      baseAuthorityAccount,
-      ethAddress: ethAccount.address,
-      handleBytesArray,
-      bumpSeed,
       metadata,
-      userStorageAccount: newUserAcctPDA,
-      adminStorageKeypair,
-      adminKeypair,
-      ...getURSMParams(),
+      userStorageAccount: userDelegate.userAccountPDA,
+      userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+      userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+      authorityDelegationStatusAccount:
+        userDelegate.authorityDelegationStatusPDA,
     });
+    const userUpdated = await userUpdatedEvent;
+    expect(userUpdated.user.toString()).to.equal(
+      userDelegate.userAccountPDA.toString()
+    );
+    expect(userUpdated.authority.toString()).to.equal(
+      userDelegate.userAuthorityDelegateKeypair.publicKey.toString()
+    );
+    expect(userUpdated.metadata).to.equal(metadata);
+  });
 
-    // New sol key that will be used to permission user updates
-    const newUserKeypair = anchor.web3.Keypair.generate();
+  it("delegate without the required permission should fail", async function () {
+    const userDelegate = await testCreateUserDelegate({
+      adminKeypair,
+      adminStorageKeypair,
+      program,
+      provider,
+      permissions:
+        DelegatePermissions.socialAction | DelegatePermissions.manageDelegates,
+    });
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
+    const acctState = await program.account.userAuthorityDelegate.fetch(
+      userDelegate.userAuthorityDelegatePDA
+    );
+    expect(acctState.permissions).to.equal(
+      DelegatePermissions.socialAction | DelegatePermissions.manageDelegates
+    );
 
     await expect(
-      testCreateUser({
-        provider,
+      updateUser({
         program,
-        message,
-        ethAccount,
-        baseAuthorityAccount,
-        handleBytesArray,
-        bumpSeed,
-        userId,
-        metadata,
-        newUserKeypair,
-        userStorageAccount: newUserAcctPDA,
-        adminStoragePublicKey: adminStorageKeypair.publicKey,
-        ...getURSMParams(),
+        metadata: randomCID(),
+        userStorageAccount: userDelegate.userAccountPDA,
+        userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+        userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+        authorityDelegationStatusAccount:
+          userDelegate.authorityDelegationStatusPDA,
       })
     )
-      .to.eventually.be.rejected.and.property("logs")
+      .to.eventually.be.rejected.and.property("msg")
       .to.include(
-        `Allocate: account Address { address: ${newUserAcctPDA.toString()}, base: None } already in use`
+        "This delegate does not have permission to perform this action."
       );
-  });
 
-  it("creating user with incorrect bump seed / pda should fail", async function () {
+    // A delegate may only grant permissions it holds itself
+    const newDelegateKeypair = anchor.web3.Keypair.generate();
+    const [newUserAuthorityDelegatePDA] = await PublicKey.findProgramAddress(
+      [
+        userDelegate.userAccountPDA.toBytes().slice(0, 32),
+        newDelegateKeypair.publicKey.toBytes().slice(0, 32),
+      ],
+      program.programId
+    );
+    const addUserAuthorityDelegateArgs = {
+      accounts: {
+        admin: adminStorageKeypair.publicKey,
+        user: userDelegate.userAccountPDA,
+        currentUserAuthorityDelegate: newUserAuthorityDelegatePDA,
+        signerUserAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+        authorityDelegationStatus: userDelegate.authorityDelegationStatusPDA,
+        authority: userDelegate.userAuthorityDelegateKeypair.publicKey,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      },
+      signers: [userDelegate.userAuthorityDelegateKeypair],
+    };
+    await expect(
+      program.rpc.addUserAuthorityDelegate(
+        userDelegate.baseAuthorityAccount,
+        userDelegate.userHandleBytesArray,
+        userDelegate.userBumpSeed,
+        newDelegateKeypair.publicKey,
+        DelegatePermissions.manageEntity,
+        null, // expiresAt
+        addUserAuthorityDelegateArgs
+      )
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include(
+        "This delegate does not have permission to perform this action."
+      );
+
+    await program.rpc.addUserAuthorityDelegate(
+      userDelegate.baseAuthorityAccount,
+      userDelegate.userHandleBytesArray,
+      userDelegate.userBumpSeed,
+      newDelegateKeypair.publicKey,
+      DelegatePermissions.socialAction,
+      null, // expiresAt
+      addUserAuthorityDelegateArgs
+    );
+    const newDelegateState = await program.account.userAuthorityDelegate.fetch(
+      newUserAuthorityDelegatePDA
+    );
+    expect(newDelegateState.permissions).to.equal(
+      DelegatePermissions.socialAction
+    );
+  });
+
+  it("recovering a user rotates its authority and invalidates delegates", async function () {
+    const userDelegate = await testCreateUserDelegate({
+      adminKeypair,
+      adminStorageKeypair,
+      program,
+      provider,
+    });
+    const claimParams = {
+      provider,
+      program,
+      ethPrivateKey: userDelegate.userEthAccount.privateKey,
+      userStorageAccount: userDelegate.userAccountPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+    };
+    const newAuthorityKeypair = anchor.web3.Keypair.generate();
+
+    // A claimed user can not be claimed again
+    await expect(
+      initUserSolPubkey({
+        ...claimParams,
+        userSolPubkey: newAuthorityKeypair.publicKey,
+      })
+    )
+      .to.eventually.be.rejected.and.property("logs")
+      .to.satisfy((logs: string[]) =>
+        logs.some((log) => log.includes("This user has already been claimed."))
+      );
+
+    // Recoveries are signed under their own prefix
+    const recovery = {
+      programId: program.programId,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      userStorageAccount: userDelegate.userAccountPDA,
+      userSolPubkey: newAuthorityKeypair.publicKey,
+      authorityEpoch: 0,
+      expiresAt: claimExpiry(),
+    };
+    await expect(
+      recoverUser({
+        ...claimParams,
+        userSolPubkey: newAuthorityKeypair.publicKey,
+        authorityEpoch: 0,
+        expiresAt: recovery.expiresAt,
+        message: getClaimMessage(recovery),
+      })
+    )
+      .to.eventually.be.rejected.and.property("logs")
+      .to.satisfy((logs: string[]) =>
+        logs.some((log) =>
+          log.includes("You are not authorized to perform this action.")
+        )
+      );
+
+    const recoveryMessage = getClaimMessage({
+      ...recovery,
+      prefix: RECOVERY_MESSAGE_PREFIX,
+    });
+    await recoverUser({
+      ...claimParams,
+      userSolPubkey: newAuthorityKeypair.publicKey,
+      authorityEpoch: 0,
+      expiresAt: recovery.expiresAt,
+      message: recoveryMessage,
+    });
+    const userAccount = await program.account.user.fetch(
+      userDelegate.userAccountPDA
+    );
+    expect(userAccount.authority.toString()).to.equal(
+      newAuthorityKeypair.publicKey.toString()
+    );
+    expect(userAccount.authorityEpoch).to.equal(1);
+
+    // The recovery can not be replayed in the new epoch
+    await expect(
+      recoverUser({
+        ...claimParams,
+        userSolPubkey: newAuthorityKeypair.publicKey,
+        authorityEpoch: 0,
+        expiresAt: recovery.expiresAt,
+        message: recoveryMessage,
+      })
+    )
+      .to.eventually.be.rejected.and.property("logs")
+      .to.satisfy((logs: string[]) =>
+        logs.some((log) =>
+          log.includes("You are not authorized to perform this action.")
+        )
+      );
+
+    // Delegates added before the recovery can no longer act for the user
+    await expect(
+      updateUser({
+        program,
+        metadata: randomCID(),
+        userStorageAccount: userDelegate.userAccountPDA,
+        userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+        userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+        authorityDelegationStatusAccount:
+          userDelegate.authorityDelegationStatusPDA,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include(
+        "This delegate was invalidated by a recovery of the user's account."
+      );
+
+    // Neither can the previous authority
+    await expect(
+      updateUser({
+        program,
+        metadata: randomCID(),
+        userStorageAccount: userDelegate.userAccountPDA,
+        userAuthorityKeypair: userDelegate.userKeypair,
+        userAuthorityDelegate: SystemProgram.programId,
+        authorityDelegationStatusAccount: SystemProgram.programId,
+      })
+    ).to.eventually.be.rejected;
+
+    await updateUser({
+      program,
+      metadata: randomCID(),
+      userStorageAccount: userDelegate.userAccountPDA,
+      userAuthorityKeypair: newAuthorityKeypair,
+      userAuthorityDelegate: SystemProgram.programId,
+      authorityDelegationStatusAccount: SystemProgram.programId,
+    });
+  });
+
+  it("deactivating and closing a user", async function () {
+    const userDelegate = await testCreateUserDelegate({
+      adminKeypair,
+      adminStorageKeypair,
+      program,
+      provider,
+    });
+    const userStatusParams = {
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      baseAuthorityAccount: userDelegate.baseAuthorityAccount,
+      userStorageAccount: userDelegate.userAccountPDA,
+      handleBytesArray: userDelegate.userHandleBytesArray,
+      bumpSeed: userDelegate.userBumpSeed,
+    };
+
+    // Only deactivated users can be closed, and only by the user or admin
+    await expect(
+      closeUser({ ...userStatusParams, authorityKeypair: adminKeypair })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This user must be deactivated before it can be closed.");
+    await expect(
+      deactivateUser({
+        ...userStatusParams,
+        authorityKeypair: anchor.web3.Keypair.generate(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await deactivateUser({
+      ...userStatusParams,
+      authorityKeypair: userDelegate.userKeypair,
+    });
+    const userAccount = await program.account.user.fetch(
+      userDelegate.userAccountPDA
+    );
+    expect(userAccount.deactivatedAt.toNumber()).to.be.greaterThan(0);
+
+    // Neither the user nor its delegates can act for a deactivated user
+    for (const [userAuthorityKeypair, userAuthorityDelegate, status] of [
+      [
+        userDelegate.userKeypair,
+        SystemProgram.programId,
+        SystemProgram.programId,
+      ],
+      [
+        userDelegate.userAuthorityDelegateKeypair,
+        userDelegate.userAuthorityDelegatePDA,
+        userDelegate.authorityDelegationStatusPDA,
+      ],
+    ] as const) {
+      await expect(
+        updateUser({
+          program,
+          metadata: randomCID(),
+          userStorageAccount: userDelegate.userAccountPDA,
+          userAuthorityKeypair,
+          userAuthorityDelegate,
+          authorityDelegationStatusAccount: status,
+        })
+      )
+        .to.eventually.be.rejected.and.property("msg")
+        .to.include("This user has been deactivated.");
+    }
+    await expect(
+      deactivateUser({ ...userStatusParams, authorityKeypair: adminKeypair })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This user has been deactivated.");
+
+    // The admin authority keeps a rent exempt balance after the refund
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(
+      0
+    );
+    await provider.send(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: adminKeypair.publicKey,
+          lamports,
+        })
+      )
+    );
+    await closeUser({ ...userStatusParams, authorityKeypair: adminKeypair });
+
+    // The handle stays taken by a tombstone, the closed user's delegates can no longer act
+    const tombstone = await program.account.userTombstone.fetch(
+      userDelegate.userAccountPDA
+    );
+    expect(tombstone.closedAt.toNumber()).to.be.greaterThan(0);
+    await expect(
+      updateUser({
+        program,
+        metadata: randomCID(),
+        userStorageAccount: userDelegate.userAccountPDA,
+        userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+        userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+        authorityDelegationStatusAccount:
+          userDelegate.authorityDelegationStatusPDA,
+      })
+    ).to.eventually.be.rejected;
+  });
+
+  it("expired delegate should be rejected and closable by anyone", async function () {
+    const getBlockTime = async () =>
+      provider.connection.getBlockTime(await provider.connection.getSlot());
+
+    await expect(
+      testCreateUserDelegate({
+        adminKeypair,
+        adminStorageKeypair,
+        program,
+        provider,
+        expiresAt: new anchor.BN((await getBlockTime()) - 10),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("The delegate expiry must be in the future");
+
+    const expiresAt = (await getBlockTime()) + 5;
+    const userDelegate = await testCreateUserDelegate({
+      adminKeypair,
+      adminStorageKeypair,
+      program,
+      provider,
+      expiresAt: new anchor.BN(expiresAt),
+    });
+
+    await expect(
+      closeExpiredUserAuthorityDelegate({
+        program,
+        userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+        payer: provider.wallet.publicKey,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This delegate authority has not expired.");
+
+    while ((await getBlockTime()) <= expiresAt) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+
+    await expect(
+      updateUser({
+        program,
+        metadata: randomCID(),
+        userStorageAccount: userDelegate.userAccountPDA,
+        userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+        userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+        authorityDelegationStatusAccount:
+          userDelegate.authorityDelegationStatusPDA,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This delegate authority has expired.");
+
+    // Rent is refunded to the account that paid for the delegate
+    const delegateRent = await provider.connection.getBalance(
+      userDelegate.userAuthorityDelegatePDA
+    );
+    const initialPayerBalance = await provider.connection.getBalance(
+      provider.wallet.publicKey
+    );
+    await closeExpiredUserAuthorityDelegate({
+      program,
+      userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
+      payer: provider.wallet.publicKey,
+    });
+    expect(
+      await provider.connection.getAccountInfo(
+        userDelegate.userAuthorityDelegatePDA
+      )
+    ).to.be.null;
+    const payerBalance = await provider.connection.getBalance(
+      provider.wallet.publicKey
+    );
+    expect(payerBalance).to.be.greaterThan(initialPayerBalance);
+    expect(payerBalance).to.be.at.most(initialPayerBalance + delegateRent);
+  });
+
+  it("creating initialized user should fail", async function () {
+    const { ethAccount, handleBytesArray, metadata, userId } =
+      initTestConstants();
+
+    const {
+      baseAuthorityAccount,
+      bumpSeed,
+      derivedAddress: newUserAcctPDA,
+    } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from(handleBytesArray)
+    );
+
+    await testInitUser({
+      provider,
+      program,
+      baseAuthorityAccount,
+      ethAddress: ethAccount.address,
+      handleBytesArray,
+      bumpSeed,
+      metadata,
+      userStorageAccount: newUserAcctPDA,
+      adminStorageKeypair,
+      adminKeypair,
+      ...getURSMParams(),
+    });
+
+    // New sol key that will be used to permission user updates
+    const newUserKeypair = anchor.web3.Keypair.generate();
+
+    await expect(
+      testCreateUser({
+        provider,
+        program,
+        ethAccount,
+        baseAuthorityAccount,
+        handleBytesArray,
+        bumpSeed,
+        userId,
+        metadata,
+        newUserKeypair,
+        userStorageAccount: newUserAcctPDA,
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
+        ...getURSMParams(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("logs")
+      .to.include(
+        `Allocate: account Address { address: ${newUserAcctPDA.toString()}, base: None } already in use`
+      );
+  });
+
+  it("creating user with incorrect bump seed / pda should fail", async function () {
     const { ethAccount, handleBytesArray, metadata } = initTestConstants();
 
     const { baseAuthorityAccount, bumpSeed } = await findDerivedPair(
@@ -868,10 +1589,6 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     const { handleBytesArray: incorrectHandleBytesArray, userId } =
       initTestConstants();
 
@@ -885,7 +1602,6 @@ describe("audius-data", function () {
       testCreateUser({
         provider,
         program,
-        message,
         ethAccount,
         baseAuthorityAccount,
         handleBytesArray,
@@ -921,14 +1637,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       ethAccount,
       baseAuthorityAccount,
       handleBytesArray,
@@ -940,6 +1651,9 @@ describe("audius-data", function () {
       adminStoragePublicKey: adminStorageKeypair.publicKey,
       ...getURSMParams(),
     });
+    let userAccount = await program.account.user.fetch(newUserAcctPDA);
+    expect(userAccount.isVerified).to.equal(false);
+
     const tx = await updateIsVerified({
       program,
       adminKeypair: adminStorageKeypair,
@@ -951,6 +1665,219 @@ describe("audius-data", function () {
     });
 
     await confirmLogInTransaction(provider, tx, "success");
+    userAccount = await program.account.user.fetch(newUserAcctPDA);
+    expect(userAccount.isVerified).to.equal(true);
+
+    // Only the admin verifier may update verification
+    await expect(
+      updateIsVerified({
+        program,
+        adminKeypair: adminStorageKeypair,
+        userStorageAccount: newUserAcctPDA,
+        verifierKeypair: newUserKeypair,
+        baseAuthorityAccount,
+        handleBytesArray,
+        bumpSeed,
+        isVerified: false,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await updateIsVerified({
+      program,
+      adminKeypair: adminStorageKeypair,
+      userStorageAccount: newUserAcctPDA,
+      verifierKeypair,
+      baseAuthorityAccount,
+      handleBytesArray,
+      bumpSeed,
+      isVerified: false,
+    });
+    userAccount = await program.account.user.fetch(newUserAcctPDA);
+    expect(userAccount.isVerified).to.equal(false);
+
+  });
+
+  it("migrating accounts already in the current layout is a no-op", async function () {
+    const { ethAccount, handleBytesArray, metadata, userId } =
+      initTestConstants();
+    const {
+      baseAuthorityAccount,
+      bumpSeed,
+      derivedAddress: userAcctPDA,
+    } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from(handleBytesArray)
+    );
+    const newUserKeypair = anchor.web3.Keypair.generate();
+
+    await testCreateUser({
+      provider,
+      program,
+      ethAccount,
+      baseAuthorityAccount,
+      handleBytesArray,
+      userId,
+      bumpSeed,
+      metadata,
+      newUserKeypair,
+      userStorageAccount: userAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      ...getURSMParams(),
+    });
+
+    for (const account of [adminStorageKeypair.publicKey, userAcctPDA]) {
+      const accountInfoBefore = await provider.connection.getAccountInfo(
+        account
+      );
+      await migrateAccount({ program, account });
+      const accountInfoAfter = await provider.connection.getAccountInfo(
+        account
+      );
+      expect(accountInfoAfter.data.equals(accountInfoBefore.data)).to.equal(
+        true
+      );
+      expect(accountInfoAfter.lamports).to.equal(accountInfoBefore.lamports);
+    }
+
+    const adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.version).to.equal(1);
+    const userAccount = await program.account.user.fetch(userAcctPDA);
+    expect(userAccount.version).to.equal(1);
+
+    // Accounts not owned by the program can not be migrated
+    await expect(
+      migrateAccount({ program, account: provider.wallet.publicKey })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+  });
+
+  it("changing a user's handle", async function () {
+    const { ethAccount, handleBytesArray, metadata, userId } =
+      initTestConstants();
+    const { handleBytesArray: newHandleBytesArray } = initTestConstants();
+
+    const {
+      baseAuthorityAccount,
+      bumpSeed,
+      derivedAddress: userAcctPDA,
+    } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from(handleBytesArray)
+    );
+    const { derivedAddress: newUserAcctPDA } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from(newHandleBytesArray)
+    );
+
+    const newUserKeypair = anchor.web3.Keypair.generate();
+
+    await testCreateUser({
+      provider,
+      program,
+      ethAccount,
+      baseAuthorityAccount,
+      handleBytesArray,
+      userId,
+      bumpSeed,
+      metadata,
+      newUserKeypair,
+      userStorageAccount: userAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      ...getURSMParams(),
+    });
+    const userAccount = await program.account.user.fetch(userAcctPDA);
+
+    // Only the user authority may change the handle
+    await expect(
+      changeUserHandle({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        baseAuthorityAccount,
+        userStorageAccount: userAcctPDA,
+        handleBytesArray,
+        bumpSeed,
+        newUserStorageAccount: newUserAcctPDA,
+        newHandleBytesArray,
+        userAuthorityKeypair: anchor.web3.Keypair.generate(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await changeUserHandle({
+      program,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      baseAuthorityAccount,
+      userStorageAccount: userAcctPDA,
+      handleBytesArray,
+      bumpSeed,
+      newUserStorageAccount: newUserAcctPDA,
+      newHandleBytesArray,
+      userAuthorityKeypair: newUserKeypair,
+    });
+
+    const newUserAccount = await program.account.user.fetch(newUserAcctPDA);
+    expect(newUserAccount.ethAddress).to.deep.equal(userAccount.ethAddress);
+    expect(newUserAccount.authority.toString()).to.equal(
+      userAccount.authority.toString()
+    );
+    expect(newUserAccount.replicaSet).to.deep.equal(userAccount.replicaSet);
+
+    const redirect = await program.account.userRedirect.fetch(userAcctPDA);
+    expect(redirect.user.toString()).to.equal(newUserAcctPDA.toString());
+
+    // Moving to a handle that is already taken should fail
+    const {
+      handleBytesArray: otherHandleBytesArray,
+      ethAccount: otherEthAccount,
+      metadata: otherMetadata,
+      userId: otherUserId,
+    } = initTestConstants();
+    const { bumpSeed: otherBumpSeed, derivedAddress: otherUserAcctPDA } =
+      await findDerivedPair(
+        program.programId,
+        adminStorageKeypair.publicKey,
+        Buffer.from(otherHandleBytesArray)
+      );
+    const otherUserKeypair = anchor.web3.Keypair.generate();
+    await testCreateUser({
+      provider,
+      program,
+      ethAccount: otherEthAccount,
+      baseAuthorityAccount,
+      handleBytesArray: otherHandleBytesArray,
+      userId: otherUserId,
+      bumpSeed: otherBumpSeed,
+      metadata: otherMetadata,
+      newUserKeypair: otherUserKeypair,
+      userStorageAccount: otherUserAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      ...getURSMParams(),
+    });
+
+    await expect(
+      changeUserHandle({
+        program,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        baseAuthorityAccount,
+        userStorageAccount: otherUserAcctPDA,
+        handleBytesArray: otherHandleBytesArray,
+        bumpSeed: otherBumpSeed,
+        newUserStorageAccount: newUserAcctPDA,
+        newHandleBytesArray,
+        userAuthorityKeypair: otherUserKeypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This handle is already taken.");
   });
 
   it("creating + deleting a track", async function () {
@@ -976,14 +1903,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       baseAuthorityAccount,
       ethAccount,
       handleBytesArray,
@@ -1029,6 +1951,122 @@ describe("audius-data", function () {
     });
   });
 
+  it("creating a track with an existing id should fail", async function () {
+    // disable admin writes
+    await updateAdmin({
+      program,
+      isWriteEnabled: false,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const trackArgs = {
+      provider,
+      program,
+      id: randomId(),
+      baseAuthorityAccount: user.authority,
+      handleBytesArray: user.handleBytesArray,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      bumpSeed: user.bumpSeed,
+      trackMetadata: randomCID(),
+      userAuthorityKeypair: user.keypair,
+      trackOwnerPDA: user.pda,
+      userAuthorityDelegateAccountPDA: SystemProgram.programId,
+      authorityDelegationStatusAccountPDA: SystemProgram.programId,
+    };
+    await testCreateTrack(trackArgs);
+
+    const [entityPDA] = await findEntityAddress({
+      programId: program.programId,
+      baseAuthorityAccount: user.authority,
+      entityType: EntityTypesEnumValues.track,
+      id: trackArgs.id,
+    });
+    const entity = await program.account.entity.fetch(entityPDA);
+    expect(entity.owner.toString(), "entity owner").to.equal(
+      user.pda.toString()
+    );
+    expect(entity.payer.toString(), "entity payer").to.equal(
+      provider.wallet.publicKey.toString()
+    );
+
+    await expect(testCreateTrack(trackArgs))
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("An entity with this id already exists.");
+  });
+
+  it("updating or deleting another user's track should fail", async function () {
+    // disable admin writes
+    await updateAdmin({
+      program,
+      isWriteEnabled: false,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+    const owner = await createSolanaUser(
+      program,
+      provider,
+      adminStorageKeypair
+    );
+    const otherUser = await createSolanaUser(
+      program,
+      provider,
+      adminStorageKeypair
+    );
+    const trackID = randomId();
+    await testCreateTrack({
+      provider,
+      program,
+      id: trackID,
+      baseAuthorityAccount: owner.authority,
+      handleBytesArray: owner.handleBytesArray,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      bumpSeed: owner.bumpSeed,
+      trackMetadata: randomCID(),
+      userAuthorityKeypair: owner.keypair,
+      trackOwnerPDA: owner.pda,
+      userAuthorityDelegateAccountPDA: SystemProgram.programId,
+      authorityDelegationStatusAccountPDA: SystemProgram.programId,
+    });
+
+    const otherUserArgs = {
+      provider,
+      program,
+      id: trackID,
+      baseAuthorityAccount: otherUser.authority,
+      handleBytesArray: otherUser.handleBytesArray,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      bumpSeed: otherUser.bumpSeed,
+      userAuthorityKeypair: otherUser.keypair,
+      userAuthorityDelegateAccountPDA: SystemProgram.programId,
+      authorityDelegationStatusAccountPDA: SystemProgram.programId,
+    };
+    await expect(
+      testUpdateTrack({
+        ...otherUserArgs,
+        userStorageAccountPDA: otherUser.pda,
+        metadata: randomCID(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+    await expect(
+      testDeleteTrack({ ...otherUserArgs, trackOwnerPDA: otherUser.pda })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+    await expect(
+      testUpdateTrack({
+        ...otherUserArgs,
+        id: randomId(),
+        userStorageAccountPDA: otherUser.pda,
+        metadata: randomCID(),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("No entity with this id exists.");
+  });
+
   it("delegate creates a track (manage entity) + all validation errors", async function () {
     // create user and delegate
     const { ethAccount, handleBytesArray, metadata, userId } =
@@ -1054,14 +2092,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       ethAccount,
       baseAuthorityAccount,
       handleBytesArray,
@@ -1155,6 +2188,7 @@ describe("audius-data", function () {
     // Init AuthorityDelegationStatus for a new authority
     const initAuthorityDelegationStatusArgs = {
       accounts: {
+        admin: adminStorageKeypair.publicKey,
         delegateAuthority: userAuthorityDelegateKeypair.publicKey,
         authorityDelegationStatusPda: authorityDelegationStatusPDA,
         payer: provider.wallet.publicKey,
@@ -1174,6 +2208,8 @@ describe("audius-data", function () {
       handleBytesArray,
       userBumpSeed,
       userAuthorityDelegateKeypair.publicKey,
+      DelegatePermissions.all,
+      null, // expiresAt
       addUserAuthorityDelegateArgs
     );
 
@@ -1332,14 +2368,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       baseAuthorityAccount,
       ethAccount,
       handleBytesArray,
@@ -1,12 +1,27 @@
 import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
 import { expect, assert } from "chai";
-import { initAdmin, updateAdmin } from "../lib/lib";
-import { findDerivedPair, getTransactionWithData } from "../lib/utils";
+import {
+  EntitySocialActions,
+  EntityTypesEnumValues,
+  findSocialActionAddress,
+  initAdmin,
+  MAX_SOCIAL_ACTION_BATCH_SIZE,
+  updateAdmin,
+  UserActions,
+  writeSocialActions,
+} from "../lib/lib";
+import {
+  findDerivedPair,
+  findFollowAddress,
+  getTransactionWithData,
+} from "../lib/utils";
 import { AudiusData } from "../target/types/audius_data";
 import {
   createSolanaContentNode,
+  createSolanaUser,
   initTestConstants,
+  pollAccountBalance,
   testCreateUser,
   testCreateUserDelegate,
 } from "./test-helpers";
@@ -40,14 +55,11 @@ describe("follows", function () {
         contentNodes["2"].spId.toNumber(),
         contentNodes["3"].spId.toNumber(),
       ],
-      replicaSetBumps: [
-        contentNodes["1"].seedBump.bump,
-        contentNodes["2"].seedBump.bump,
-        contentNodes["3"].seedBump.bump,
+      contentNodes: [
+        contentNodes["1"].pda,
+        contentNodes["2"].pda,
+        contentNodes["3"].pda,
       ],
-      cn1: contentNodes["1"].pda,
-      cn2: contentNodes["2"].pda,
-      cn3: contentNodes["3"].pda,
     };
   };
 
@@ -143,11 +155,6 @@ describe("follows", function () {
       newUser1Key = anchor.web3.Keypair.generate();
       newUser2Key = anchor.web3.Keypair.generate();
 
-      // Generate signed SECP instruction
-      // Message as the incoming public key
-      const message1 = newUser1Key.publicKey.toBytes();
-      const message2 = newUser2Key.publicKey.toBytes();
-
       // disable admin writes
       await updateAdmin({
         program,
@@ -159,7 +166,6 @@ describe("follows", function () {
       await testCreateUser({
         provider,
         program,
-        message: message1,
         baseAuthorityAccount,
         ethAccount: constants1.ethAccount,
         handleBytesArray: handleBytesArray1,
@@ -175,7 +181,6 @@ describe("follows", function () {
       await testCreateUser({
         provider,
         program,
-        message: message2,
         baseAuthorityAccount,
         ethAccount: constants2.ethAccount,
         handleBytesArray: handleBytesArray2,
@@ -189,9 +194,13 @@ describe("follows", function () {
       });
     });
 
-    it("follow user", async function () {
-      // Submit a tx where user 1 follows user 2
-      const followArgs = {
+    const getFollowArgs = async () => {
+      const [followPDA] = await findFollowAddress(
+        program.programId,
+        userStorageAccount1,
+        userStorageAccount2
+      );
+      return {
         accounts: {
           audiusAdmin: adminStorageKeypair.publicKey,
           authority: newUser1Key.publicKey,
@@ -199,9 +208,25 @@ describe("follows", function () {
           followeeUserStorage: userStorageAccount2,
           userAuthorityDelegate: SystemProgram.programId,
           authorityDelegationStatus: SystemProgram.programId,
+          follow: followPDA,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         },
+        // Payer of the follow, refunded its rent on unfollow
+        remainingAccounts: [
+          {
+            pubkey: provider.wallet.publicKey,
+            isSigner: false,
+            isWritable: true,
+          },
+        ],
         signers: [newUser1Key],
       };
+    };
+
+    it("follow user", async function () {
+      // Submit a tx where user 1 follows user 2
+      const followArgs = await getFollowArgs();
 
       const followTx = await program.rpc.followUser(
         baseAuthorityAccount,
@@ -231,6 +256,43 @@ describe("follows", function () {
         adminStorageKeypair.publicKey.toString()
       );
       expect(accountPubKeys[5]).to.equal(newUser1Key.publicKey.toString());
+
+      // Confirm the follow is recorded on chain
+      const follow = await program.account.follow.fetch(
+        followArgs.accounts.follow
+      );
+      expect(follow.follower.toString()).to.equal(
+        userStorageAccount1.toString()
+      );
+      expect(follow.followee.toString()).to.equal(
+        userStorageAccount2.toString()
+      );
+      expect(follow.payer.toString()).to.equal(
+        provider.wallet.publicKey.toString()
+      );
+    });
+
+    it("follow user twice should fail", async function () {
+      const followArgs = await getFollowArgs();
+      await program.rpc.followUser(
+        baseAuthorityAccount,
+        UserActionEnumValues.followUser,
+        { seed: handleBytesArray1, bump: handle1DerivedInfo.bumpSeed },
+        { seed: handleBytesArray2, bump: handle2DerivedInfo.bumpSeed },
+        followArgs
+      );
+
+      await expect(
+        program.rpc.followUser(
+          baseAuthorityAccount,
+          UserActionEnumValues.followUser,
+          { seed: handleBytesArray1, bump: handle1DerivedInfo.bumpSeed },
+          { seed: handleBytesArray2, bump: handle2DerivedInfo.bumpSeed },
+          followArgs
+        )
+      )
+        .to.eventually.be.rejected.and.property("msg")
+        .to.include("This user is already followed.");
     });
 
     it("delegate follows user", async function () {
@@ -241,6 +303,12 @@ describe("follows", function () {
         provider,
       });
 
+      const [followPDA] = await findFollowAddress(
+        program.programId,
+        userDelegate.userAccountPDA,
+        userStorageAccount2
+      );
+
       // Submit a tx where user 1 follows user 2
       const followArgs = {
         accounts: {
@@ -250,6 +318,9 @@ describe("follows", function () {
           followeeUserStorage: userStorageAccount2,
           userAuthorityDelegate: userDelegate.userAuthorityDelegatePDA,
           authorityDelegationStatus: userDelegate.authorityDelegationStatusPDA,
+          follow: followPDA,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
         },
         signers: [userDelegate.userAuthorityDelegateKeypair],
       };
@@ -291,18 +362,16 @@ describe("follows", function () {
 
     it("unfollow user", async function () {
       // Submit a tx where user 1 follows user 2
-      const followArgs = {
-        accounts: {
-          audiusAdmin: adminStorageKeypair.publicKey,
-          payer: provider.wallet.publicKey,
-          authority: newUser1Key.publicKey,
-          followerUserStorage: userStorageAccount1,
-          followeeUserStorage: userStorageAccount2,
-          userAuthorityDelegate: SystemProgram.programId,
-          authorityDelegationStatus: SystemProgram.programId,
-        },
-        signers: [newUser1Key],
-      };
+      const followArgs = await getFollowArgs();
+      await program.rpc.followUser(
+        baseAuthorityAccount,
+        UserActionEnumValues.followUser,
+        { seed: handleBytesArray1, bump: handle1DerivedInfo.bumpSeed },
+        { seed: handleBytesArray2, bump: handle2DerivedInfo.bumpSeed },
+        followArgs
+      );
+
+      // Submit a tx where user 1 unfollows user 2
       const unfollowTx = await program.rpc.followUser(
         baseAuthorityAccount,
         UserActionEnumValues.unfollowUser,
@@ -331,23 +400,182 @@ describe("follows", function () {
         adminStorageKeypair.publicKey.toString()
       );
       expect(accountPubKeys[5]).to.equal(newUser1Key.publicKey.toString());
+
+      // Confirm the follow account is closed after unfollowing
+      await pollAccountBalance({
+        provider,
+        targetAccount: followArgs.accounts.follow,
+        targetBalance: 0,
+        maxRetries: 100,
+      });
+    });
+
+    it("unfollow user that is not followed should fail", async function () {
+      const followArgs = await getFollowArgs();
+      await expect(
+        program.rpc.followUser(
+          baseAuthorityAccount,
+          UserActionEnumValues.unfollowUser,
+          { seed: handleBytesArray1, bump: handle1DerivedInfo.bumpSeed },
+          { seed: handleBytesArray2, bump: handle2DerivedInfo.bumpSeed },
+          followArgs
+        )
+      )
+        .to.eventually.be.rejected.and.property("msg")
+        .to.include("This user is not followed.");
+    });
+
+    it("write batched follow and save", async function () {
+      const trackId = "1";
+      await writeSocialActions({
+        program,
+        baseAuthorityAccount,
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
+        userStorageAccountPDA: userStorageAccount1,
+        userHandle: {
+          seed: handleBytesArray1,
+          bump: handle1DerivedInfo.bumpSeed,
+        },
+        userAuthorityKeypair: newUser1Key,
+        userAuthorityDelegateAccountPDA: SystemProgram.programId,
+        authorityDelegationStatusAccountPDA: SystemProgram.programId,
+        actions: [
+          {
+            userAction: UserActions.followUser,
+            followeeUserStorageAccount: userStorageAccount2,
+            followeeHandle: {
+              seed: handleBytesArray2,
+              bump: handle2DerivedInfo.bumpSeed,
+            },
+          },
+          {
+            entitySocialAction: EntitySocialActions.addSave,
+            entityType: EntityTypesEnumValues.track,
+            id: trackId,
+          },
+        ],
+      });
+
+      const [followPDA] = await findFollowAddress(
+        program.programId,
+        userStorageAccount1,
+        userStorageAccount2
+      );
+      const follow = await program.account.follow.fetch(followPDA);
+      expect(follow.followee.toString()).to.equal(
+        userStorageAccount2.toString()
+      );
+
+      const [socialActionPDA] = await findSocialActionAddress({
+        programId: program.programId,
+        userStorageAccount: userStorageAccount1,
+        entityType: EntityTypesEnumValues.track,
+        entitySocialAction: EntitySocialActions.addSave,
+        id: trackId,
+      });
+      const socialAction = await program.account.entitySocialAction.fetch(
+        socialActionPDA
+      );
+      expect(socialAction.user.toString()).to.equal(
+        userStorageAccount1.toString()
+      );
+    });
+
+    it("write full follow and unfollow batches as a delegate", async function () {
+      // The validator runs the SBF build of the program, so the batches must
+      // also fit in the default compute budget
+      const userDelegate = await testCreateUserDelegate({
+        adminKeypair,
+        adminStorageKeypair: adminStorageKeypair,
+        program,
+        provider,
+      });
+      const followees = [];
+      for (let i = 0; i < MAX_SOCIAL_ACTION_BATCH_SIZE; i++) {
+        followees.push(
+          await createSolanaUser(program, provider, adminStorageKeypair)
+        );
+      }
+
+      for (const userAction of [
+        UserActions.followUser,
+        UserActions.unfollowUser,
+      ]) {
+        await writeSocialActions({
+          program,
+          baseAuthorityAccount: userDelegate.baseAuthorityAccount,
+          adminStoragePublicKey: adminStorageKeypair.publicKey,
+          userStorageAccountPDA: userDelegate.userAccountPDA,
+          userHandle: {
+            seed: userDelegate.userHandleBytesArray,
+            bump: userDelegate.userBumpSeed,
+          },
+          userAuthorityKeypair: userDelegate.userAuthorityDelegateKeypair,
+          userAuthorityDelegateAccountPDA:
+            userDelegate.userAuthorityDelegatePDA,
+          authorityDelegationStatusAccountPDA:
+            userDelegate.authorityDelegationStatusPDA,
+          actions: followees.map((followee) => ({
+            userAction,
+            followeeUserStorageAccount: followee.pda,
+            followeeHandle: {
+              seed: followee.handleBytesArray,
+              bump: followee.bumpSeed,
+            },
+          })),
+        });
+      }
+
+      for (const followee of followees) {
+        const [followPDA] = await findFollowAddress(
+          program.programId,
+          userDelegate.userAccountPDA,
+          followee.pda
+        );
+        await pollAccountBalance({
+          provider,
+          targetAccount: followPDA,
+          targetBalance: 0,
+          maxRetries: 100,
+        });
+      }
+    });
+
+    it("write oversized social action batch should fail", async function () {
+      const actions = [];
+      for (let i = 0; i <= MAX_SOCIAL_ACTION_BATCH_SIZE; i++) {
+        actions.push({
+          entitySocialAction: EntitySocialActions.addRepost,
+          entityType: EntityTypesEnumValues.track,
+          id: i.toString(),
+        });
+      }
+      await expect(
+        writeSocialActions({
+          program,
+          baseAuthorityAccount,
+          adminStoragePublicKey: adminStorageKeypair.publicKey,
+          userStorageAccountPDA: userStorageAccount1,
+          userHandle: {
+            seed: handleBytesArray1,
+            bump: handle1DerivedInfo.bumpSeed,
+          },
+          userAuthorityKeypair: newUser1Key,
+          userAuthorityDelegateAccountPDA: SystemProgram.programId,
+          authorityDelegationStatusAccountPDA: SystemProgram.programId,
+          actions,
+        })
+      )
+        .to.eventually.be.rejected.and.property("msg")
+        .to.include(
+          "The social action batch is empty, too large or missing accounts."
+        );
     });
 
     it("submit invalid follow action", async function () {
       // Submit a tx where user 1 follows user 2
       let expectedErrorFound = false;
-      const followArgs = {
-        accounts: {
-          audiusAdmin: adminStorageKeypair.publicKey,
-          payer: provider.wallet.publicKey,
-          authority: newUser1Key.publicKey,
-          followerUserStorage: userStorageAccount1,
-          followeeUserStorage: userStorageAccount2,
-          userAuthorityDelegate: SystemProgram.programId,
-          authorityDelegationStatus: SystemProgram.programId,
-        },
-        signers: [newUser1Key],
-      };
+      const followArgs = await getFollowArgs();
       try {
         // Use invalid enum value and confirm failure
         const txHash = await program.rpc.followUser(
@@ -369,18 +597,8 @@ describe("follows", function () {
       // Submit a tx where user 1 follows user 2
       // and user 2 account is not a PDA
       const wrongUserKeypair = anchor.web3.Keypair.generate();
-      const followArgs = {
-        accounts: {
-          audiusAdmin: adminStorageKeypair.publicKey,
-          payer: provider.wallet.publicKey,
-          authority: newUser1Key.publicKey,
-          followerUserStorage: userStorageAccount1,
-          followeeUserStorage: wrongUserKeypair.publicKey,
-          userAuthorityDelegate: SystemProgram.programId,
-          authorityDelegationStatus: SystemProgram.programId,
-        },
-        signers: [newUser1Key],
-      };
+      const followArgs = await getFollowArgs();
+      followArgs.accounts.followeeUserStorage = wrongUserKeypair.publicKey;
       let expectedErrorFound = false;
       let expectedErrorString =
         "The program expected this account to be already initialized";
@@ -96,7 +96,7 @@ describe("playlist-actions", function () {
   it("Delete save for a playlist", async function () {
     const user = await createSolanaUser(program, provider, adminStorageKeypair);
 
-    const tx = await deletePlaylistSave({
+    const socialActionArgs = {
       program,
       baseAuthorityAccount: user.authority,
       adminStoragePublicKey: adminStorageKeypair.publicKey,
@@ -107,7 +107,10 @@ describe("playlist-actions", function () {
       handleBytesArray: user.handleBytesArray,
       bumpSeed: user.bumpSeed,
       id: randomString(10),
-    });
+    };
+    await addPlaylistSave(socialActionArgs);
+
+    const tx = await deletePlaylistSave(socialActionArgs);
     const info = await getTransaction(provider, tx);
     const instructionCoder = program.coder.instruction as BorshInstructionCoder;
     const decodedInstruction = instructionCoder.decode(
@@ -198,7 +201,7 @@ describe("playlist-actions", function () {
   it("Delete repost for a playlist", async function () {
     const user = await createSolanaUser(program, provider, adminStorageKeypair);
 
-    const tx = await deletePlaylistRepost({
+    const socialActionArgs = {
       program,
       baseAuthorityAccount: user.authority,
       adminStoragePublicKey: adminStorageKeypair.publicKey,
@@ -209,7 +212,10 @@ describe("playlist-actions", function () {
       handleBytesArray: user.handleBytesArray,
       bumpSeed: user.bumpSeed,
       id: randomString(10),
-    });
+    };
+    await addPlaylistRepost(socialActionArgs);
+
+    const tx = await deletePlaylistRepost(socialActionArgs);
     const info = await getTransaction(provider, tx);
     const instructionCoder = program.coder.instruction as BorshInstructionCoder;
     const decodedInstruction = instructionCoder.decode(
@@ -228,4 +234,46 @@ describe("playlist-actions", function () {
       EntityTypesEnumValues.playlist
     );
   });
+
+  it("Deleting a playlist save that does not exist should fail", async function () {
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+
+    await expect(
+      deletePlaylistSave({
+        program,
+        baseAuthorityAccount: user.authority,
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
+        userStorageAccountPDA: user.pda,
+        userAuthorityDelegateAccountPDA: SystemProgram.programId,
+        authorityDelegationStatusAccountPDA: SystemProgram.programId,
+        userAuthorityKeypair: user.keypair,
+        handleBytesArray: user.handleBytesArray,
+        bumpSeed: user.bumpSeed,
+        id: randomString(10),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This social action has not been applied to the entity.");
+  });
+
+  it("Reposting a playlist twice should fail", async function () {
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const socialActionArgs = {
+      program,
+      baseAuthorityAccount: user.authority,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      userStorageAccountPDA: user.pda,
+      userAuthorityDelegateAccountPDA: SystemProgram.programId,
+      authorityDelegationStatusAccountPDA: SystemProgram.programId,
+      userAuthorityKeypair: user.keypair,
+      handleBytesArray: user.handleBytesArray,
+      bumpSeed: user.bumpSeed,
+      id: randomString(10),
+    };
+    await addPlaylistRepost(socialActionArgs);
+
+    await expect(addPlaylistRepost(socialActionArgs))
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This social action has already been applied to the entity.");
+  });
 });
@@ -2,12 +2,20 @@ import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
 import chai, { expect } from "chai";
 import chaiAsPromised from "chai-as-promised";
-import { initAdmin, updateAdmin } from "../lib/lib";
+import {
+  createPlaylist,
+  deletePlaylist,
+  findPlaylistContentsAddress,
+  initAdmin,
+  managePlaylistContents,
+  updateAdmin,
+} from "../lib/lib";
 import { findDerivedPair, randomCID, randomId } from "../lib/utils";
 import { AudiusData } from "../target/types/audius_data";
 import {
   testCreatePlaylist,
   createSolanaContentNode,
+  createSolanaUser,
   initTestConstants,
   testCreateUser,
   testInitUser,
@@ -42,14 +50,11 @@ describe("audius-data", function () {
         contentNodes["2"].spId.toNumber(),
         contentNodes["3"].spId.toNumber(),
       ],
-      replicaSetBumps: [
-        contentNodes["1"].seedBump.bump,
-        contentNodes["2"].seedBump.bump,
-        contentNodes["3"].seedBump.bump,
+      contentNodes: [
+        contentNodes["1"].pda,
+        contentNodes["2"].pda,
+        contentNodes["3"].pda,
       ],
-      cn1: contentNodes["1"].pda,
-      cn2: contentNodes["2"].pda,
-      cn3: contentNodes["3"].pda,
     };
   };
   it("Initializing admin account!", async function () {
@@ -127,17 +132,13 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testInitUserSolPubkey({
       provider,
       program,
-      message,
       ethPrivateKey: ethAccount.privateKey,
       newUserPublicKey: newUserKeypair.publicKey,
       newUserAcctPDA,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
     });
 
     const playlistMetadata = randomCID();
@@ -221,14 +222,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       baseAuthorityAccount,
       ethAccount,
       handleBytesArray,
@@ -299,14 +295,9 @@ describe("audius-data", function () {
     // New sol key that will be used to permission user updates
     const newUserKeypair = anchor.web3.Keypair.generate();
 
-    // Generate signed SECP instruction
-    // Message as the incoming public key
-    const message = newUserKeypair.publicKey.toBytes();
-
     await testCreateUser({
       provider,
       program,
-      message,
       baseAuthorityAccount,
       ethAccount,
       handleBytesArray,
@@ -369,4 +360,96 @@ describe("audius-data", function () {
     ]);
     console.log(`Created 3 playlists in ${Date.now() - start}ms`);
   });
+
+  it("appending, removing and moving playlist tracks", async function () {
+    await updateAdmin({
+      program,
+      isWriteEnabled: false,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const playlistArgs = {
+      provider,
+      program,
+      id: randomId(),
+      baseAuthorityAccount: user.authority,
+      handleBytesArray: user.handleBytesArray,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      bumpSeed: user.bumpSeed,
+      userAuthorityKeypair: user.keypair,
+      userStorageAccountPDA: user.pda,
+      userAuthorityDelegateAccountPDA: SystemProgram.programId,
+      authorityDelegationStatusAccountPDA: SystemProgram.programId,
+    };
+    await createPlaylist({ ...playlistArgs, metadata: randomCID() });
+
+    const [playlistContentsPDA] = await findPlaylistContentsAddress({
+      programId: program.programId,
+      baseAuthorityAccount: user.authority,
+      id: playlistArgs.id,
+    });
+    const getTrackIds = async () => {
+      const contents = await program.account.playlistContents.fetch(
+        playlistContentsPDA
+      );
+      return contents.trackIds.map((trackId) => trackId.toNumber());
+    };
+
+    // Appending past the initial capacity of 16 tracks grows the account
+    for (let trackId = 1; trackId <= 17; trackId++) {
+      await managePlaylistContents({
+        ...playlistArgs,
+        action: { append: { trackId: new anchor.BN(trackId) } },
+      });
+    }
+    const accountInfo = await provider.connection.getAccountInfo(
+      playlistContentsPDA
+    );
+    expect(accountInfo.data.length, "account size").to.equal(85 + 8 * 32);
+    const appended = Array.from({ length: 17 }, (_, i) => i + 1);
+    expect(await getTrackIds()).to.deep.equal(appended);
+
+    await managePlaylistContents({
+      ...playlistArgs,
+      action: { move: { from: 16, to: 0, trackId: new anchor.BN(17) } },
+    });
+    await managePlaylistContents({
+      ...playlistArgs,
+      action: { remove: { index: 1, trackId: new anchor.BN(1) } },
+    });
+    expect(await getTrackIds()).to.deep.equal([17, ...appended.slice(1, 16)]);
+
+    // Edits naming a track other than the one at the index are rejected
+    await expect(
+      managePlaylistContents({
+        ...playlistArgs,
+        action: { remove: { index: 0, trackId: new anchor.BN(1) } },
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("The playlist index is out of range");
+
+    // Only the playlist owner's authority may edit its tracks
+    await expect(
+      managePlaylistContents({
+        ...playlistArgs,
+        userAuthorityKeypair: anchor.web3.Keypair.generate(),
+        action: { append: { trackId: new anchor.BN(1) } },
+      })
+    ).to.eventually.be.rejected;
+
+    // Deleting the playlist closes its contents, a playlist re-created with the id starts empty
+    await deletePlaylist(playlistArgs);
+    const closed = await provider.connection.getAccountInfo(
+      playlistContentsPDA
+    );
+    expect(closed).to.equal(null);
+    await createPlaylist({ ...playlistArgs, metadata: randomCID() });
+    await managePlaylistContents({
+      ...playlistArgs,
+      action: { append: { trackId: new anchor.BN(5) } },
+    });
+    expect(await getTrackIds()).to.deep.equal([5]);
+  });
 });
@@ -26,6 +26,7 @@ import {
   deletePlaylist,
   updatePlaylist,
   updateAdmin,
+  DelegatePermissions,
 } from "../lib/lib";
 import { AudiusData } from "../target/types/audius_data";
 
@@ -73,17 +74,14 @@ export const testInitUser = async ({
   adminStorageKeypair,
   adminKeypair,
   replicaSet,
-  replicaSetBumps,
-  cn1,
-  cn2,
-  cn3,
+  contentNodes,
 }) => {
   const tx = await initUser({
     provider,
     program,
     ethAddress,
     replicaSet,
-    replicaSetBumps,
+    contentNodes,
     handleBytesArray,
     bumpSeed,
     metadata,
@@ -91,9 +89,6 @@ export const testInitUser = async ({
     baseAuthorityAccount,
     adminStorageAccount: adminStorageKeypair.publicKey,
     adminKeypair,
-    cn1,
-    cn2,
-    cn3,
   });
 
   const account = await program.account.user.fetch(userStorageAccount);
@@ -118,18 +113,22 @@ export const testInitUser = async ({
 export const testInitUserSolPubkey = async ({
   provider,
   program,
-  message,
+  message = undefined,
+  expiresAt = undefined,
   ethPrivateKey,
   newUserPublicKey,
   newUserAcctPDA,
+  adminStoragePublicKey,
 }) => {
   const tx = await initUserSolPubkey({
     provider,
     program,
     ethPrivateKey,
     message,
+    expiresAt,
     userSolPubkey: newUserPublicKey,
     userStorageAccount: newUserAcctPDA,
+    adminStoragePublicKey,
   });
 
   const { decodedInstruction, decodedData } = await getTransactionWithData(
@@ -154,7 +153,8 @@ export const testInitUserSolPubkey = async ({
 export const testCreateUser = async ({
   provider,
   program,
-  message,
+  message = undefined,
+  expiresAt = undefined,
   baseAuthorityAccount,
   ethAccount,
   handleBytesArray,
@@ -164,10 +164,7 @@ export const testCreateUser = async ({
   userStorageAccount,
   adminStoragePublicKey,
   replicaSet,
-  replicaSetBumps,
-  cn1,
-  cn2,
-  cn3,
+  contentNodes,
   userId,
 }) => {
   const tx = await createUser({
@@ -175,18 +172,16 @@ export const testCreateUser = async ({
     program,
     ethAccount,
     message,
+    expiresAt,
     handleBytesArray,
     bumpSeed,
     replicaSet,
-    replicaSetBumps,
+    contentNodes,
     metadata,
     userSolPubkey: newUserKeypair.publicKey,
     userStorageAccount,
     adminStoragePublicKey,
     baseAuthorityAccount,
-    cn1,
-    cn2,
-    cn3,
     userId,
   });
 
@@ -202,7 +197,7 @@ export const testCreateUser = async ({
   expect(decodedData.userBump).to.equal(bumpSeed);
   expect(decodedData.metadata).to.equal(metadata);
   expect(accountPubKeys[0]).to.equal(userStorageAccount.toString());
-  expect(accountPubKeys[5]).to.equal(adminStoragePublicKey.toString());
+  expect(accountPubKeys[2]).to.equal(adminStoragePublicKey.toString());
 
   const account = await program.account.user.fetch(userStorageAccount);
 
@@ -475,6 +470,15 @@ export const testCreateUserDelegate = async ({
   adminStorageKeypair,
   program,
   provider,
+  permissions = DelegatePermissions.all,
+  expiresAt = null,
+}: {
+  adminKeypair: anchor.web3.Keypair;
+  adminStorageKeypair: anchor.web3.Keypair;
+  program: Program<AudiusData>;
+  provider: anchor.Provider;
+  permissions?: number;
+  expiresAt?: anchor.BN | null;
 }) => {
   // disable admin writes
   await updateAdmin({
@@ -502,6 +506,7 @@ export const testCreateUserDelegate = async ({
 
   const initAuthorityDelegationStatusArgs = {
     accounts: {
+      admin: adminStorageKeypair.publicKey,
       delegateAuthority: userAuthorityDelegateKeypair.publicKey,
       authorityDelegationStatusPda: authorityDelegationStatusPDA,
       payer: provider.wallet.publicKey,
@@ -563,6 +568,8 @@ export const testCreateUserDelegate = async ({
     user.handleBytesArray,
     user.bumpSeed,
     userAuthorityDelegateKeypair.publicKey,
+    permissions,
+    expiresAt,
     addUserAuthorityDelegateArgs
   );
 
@@ -589,6 +596,10 @@ export const testCreateUserDelegate = async ({
   expect(
     addUserAuthorityDelegateData.userAuthorityDelegate.toString()
   ).to.equal(userAuthorityDelegateKeypair.publicKey.toString());
+  expect(addUserAuthorityDelegateData.permissions).to.equal(permissions);
+  expect(addUserAuthorityDelegateData.expiresAt?.toString()).to.equal(
+    expiresAt?.toString()
+  );
 
   return {
     baseAuthorityAccount: user.authority,
@@ -600,10 +611,20 @@ export const testCreateUserDelegate = async ({
     authorityDelegationStatusPDA,
     authorityDelegationStatusBump,
     userKeypair: user.keypair,
+    userEthAccount: user.ethAccount,
     userAuthorityDelegateKeypair,
   };
 };
 
+/// Resolve with the next event of the given name emitted by the program
+export const waitForEvent = (program: Program<AudiusData>, eventName: string) =>
+  new Promise<any>((resolve) => {
+    const listener = program.addEventListener(eventName, (event) => {
+      program.removeEventListener(listener);
+      resolve(event);
+    });
+  });
+
 export const pollAccountBalance = async (args: {
   provider: anchor.Provider;
   targetAccount: anchor.web3.PublicKey;
@@ -668,10 +689,6 @@ export const createSolanaUser = async (
   // New sol key that will be used to permission user updates
   const newUserKeypair = anchor.web3.Keypair.generate();
 
-  // Generate signed SECP instruction
-  // Message as the incoming public key
-  const message = newUserKeypair.publicKey.toBytes();
-
   const cn1 = await getContentNode(program, adminStorageKeypair.publicKey, "1");
   const cn2 = await getContentNode(program, adminStorageKeypair.publicKey, "2");
   const cn3 = await getContentNode(program, adminStorageKeypair.publicKey, "3");
@@ -681,7 +698,6 @@ export const createSolanaUser = async (
     program,
     ethAccount: testConsts.ethAccount,
     handleBytesArray: testConsts.handleBytesArray,
-    message,
     bumpSeed,
     metadata: testConsts.metadata,
     userSolPubkey: newUserKeypair.publicKey,
@@ -689,10 +705,7 @@ export const createSolanaUser = async (
     adminStoragePublicKey: adminStorageKeypair.publicKey,
     baseAuthorityAccount,
     replicaSet: [1, 2, 3],
-    replicaSetBumps: [cn1.bumpSeed, cn2.bumpSeed, cn3.bumpSeed],
-    cn1: cn1.derivedAddress,
-    cn2: cn2.derivedAddress,
-    cn3: cn3.derivedAddress,
+    contentNodes: [cn1.derivedAddress, cn2.derivedAddress, cn3.derivedAddress],
     userId: testConsts.userId,
   });
 
@@ -705,6 +718,7 @@ export const createSolanaUser = async (
     bumpSeed,
     keypair: newUserKeypair,
     authority: baseAuthorityAccount,
+    ethAccount: testConsts.ethAccount,
   };
 };
 
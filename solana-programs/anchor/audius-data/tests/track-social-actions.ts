@@ -11,6 +11,7 @@ import {
   EntitySocialActionEnumValues,
   EntityTypesEnumValues,
   deleteTrackRepost,
+  findSocialActionAddress,
 } from "../lib/lib";
 import { getTransaction, randomString } from "../lib/utils";
 import { AudiusData } from "../target/types/audius_data";
@@ -96,7 +97,7 @@ describe("track-actions", function () {
   it("Delete save for a track", async function () {
     const user = await createSolanaUser(program, provider, adminStorageKeypair);
 
-    const txHash = await deleteTrackSave({
+    const socialActionArgs = {
       program,
       baseAuthorityAccount: user.authority,
       adminStoragePublicKey: adminStorageKeypair.publicKey,
@@ -107,7 +108,10 @@ describe("track-actions", function () {
       handleBytesArray: user.handleBytesArray,
       bumpSeed: user.bumpSeed,
       id: randomString(10),
-    });
+    };
+    await addTrackSave(socialActionArgs);
+
+    const txHash = await deleteTrackSave(socialActionArgs);
     const info = await getTransaction(provider, txHash);
     const instructionCoder = program.coder.instruction as BorshInstructionCoder;
     const decodedInstruction = instructionCoder.decode(
@@ -129,6 +133,7 @@ describe("track-actions", function () {
 
   it("Save a newly created track", async function () {
     const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const trackId = randomString(10);
 
     const txHash = await addTrackSave({
       program,
@@ -140,8 +145,25 @@ describe("track-actions", function () {
       userAuthorityKeypair: user.keypair,
       handleBytesArray: user.handleBytesArray,
       bumpSeed: user.bumpSeed,
-      id: randomString(10),
+      id: trackId,
     });
+
+    // Confirm the save is recorded on chain
+    const [socialActionPDA] = await findSocialActionAddress({
+      programId: program.programId,
+      userStorageAccount: user.pda,
+      entityType: EntityTypesEnumValues.track,
+      entitySocialAction: EntitySocialActionEnumValues.addSave,
+      id: trackId,
+    });
+    const socialAction = await program.account.entitySocialAction.fetch(
+      socialActionPDA
+    );
+    expect(socialAction.user.toString()).to.equal(user.pda.toString());
+    expect(socialAction.entityType).to.deep.equal(EntityTypesEnumValues.track);
+    expect(socialAction.payer.toString()).to.equal(
+      provider.wallet.publicKey.toString()
+    );
     const info = await getTransaction(provider, txHash);
     const instructionCoder = program.coder.instruction as BorshInstructionCoder;
     const decodedInstruction = instructionCoder.decode(
@@ -240,7 +262,7 @@ describe("track-actions", function () {
   it("Delete repost for a track", async function () {
     const user = await createSolanaUser(program, provider, adminStorageKeypair);
 
-    const txHash = await deleteTrackRepost({
+    const socialActionArgs = {
       program,
       baseAuthorityAccount: user.authority,
       adminStoragePublicKey: adminStorageKeypair.publicKey,
@@ -251,7 +273,10 @@ describe("track-actions", function () {
       handleBytesArray: user.handleBytesArray,
       bumpSeed: user.bumpSeed,
       id: randomString(10),
-    });
+    };
+    await addTrackRepost(socialActionArgs);
+
+    const txHash = await deleteTrackRepost(socialActionArgs);
 
     const info = await getTransaction(provider, txHash);
     const instructionCoder = program.coder.instruction as BorshInstructionCoder;
@@ -271,4 +296,46 @@ describe("track-actions", function () {
       EntityTypesEnumValues.track
     );
   });
+
+  it("Deleting a track save that does not exist should fail", async function () {
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+
+    await expect(
+      deleteTrackSave({
+        program,
+        baseAuthorityAccount: user.authority,
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
+        userStorageAccountPDA: user.pda,
+        userAuthorityDelegateAccountPDA: SystemProgram.programId,
+        authorityDelegationStatusAccountPDA: SystemProgram.programId,
+        userAuthorityKeypair: user.keypair,
+        handleBytesArray: user.handleBytesArray,
+        bumpSeed: user.bumpSeed,
+        id: randomString(10),
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This social action has not been applied to the entity.");
+  });
+
+  it("Reposting a track twice should fail", async function () {
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const socialActionArgs = {
+      program,
+      baseAuthorityAccount: user.authority,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      userStorageAccountPDA: user.pda,
+      userAuthorityDelegateAccountPDA: SystemProgram.programId,
+      authorityDelegationStatusAccountPDA: SystemProgram.programId,
+      userAuthorityKeypair: user.keypair,
+      handleBytesArray: user.handleBytesArray,
+      bumpSeed: user.bumpSeed,
+      id: randomString(10),
+    };
+    await addTrackRepost(socialActionArgs);
+
+    await expect(addTrackRepost(socialActionArgs))
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("This social action has already been applied to the entity.");
+  });
 });
@@ -9,8 +9,17 @@ import {
   publicDeleteContentNode,
   updateUserReplicaSet,
   updateAdmin,
+  updateMaxReplicaSetSize,
+  updateProposerThreshold,
+  toProposerAccounts,
+  updateContentNodeAuthority,
+  updateContentNodeProfile,
 } from "../lib/lib";
-import { findDerivedPair } from "../lib/utils";
+import {
+  findDerivedPair,
+  getReplicaSetContentNodes,
+  randomCID,
+} from "../lib/utils";
 import { AudiusData } from "../target/types/audius_data";
 import {
   createSolanaContentNode,
@@ -130,21 +139,23 @@ describe("replicaSet", function () {
       spID,
       contentNodeAuthority: authority.publicKey,
       ownerEthAddress: ownerEth.address,
-      proposer1: {
-        pda: cn2.pda,
-        authority: cn2.authority,
-        seedBump: cn2.seedBump,
-      },
-      proposer2: {
-        pda: cn4.pda,
-        authority: cn4.authority,
-        seedBump: cn4.seedBump,
-      },
-      proposer3: {
-        pda: cn3.pda,
-        authority: cn3.authority,
-        seedBump: cn3.seedBump,
-      },
+      proposers: [
+        {
+          pda: cn2.pda,
+          authority: cn2.authority,
+          seedBump: cn2.seedBump,
+        },
+        {
+          pda: cn4.pda,
+          authority: cn4.authority,
+          seedBump: cn4.seedBump,
+        },
+        {
+          pda: cn3.pda,
+          authority: cn3.authority,
+          seedBump: cn3.seedBump,
+        },
+      ],
     });
 
     const account = await program.account.contentNode.fetch(derivedAddress);
@@ -198,9 +209,7 @@ describe("replicaSet", function () {
       const tx = new anchor.web3.Transaction({ recentBlockhash });
       const txInstr = program.instruction.publicCreateOrUpdateContentNode(
         baseAuthorityAccount,
-        { seed: [...cn6.seedBump.seed], bump: cn6.seedBump.bump },
-        { seed: [...cn7.seedBump.seed], bump: cn7.seedBump.bump },
-        { seed: [...cn8.seedBump.seed], bump: cn8.seedBump.bump },
+        [6, 7, 8],
         spID.toNumber(),
         authority.publicKey,
         [...anchor.utils.bytes.hex.decode(ownerEth.address)],
@@ -210,13 +219,8 @@ describe("replicaSet", function () {
             payer: provider.wallet.publicKey,
             contentNode: derivedAddress,
             systemProgram: SystemProgram.programId,
-            proposer1: cn6.pda,
-            proposer1Authority: cn6.authority.publicKey,
-            proposer2: cn7.pda,
-            proposer2Authority: cn7.authority.publicKey,
-            proposer3: cn8.pda,
-            proposer3Authority: cn8.authority.publicKey,
           },
+          remainingAccounts: toProposerAccounts([cn6, cn7, cn8]),
         }
       );
       tx.add(txInstr);
@@ -273,6 +277,80 @@ describe("replicaSet", function () {
     ).to.equal(ownerEth.address.toLowerCase());
   });
 
+  it("Creates Content Node with the admin proposer threshold", async function () {
+    const cn2 = contentNodes["2"];
+    const cn3 = contentNodes["3"];
+
+    const spID = new anchor.BN(10);
+    const ownerEth = EthWeb3.eth.accounts.create();
+    const { baseAuthorityAccount, derivedAddress } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.concat([Buffer.from("sp_id", "utf8"), spID.toBuffer("le", 2)])
+    );
+    const authority = anchor.web3.Keypair.generate();
+    const createArgs = {
+      provider,
+      program,
+      baseAuthorityAccount,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      contentNodeAcct: derivedAddress,
+      spID,
+      contentNodeAuthority: authority.publicKey,
+      ownerEthAddress: ownerEth.address,
+    };
+
+    // Default threshold is 3
+    await expect(
+      publicCreateOrUpdateContentNode({ ...createArgs, proposers: [cn2, cn3] })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("Not enough content node proposers.");
+
+    // Only the admin authority may update the threshold
+    await expect(
+      updateProposerThreshold({
+        program,
+        proposerThreshold: 2,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        adminAuthorityKeypair: cn2.authority,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await updateProposerThreshold({
+      program,
+      proposerThreshold: 2,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+
+    // Proposers must have unique owner eth addresses
+    await expect(
+      publicCreateOrUpdateContentNode({ ...createArgs, proposers: [cn2, cn2] })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await publicCreateOrUpdateContentNode({
+      ...createArgs,
+      proposers: [cn2, cn3],
+    });
+    const account = await program.account.contentNode.fetch(derivedAddress);
+    expect(
+      account.authority.toString(),
+      "content node authority set correctly"
+    ).to.equal(authority.publicKey.toString());
+
+    await updateProposerThreshold({
+      program,
+      proposerThreshold: 3,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+  });
+
   it("Updates a Content Node with the proposers", async function () {
     const cn2 = contentNodes["2"];
     const cn3 = contentNodes["3"];
@@ -301,21 +379,23 @@ describe("replicaSet", function () {
       contentNodeAcct: cnToUpdate.pda,
       spID: cnToUpdate.spId,
       ownerEthAddress: cnToUpdate.ownerEthAddress,
-      proposer1: {
-        pda: cn4.pda,
-        authority: cn4.authority,
-        seedBump: cn4.seedBump,
-      },
-      proposer2: {
-        pda: cn2.pda,
-        authority: cn2.authority,
-        seedBump: cn2.seedBump,
-      },
-      proposer3: {
-        pda: cn3.pda,
-        authority: cn3.authority,
-        seedBump: cn3.seedBump,
-      },
+      proposers: [
+        {
+          pda: cn4.pda,
+          authority: cn4.authority,
+          seedBump: cn4.seedBump,
+        },
+        {
+          pda: cn2.pda,
+          authority: cn2.authority,
+          seedBump: cn2.seedBump,
+        },
+        {
+          pda: cn3.pda,
+          authority: cn3.authority,
+          seedBump: cn3.seedBump,
+        },
+      ],
     });
 
     const account = await program.account.contentNode.fetch(cnToUpdate.pda);
@@ -344,15 +424,54 @@ describe("replicaSet", function () {
         seed
       );
 
-    const initAudiusAdminBalance = await provider.connection.getBalance(
-      adminKeypair.publicKey
+    const refundAccount = cn4.authority.publicKey;
+    const initialRefundBalance = await provider.connection.getBalance(
+      refundAccount
     );
 
+    // The rent can only be refunded to the authority of the deleted content node
+    await expect(
+      publicDeleteContentNode({
+        provider,
+        program,
+        baseAuthorityAccount,
+        refundAccount: anchor.web3.Keypair.generate().publicKey,
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
+        cnDelete: {
+          pda: derivedAddress,
+          authority: cnToDelete.authority,
+          seedBump: {
+            seed,
+            bump: bumpSeed,
+          },
+        },
+        proposers: [
+          {
+            pda: cn4.pda,
+            authority: cn4.authority,
+            seedBump: cn4.seedBump,
+          },
+          {
+            pda: cn2.pda,
+            authority: cn2.authority,
+            seedBump: cn2.seedBump,
+          },
+          {
+            pda: cn3.pda,
+            authority: cn3.authority,
+            seedBump: cn3.seedBump,
+          },
+        ],
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
     await publicDeleteContentNode({
       provider,
       program,
       baseAuthorityAccount,
-      adminAuthorityPublicKey: adminKeypair.publicKey,
+      refundAccount,
       adminStoragePublicKey: adminStorageKeypair.publicKey,
       cnDelete: {
         pda: derivedAddress,
@@ -362,39 +481,36 @@ describe("replicaSet", function () {
           bump: bumpSeed,
         },
       },
-      proposer1: {
-        pda: cn4.pda,
-        authority: cn4.authority,
-        seedBump: cn4.seedBump,
-      },
-      proposer2: {
-        pda: cn2.pda,
-        authority: cn2.authority,
-        seedBump: cn2.seedBump,
-      },
-      proposer3: {
-        pda: cn3.pda,
-        authority: cn3.authority,
-        seedBump: cn3.seedBump,
-      },
+      proposers: [
+        {
+          pda: cn4.pda,
+          authority: cn4.authority,
+          seedBump: cn4.seedBump,
+        },
+        {
+          pda: cn2.pda,
+          authority: cn2.authority,
+          seedBump: cn2.seedBump,
+        },
+        {
+          pda: cn3.pda,
+          authority: cn3.authority,
+          seedBump: cn3.seedBump,
+        },
+      ],
     });
-    // Confirm that the account is zero'd out
+    // Confirm that the content node rent is refunded to its authority
     // Note that there appears to be a delay in the propagation, hence the retries
-    let updatedAudiusAdminBalance = initAudiusAdminBalance;
+    let refundBalance = initialRefundBalance;
     let retries = 20;
-    while (
-      updatedAudiusAdminBalance === initAudiusAdminBalance &&
-      retries > 0
-    ) {
-      updatedAudiusAdminBalance = await provider.connection.getBalance(
-        adminKeypair.publicKey
-      );
+    while (refundBalance === initialRefundBalance && retries > 0) {
+      refundBalance = await provider.connection.getBalance(refundAccount);
       await new Promise((resolve) => setTimeout(resolve, 100));
       retries--;
     }
 
-    if (initAudiusAdminBalance === updatedAudiusAdminBalance) {
-      throw new Error("Failed to deallocate track");
+    if (refundBalance === initialRefundBalance) {
+      throw new Error("Failed to refund content node");
     }
 
     try {
@@ -407,6 +523,34 @@ describe("replicaSet", function () {
     }
   });
 
+  it("Fail on update to a replica set with a deleted content node", async function () {
+    const cn2 = contentNodes["2"];
+    const cn3 = contentNodes["3"];
+    const cn4 = contentNodes["4"];
+    const { baseAuthorityAccount } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from([])
+    );
+
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    await expect(
+      updateUserReplicaSet({
+        provider,
+        program,
+        baseAuthorityAccount,
+        userAcct: user.pda,
+        userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
+        adminStoragePublicKey: adminStorageKeypair.publicKey,
+        replicaSet: [2, 3, 4],
+        contentNodes: [cn2.pda, cn3.pda, cn4.pda],
+        contentNodeAuthority: user.keypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("No content node with this sp_id exists.");
+  });
+
   it("Updates a user replica set with the proposers", async function () {
     const cn2 = contentNodes["2"];
     const cn3 = contentNodes["3"];
@@ -432,15 +576,8 @@ describe("replicaSet", function () {
       userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
       adminStoragePublicKey: adminStorageKeypair.publicKey,
       replicaSet: [2, 3, 6],
-      replicaSetBumps: [
-        cn2.seedBump.bump,
-        cn3.seedBump.bump,
-        cn6.seedBump.bump,
-      ],
+      contentNodes: [cn2.pda, cn3.pda, cn6.pda],
       contentNodeAuthority: cn2.authority,
-      cn1: cn2.pda,
-      cn2: cn3.pda,
-      cn3: cn6.pda,
     });
     const updatedUser = await program.account.user.fetch(user.pda);
     expect(
@@ -474,15 +611,8 @@ describe("replicaSet", function () {
       userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
       adminStoragePublicKey: adminStorageKeypair.publicKey,
       replicaSet: [6, 7, 8],
-      replicaSetBumps: [
-        cn6.seedBump.bump,
-        cn7.seedBump.bump,
-        cn8.seedBump.bump,
-      ],
+      contentNodes: [cn6.pda, cn7.pda, cn8.pda],
       contentNodeAuthority: user.keypair,
-      cn1: cn6.pda,
-      cn2: cn7.pda,
-      cn3: cn8.pda,
     });
     const updatedUser = await program.account.user.fetch(user.pda);
     expect(
@@ -518,18 +648,240 @@ describe("replicaSet", function () {
         userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
         adminStoragePublicKey: adminStorageKeypair.publicKey,
         replicaSet: [2, 7, 8],
-        replicaSetBumps: [
-          cn2.seedBump.bump,
-          cn7.seedBump.bump,
-          cn8.seedBump.bump,
-        ],
+        contentNodes: [cn2.pda, cn7.pda, cn8.pda],
         contentNodeAuthority: cn7.authority,
-        cn1: cn2.pda,
-        cn2: cn7.pda,
-        cn3: cn8.pda,
       })
     )
       .to.eventually.be.rejected.and.property("msg")
       .to.include(`You are not authorized to perform this action.`);
   });
+
+  it("Updates a user replica set to a single content node", async function () {
+    const cn7 = contentNodes["7"];
+    const { baseAuthorityAccount } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from([])
+    );
+
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const initialSize = (await provider.connection.getAccountInfo(user.pda))
+      .data.length;
+    await updateUserReplicaSet({
+      provider,
+      program,
+      baseAuthorityAccount,
+      userAcct: user.pda,
+      userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      replicaSet: [7],
+      contentNodes: [cn7.pda],
+      contentNodeAuthority: user.keypair,
+    });
+    const updatedUser = await program.account.user.fetch(user.pda);
+    expect(updatedUser.replicaSet).to.deep.equal([7]);
+    const updatedSize = (await provider.connection.getAccountInfo(user.pda))
+      .data.length;
+    expect(updatedSize, "user account is not shrunk").to.equal(initialSize);
+  });
+
+  it("Updates a user replica set up to the admin maximum", async function () {
+    const { baseAuthorityAccount } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from([])
+    );
+    const replicaSet = [2, 3, 6, 7, 8];
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const updateArgs = {
+      provider,
+      program,
+      baseAuthorityAccount,
+      userAcct: user.pda,
+      userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      replicaSet,
+      contentNodes: replicaSet.map((spId) => contentNodes[spId.toString()].pda),
+      contentNodeAuthority: user.keypair,
+    };
+
+    // Default maximum is 3
+    await expect(updateUserReplicaSet(updateArgs))
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("Invalid replica set.");
+
+    // Only the admin authority may raise the maximum
+    await expect(
+      updateMaxReplicaSetSize({
+        program,
+        maxReplicaSetSize: 5,
+        adminStorageAccount: adminStorageKeypair.publicKey,
+        adminAuthorityKeypair: user.keypair,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await updateMaxReplicaSetSize({
+      program,
+      maxReplicaSetSize: 5,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+    const adminAccount = await program.account.audiusAdmin.fetch(
+      adminStorageKeypair.publicKey
+    );
+    expect(adminAccount.maxReplicaSetSize).to.equal(5);
+
+    await updateUserReplicaSet(updateArgs);
+    const updatedUser = await program.account.user.fetch(user.pda);
+    expect(updatedUser.replicaSet).to.deep.equal(replicaSet);
+
+    await updateMaxReplicaSetSize({
+      program,
+      maxReplicaSetSize: 3,
+      adminStorageAccount: adminStorageKeypair.publicKey,
+      adminAuthorityKeypair: adminKeypair,
+    });
+  });
+
+  it("Fail on update to an invalid replica set", async function () {
+    const cn2 = contentNodes["2"];
+    const cn3 = contentNodes["3"];
+    const cn7 = contentNodes["7"];
+    const { baseAuthorityAccount } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      Buffer.from([])
+    );
+    const user = await createSolanaUser(program, provider, adminStorageKeypair);
+    const updateArgs = {
+      provider,
+      program,
+      baseAuthorityAccount,
+      userAcct: user.pda,
+      userHandle: { seed: [...user.handleBytesArray], bump: user.bumpSeed },
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      contentNodeAuthority: user.keypair,
+    };
+
+    // Repeated content node
+    await expect(
+      updateUserReplicaSet({
+        ...updateArgs,
+        replicaSet: [2, 2, 3],
+        contentNodes: [cn2.pda, cn2.pda, cn3.pda],
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("Invalid replica set.");
+
+    // Missing content node account
+    await expect(
+      updateUserReplicaSet({
+        ...updateArgs,
+        replicaSet: [2, 3, 7],
+        contentNodes: [cn2.pda, cn3.pda],
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("Invalid replica set.");
+
+    // Content node account does not match the sp_id
+    await expect(
+      updateUserReplicaSet({
+        ...updateArgs,
+        replicaSet: [2, 3, 7],
+        contentNodes: [cn2.pda, cn7.pda, cn3.pda],
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("The expected program derived address was not found.");
+  });
+
+  it("Rotates a Content Node authority and publishes its profile", async function () {
+    const cn8 = await createSolanaContentNode({
+      program,
+      provider,
+      adminKeypair,
+      adminStorageKeypair,
+      spId: new anchor.BN(8),
+    });
+    const seed = Buffer.concat([
+      Buffer.from("sp_id", "utf8"),
+      cn8.spId.toBuffer("le", 2),
+    ]);
+    const { baseAuthorityAccount } = await findDerivedPair(
+      program.programId,
+      adminStorageKeypair.publicKey,
+      seed
+    );
+    const args = {
+      program,
+      baseAuthorityAccount,
+      adminStoragePublicKey: adminStorageKeypair.publicKey,
+      contentNodeAcct: cn8.pda,
+      spID: cn8.spId,
+    };
+    const newAuthority = anchor.web3.Keypair.generate();
+    const metadata = randomCID();
+
+    // Only the current authority may rotate the authority
+    await expect(
+      updateContentNodeAuthority({
+        ...args,
+        contentNodeAuthority: newAuthority,
+        newAuthority: newAuthority.publicKey,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await updateContentNodeAuthority({
+      ...args,
+      contentNodeAuthority: cn8.authority,
+      newAuthority: newAuthority.publicKey,
+    });
+
+    // The previous authority can no longer update the content node
+    await expect(
+      updateContentNodeProfile({
+        ...args,
+        contentNodeAuthority: cn8.authority,
+        endpoint: "https://cn8.audius.co",
+        metadata,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("You are not authorized to perform this action.");
+
+    await expect(
+      updateContentNodeProfile({
+        ...args,
+        contentNodeAuthority: newAuthority,
+        endpoint: "cn8.audius.co",
+        metadata,
+      })
+    )
+      .to.eventually.be.rejected.and.property("msg")
+      .to.include("The endpoint is not an http or https URL or is too long.");
+
+    await updateContentNodeProfile({
+      ...args,
+      contentNodeAuthority: newAuthority,
+      endpoint: "https://cn8.audius.co",
+      metadata,
+    });
+
+    const [contentNode] = await getReplicaSetContentNodes(
+      program,
+      adminStorageKeypair.publicKey,
+      [8]
+    );
+    expect(contentNode.authority.toBase58()).to.equal(
+      newAuthority.publicKey.toBase58()
+    );
+    expect(contentNode.endpoint).to.equal("https://cn8.audius.co");
+    expect(contentNode.metadata).to.equal(metadata);
+  });
 });